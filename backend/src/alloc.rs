@@ -0,0 +1,137 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use event::AllocStats;
+
+// Set once `TracedAlloc` has seen an allocation, i.e. once it's the global allocator.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Running totals for this thread.  This can't touch `TRACER_STATE`, which may be mid-borrow
+    // (or mid-initialization) when we're called, so spans instead diff these when they go on and
+    // off CPU.  A const `Cell` never allocates or registers a destructor, so it's safe to use
+    // from inside the allocator.
+    static ALLOCATED: Cell<AllocStats> = const { Cell::new(AllocStats { count: 0, bytes: 0 }) };
+    // How much of `ALLOCATED` spans on this thread have already claimed, so that a span doesn't
+    // also count what the spans inside it allocated.
+    static REPORTED: Cell<AllocStats> = const { Cell::new(AllocStats { count: 0, bytes: 0 }) };
+}
+
+/// Where this thread's counts were when a span went on CPU.
+#[derive(Copy, Clone, Debug)]
+pub struct AllocMark {
+    allocated: AllocStats,
+    reported: AllocStats,
+}
+
+/// Global allocator wrapper that counts allocations made by each span.  Counts show up on
+/// `SyncEnd` and `AsyncOffCPU` events, and leave out anything allocated by spans running inside
+/// that one; they're `None` unless this is installed:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: TracedAlloc = TracedAlloc::system();
+/// ```
+///
+/// Reallocations count as one allocation of their new size.  Frees aren't tracked.
+pub struct TracedAlloc<A = System> {
+    inner: A,
+}
+
+impl TracedAlloc<System> {
+    pub const fn system() -> Self {
+        TracedAlloc { inner: System }
+    }
+}
+
+impl<A> TracedAlloc<A> {
+    pub const fn new(inner: A) -> Self {
+        TracedAlloc { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TracedAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        self.inner.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout)
+    }
+}
+
+fn record(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+    // Fails during thread teardown, when those allocations don't belong to any span anyway.
+    let _ = ALLOCATED.try_with(|allocated| {
+        let mut stats = allocated.get();
+        stats.count += 1;
+        stats.bytes += size as u64;
+        allocated.set(stats);
+    });
+}
+
+// This thread's totals so far, or `None` if `TracedAlloc` isn't installed.
+fn allocated() -> Option<AllocStats> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    ALLOCATED.try_with(|allocated| allocated.get()).ok()
+}
+
+fn diff(now: AllocStats, start: AllocStats) -> AllocStats {
+    AllocStats {
+        count: now.count.wrapping_sub(start.count),
+        bytes: now.bytes.wrapping_sub(start.bytes),
+    }
+}
+
+/// Mark the start of a span, or `None` if `TracedAlloc` isn't installed.
+pub fn snapshot() -> Option<AllocMark> {
+    Some(AllocMark {
+        allocated: allocated()?,
+        reported: REPORTED.try_with(|reported| reported.get()).ok()?,
+    })
+}
+
+/// Allocations made on this thread since `start` was taken, less those already claimed by spans
+/// that started and ended in the meantime.  Those spans were nested in this one, so this is what
+/// the span allocated itself.
+pub fn since(start: Option<AllocMark>) -> Option<AllocStats> {
+    let (start, now) = (start?, snapshot()?);
+    let total = diff(now.allocated, start.allocated);
+    let nested = diff(now.reported, start.reported);
+    // Saturating, since spans exited out of order (see `SpanHandle`) don't nest cleanly.
+    let own = AllocStats {
+        count: total.count.saturating_sub(nested.count),
+        bytes: total.bytes.saturating_sub(nested.bytes),
+    };
+    let _ = REPORTED.try_with(|reported| reported.set(AllocStats {
+        count: now.reported.count.wrapping_add(own.count),
+        bytes: now.reported.bytes.wrapping_add(own.bytes),
+    }));
+    Some(own)
+}
+
+/// Run `f` without counting its allocations, e.g. the tracer's own while logging an event.
+pub fn untracked<R, F: FnOnce() -> R>(f: F) -> R {
+    let before = allocated();
+    let result = f();
+    if let Some(before) = before {
+        let _ = ALLOCATED.try_with(|allocated| allocated.set(before));
+    }
+    result
+}
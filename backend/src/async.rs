@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::mem;
 use std::ops::{
@@ -5,6 +6,7 @@ use std::ops::{
     DerefMut,
 };
 use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
 use futures::{
     Async,
     Future,
@@ -16,8 +18,10 @@ use futures::task::{
 };
 use futures::executor::{Notify, NotifyHandle, spawn};
 use serde_json;
-use event::{AsyncOutcome, SpanId, TraceEvent};
-use state::TRACER_STATE;
+use alloc;
+use context::SpanContext;
+use event::{AsyncOutcome, Name, SpanId, TraceEvent};
+use state::{self, TRACER_STATE};
 
 /// Atomic slot of a single parked task.  Note that this only parks at most one
 /// task: If your data-structure needs to wakeup potentially many threads, using
@@ -47,28 +51,38 @@ impl AtomicTask {
 }
 
 pub trait TraceFuture: Future + Sized where Self::Error : Debug {
-    fn traced<S: Into<String>>(self, name: S) -> TracedFuture<Self> {
+    fn traced<S: Into<Cow<'static, str>>>(self, name: S) -> TracedFuture<Self> {
         self.with_metadata(name, serde_json::Value::Null)
     }
 
-    fn with_metadata<S: Into<String>>(self, name: S, meta: serde_json::Value) -> TracedFuture<Self> {
-        TracedFuture {
-            state: TraceState::Created { name: name.into(), metadata: meta },
-            inner: self,
-        }
+    fn with_metadata<S: Into<Cow<'static, str>>>(self, name: S, meta: serde_json::Value) -> TracedFuture<Self> {
+        TracedFuture::new(self, name.into(), meta, None, None)
+    }
+
+    /// Like `traced`, but also records `remote` as the span's parent in another process.
+    fn traced_remote<S: Into<Cow<'static, str>>>(self, name: S, remote: SpanContext) -> TracedFuture<Self> {
+        TracedFuture::new(self, name.into(), serde_json::Value::Null, Some(remote), None)
     }
 }
 impl<F: Future + Sized> TraceFuture for F where F::Error : Debug {}
 
 enum TraceState {
     Created {
-        name: String,
+        name: Cow<'static, str>,
         metadata: serde_json::Value,
+        remote: Option<SpanContext>,
+        // Explicit parent, instead of whichever span first polls us.
+        parent: Option<SpanId>,
     },
     Executing {
         parent: SpanId,
         id: SpanId,
+        // Span and thread that polled us last, so we can tell when we've been handed off.
+        poller: Option<SpanId>,
+        thread: ThreadId,
     },
+    // Started on a thread that isn't being traced.
+    Untraced,
     Resolved,
     Poisoned,
 }
@@ -76,6 +90,8 @@ enum TraceState {
 pub struct TracedFuture<F> {
     state: TraceState,
     inner: F,
+    // Wakeups from threads that can't log them, emitted from here on our next poll.
+    untraced_wakeups: Arc<Mutex<Vec<TraceEvent>>>,
 }
 
 impl<F> Deref for TracedFuture<F> {
@@ -92,6 +108,24 @@ impl<F> DerefMut for TracedFuture<F> {
 }
 
 impl<F> TracedFuture<F> {
+    fn new(inner: F,
+           name: Cow<'static, str>,
+           metadata: serde_json::Value,
+           remote: Option<SpanContext>,
+           parent: Option<SpanId>) -> Self {
+        TracedFuture {
+            state: TraceState::Created { name, metadata, remote, parent },
+            inner,
+            untraced_wakeups: Arc::default(),
+        }
+    }
+
+    /// Trace `inner` as a child of `parent`, rather than of the span that first polls it.  Handy
+    /// when the future is created in one place but run somewhere else entirely.
+    pub fn with_parent<S: Into<Cow<'static, str>>>(inner: F, name: S, parent: SpanId) -> Self {
+        Self::new(inner, name.into(), serde_json::Value::Null, None, Some(parent))
+    }
+
     pub fn into_inner(self) -> F {
         self.inner
     }
@@ -102,39 +136,93 @@ impl<F: Future> Future for TracedFuture<F> where F::Error : Debug {
     type Error = F::Error;
 
     fn poll(&mut self) -> Poll<F::Item, F::Error> {
+        match self.state {
+            // Polled after completing, or after tracing panicked mid-poll: there's no span left to
+            // attribute this to, so leave it to the inner future.
+            TraceState::Untraced | TraceState::Resolved | TraceState::Poisoned => return self.inner.poll(),
+            _ => (),
+        }
         TRACER_STATE.with(|c| {
-            let (parent_id, span_id) = {
+            let (prev_span, span_id) = {
                 let mut st = c.borrow_mut();
-                let (parent_id, span_id) = match mem::replace(&mut self.state, TraceState::Poisoned) {
+                let thread_parent = match self.state {
+                    TraceState::Created { .. } => st.parent_span(),
+                    _ => None,
+                };
+                let prev_span = st.current_span;
+                let span_id = match mem::replace(&mut self.state, TraceState::Poisoned) {
                     // First poll!  Let's set up our execution state.
-                    TraceState::Created { name, metadata } => {
+                    TraceState::Created { name, metadata, remote, parent } => {
+                        // With a parent of our own, we only need somewhere to log to, so tasks
+                        // are traced even on worker threads the `UntracedPolicy` skips.
+                        let parent_id = match parent {
+                            Some(parent) if st.ensure_writer() => Some(parent),
+                            Some(..) => None,
+                            None => thread_parent,
+                        };
+                        let parent_id = match parent_id {
+                            Some(parent_id) => parent_id,
+                            None => {
+                                drop(st);
+                                self.state = TraceState::Untraced;
+                                return self.inner.poll();
+                            },
+                        };
                         let span_id = SpanId::new();
-                        let parent_id = st.current_span.expect("Missing parent span");
 
                         let event = TraceEvent::AsyncStart {
-                            name: name,
+                            name: Name::Str(name),
                             id: span_id,
-                            parent_id: parent_id,
+                            parent_id,
                             ts: st.now(),
-                            metadata: metadata,
+                            metadata,
                         };
                         st.emit(event);
+                        if let Some(remote) = remote {
+                            let event = TraceEvent::RemoteParent {
+                                id: span_id,
+                                parent: remote,
+                                ts: st.now(),
+                            };
+                            st.emit(event);
+                        }
 
                         self.state = TraceState::Executing {
                             parent: parent_id,
                             id: span_id,
+                            poller: prev_span,
+                            thread: thread::current().id(),
                         };
-                        (parent_id, span_id)
+                        span_id
                     },
-                    TraceState::Executing { parent, id } => {
-                        assert_eq!(st.current_span, Some(parent), "Parent span changed across execution");
-                        self.state = TraceState::Executing { parent, id };
-                        (parent, id)
+                    TraceState::Executing { parent, id, poller, thread } => {
+                        // Work-stealing executors and futures passed between tasks poll us from
+                        // elsewhere.  Note the new poller, but keep our original parent.
+                        let current_thread = thread::current().id();
+                        if prev_span != poller || current_thread != thread {
+                            let event = TraceEvent::AsyncMigrate {
+                                id,
+                                poller: prev_span,
+                                thread: state::thread_name(),
+                                ts: st.now(),
+                            };
+                            st.emit(event);
+                        }
+                        self.state = TraceState::Executing {
+                            parent,
+                            id,
+                            poller: prev_span,
+                            thread: current_thread,
+                        };
+                        id
                     },
-                    TraceState::Resolved => panic!("Polled after resolved"),
-                    TraceState::Poisoned => panic!("Polled after panic"),
+                    TraceState::Untraced | TraceState::Resolved | TraceState::Poisoned => unreachable!(),
                 };
 
+                for event in self.untraced_wakeups.lock().unwrap().drain(..) {
+                    st.emit(event);
+                }
+
                 let on_event = TraceEvent::AsyncOnCPU {
                     id: span_id,
                     ts: st.now(),
@@ -142,13 +230,18 @@ impl<F: Future> Future for TracedFuture<F> where F::Error : Debug {
                 st.emit(on_event);
                 st.current_span = Some(span_id);
 
-                (parent_id, span_id)
+                (prev_span, span_id)
             };
 
-            let notifier = Notifier { parent_task: AtomicTask::default(), parked_span: span_id };
+            let notifier = Notifier {
+                parent_task: AtomicTask::default(),
+                parked_span: span_id,
+                untraced_wakeups: self.untraced_wakeups.clone(),
+            };
             notifier.parent_task.park();
             let handle = NotifyHandle::from(Arc::new(notifier));
 
+            let alloc_start = alloc::snapshot();
             let result = {
                 let mut f = spawn(&mut self.inner);
                 f.poll_future_notify(&handle, 0)
@@ -156,10 +249,11 @@ impl<F: Future> Future for TracedFuture<F> where F::Error : Debug {
 
             let mut st = c.borrow_mut();
 
-            st.current_span = Some(parent_id);
+            st.current_span = prev_span;
             let off_event = TraceEvent::AsyncOffCPU {
                 id: span_id,
                 ts: st.now(),
+                alloc: alloc::since(alloc_start),
             };
             st.emit(off_event);
 
@@ -192,6 +286,7 @@ impl<F: Future> Future for TracedFuture<F> where F::Error : Debug {
 struct Notifier {
     parent_task: AtomicTask,
     parked_span: SpanId,
+    untraced_wakeups: Arc<Mutex<Vec<TraceEvent>>>,
 }
 
 impl Notify for Notifier {
@@ -201,13 +296,25 @@ impl Notify for Notifier {
                 let mut st = c.borrow_mut();
                 let should_log = !st.currently_logging_wakeup;
                 if should_log {
-                    if let Some(current_span) = st.current_span {
-                        let event = TraceEvent::Wakeup {
-                            waking_span: current_span,
-                            parked_span: self.parked_span,
-                            ts: st.now(),
-                        };
-                        st.emit(event);
+                    match st.current_span {
+                        Some(current_span) => {
+                            let event = TraceEvent::Wakeup {
+                                waking_span: current_span,
+                                parked_span: self.parked_span,
+                                ts: st.now(),
+                            };
+                            st.emit(event);
+                        },
+                        None => {
+                            // Woken from a reactor, timer or other untraced thread: there's no
+                            // span (and likely no logger) here, so hand the event to the future.
+                            let event = TraceEvent::UntracedWakeup {
+                                waking_thread: state::thread_name(),
+                                parked_span: self.parked_span,
+                                ts: st.now(),
+                            };
+                            self.untraced_wakeups.lock().unwrap().push(event);
+                        },
                     }
                     st.currently_logging_wakeup = true;
                 }
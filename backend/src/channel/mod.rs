@@ -0,0 +1,95 @@
+//! Traced wrappers around channels.  Each message gets an id that's logged when it's sent and
+//! again when it's received, so the viewer can draw an edge from the sending span to the
+//! receiving one, and each channel logs its queue depth as a counter named after it.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use event::{MessageId, Name, TraceEvent};
+use state::TRACER_STATE;
+
+pub mod mpsc;
+pub mod oneshot;
+pub mod std_mpsc;
+
+/// Error for sends on a closed channel, holding on to the message that couldn't be sent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SendError").field(&"...").finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "send failed because receiver is gone")
+    }
+}
+
+struct ChannelState {
+    name: Cow<'static, str>,
+    depth: AtomicUsize,
+}
+
+impl ChannelState {
+    fn new(name: Cow<'static, str>) -> Self {
+        ChannelState { name, depth: AtomicUsize::new(0) }
+    }
+
+    // Count a message on its way into the channel, returning the id to send along with it.  It's
+    // counted before it goes in, so the depth can't drop below zero if it's received straight
+    // away, but only logged by `sent` once it's made it.
+    fn sending(&self) -> MessageId {
+        self.depth.fetch_add(1, Ordering::SeqCst);
+        MessageId::new()
+    }
+
+    // Log a message that made it into the channel.
+    fn sent(&self, msg: MessageId) {
+        let depth = self.depth.load(Ordering::SeqCst);
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            let event = TraceEvent::MessageSend {
+                msg,
+                channel: Name::Str(self.name.clone()),
+                span: st.parent_span(),
+                ts: st.now(),
+            };
+            st.emit(event);
+            let event = TraceEvent::Counter {
+                name: Name::Str(self.name.clone()),
+                value: depth as u64,
+                ts: st.now(),
+            };
+            st.emit(event);
+        });
+    }
+
+    // Log a message coming out of the channel, in the current span.
+    fn received(&self, msg: MessageId) {
+        let depth = self.depth.fetch_sub(1, Ordering::SeqCst) - 1;
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            let event = TraceEvent::MessageRecv {
+                msg,
+                channel: Name::Str(self.name.clone()),
+                span: st.parent_span(),
+                ts: st.now(),
+            };
+            st.emit(event);
+            let event = TraceEvent::Counter {
+                name: Name::Str(self.name.clone()),
+                value: depth as u64,
+                ts: st.now(),
+            };
+            st.emit(event);
+        });
+    }
+
+    // Forget a message from `sending` that never made it into the channel.
+    fn unsent(&self) {
+        self.depth.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,159 @@
+//! Traced version of `futures::sync::mpsc`.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use futures::{Async, AsyncSink, Poll, Sink, StartSend, Stream};
+use futures::sync::mpsc;
+use event::MessageId;
+use super::{ChannelState, SendError};
+
+pub fn channel<T, S: Into<Cow<'static, str>>>(name: S, buffer: usize) -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    let state = Arc::new(ChannelState::new(name.into()));
+    (Sender { inner: tx, state: state.clone() }, Receiver { inner: rx, state })
+}
+
+pub fn unbounded<T, S: Into<Cow<'static, str>>>(name: S) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    let (tx, rx) = mpsc::unbounded();
+    let state = Arc::new(ChannelState::new(name.into()));
+    (UnboundedSender { inner: tx, state: state.clone() }, UnboundedReceiver { inner: rx, state })
+}
+
+pub struct Sender<T> {
+    inner: mpsc::Sender<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Sender { inner: self.inner.clone(), state: self.state.clone() }
+    }
+}
+
+impl<T> Sender<T> {
+    pub fn poll_ready(&mut self) -> Poll<(), SendError<()>> {
+        self.inner.poll_ready().map_err(|_| SendError(()))
+    }
+}
+
+impl<T> Sink for Sender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, t: T) -> StartSend<T, SendError<T>> {
+        let msg = self.state.sending();
+        match self.inner.start_send((msg, t)) {
+            Ok(AsyncSink::Ready) => {
+                self.state.sent(msg);
+                Ok(AsyncSink::Ready)
+            },
+            Ok(AsyncSink::NotReady((_, t))) => {
+                self.state.unsent();
+                Ok(AsyncSink::NotReady(t))
+            },
+            Err(e) => {
+                self.state.unsent();
+                Err(SendError(e.into_inner().1))
+            },
+        }
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        // Never fails for mpsc senders.
+        Ok(self.inner.poll_complete().unwrap_or(Async::Ready(())))
+    }
+}
+
+pub struct Receiver<T> {
+    inner: mpsc::Receiver<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Receiver<T> {
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        match self.inner.poll()? {
+            Async::Ready(Some((msg, t))) => {
+                self.state.received(msg);
+                Ok(Async::Ready(Some(t)))
+            },
+            Async::Ready(None) => Ok(Async::Ready(None)),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+pub struct UnboundedSender<T> {
+    inner: mpsc::UnboundedSender<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> Self {
+        UnboundedSender { inner: self.inner.clone(), state: self.state.clone() }
+    }
+}
+
+impl<T> UnboundedSender<T> {
+    pub fn unbounded_send(&self, t: T) -> Result<(), SendError<T>> {
+        let msg = self.state.sending();
+        match self.inner.unbounded_send((msg, t)) {
+            Ok(()) => {
+                self.state.sent(msg);
+                Ok(())
+            },
+            Err(e) => {
+                self.state.unsent();
+                Err(SendError(e.into_inner().1))
+            },
+        }
+    }
+}
+
+impl<T> Sink for UnboundedSender<T> {
+    type SinkItem = T;
+    type SinkError = SendError<T>;
+
+    fn start_send(&mut self, t: T) -> StartSend<T, SendError<T>> {
+        self.unbounded_send(t).map(|()| AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), SendError<T>> {
+        Ok(Async::Ready(()))
+    }
+}
+
+pub struct UnboundedReceiver<T> {
+    inner: mpsc::UnboundedReceiver<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> UnboundedReceiver<T> {
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+impl<T> Stream for UnboundedReceiver<T> {
+    type Item = T;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<T>, ()> {
+        match self.inner.poll()? {
+            Async::Ready(Some((msg, t))) => {
+                self.state.received(msg);
+                Ok(Async::Ready(Some(t)))
+            },
+            Async::Ready(None) => Ok(Async::Ready(None)),
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Traced version of `futures::sync::oneshot`.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use futures::{Async, Future, Poll};
+use futures::sync::oneshot;
+pub use futures::sync::oneshot::Canceled;
+use event::MessageId;
+use super::ChannelState;
+
+pub fn channel<T, S: Into<Cow<'static, str>>>(name: S) -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = oneshot::channel();
+    let state = Arc::new(ChannelState::new(name.into()));
+    (Sender { inner: tx, state: state.clone() }, Receiver { inner: rx, state })
+}
+
+pub struct Sender<T> {
+    inner: oneshot::Sender<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Sender<T> {
+    pub fn send(self, t: T) -> Result<(), T> {
+        let msg = self.state.sending();
+        let state = self.state;
+        match self.inner.send((msg, t)) {
+            Ok(()) => {
+                state.sent(msg);
+                Ok(())
+            },
+            Err((_, t)) => {
+                state.unsent();
+                Err(t)
+            },
+        }
+    }
+
+    #[allow(clippy::result_unit_err)]
+    pub fn poll_cancel(&mut self) -> Poll<(), ()> {
+        self.inner.poll_cancel()
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.inner.is_canceled()
+    }
+}
+
+pub struct Receiver<T> {
+    inner: oneshot::Receiver<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Receiver<T> {
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Item = T;
+    type Error = Canceled;
+
+    fn poll(&mut self) -> Poll<T, Canceled> {
+        match self.inner.poll()? {
+            Async::Ready((msg, t)) => {
+                self.state.received(msg);
+                Ok(Async::Ready(t))
+            },
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Traced version of `std::sync::mpsc`.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::mpsc;
+pub use std::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+use std::time::Duration;
+use event::MessageId;
+use super::ChannelState;
+
+pub fn channel<T, S: Into<Cow<'static, str>>>(name: S) -> (Sender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::channel();
+    let state = Arc::new(ChannelState::new(name.into()));
+    (Sender { inner: tx, state: state.clone() }, Receiver { inner: rx, state })
+}
+
+pub fn sync_channel<T, S: Into<Cow<'static, str>>>(name: S, bound: usize) -> (SyncSender<T>, Receiver<T>) {
+    let (tx, rx) = mpsc::sync_channel(bound);
+    let state = Arc::new(ChannelState::new(name.into()));
+    (SyncSender { inner: tx, state: state.clone() }, Receiver { inner: rx, state })
+}
+
+pub struct Sender<T> {
+    inner: mpsc::Sender<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Sender { inner: self.inner.clone(), state: self.state.clone() }
+    }
+}
+
+impl<T> Sender<T> {
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        let msg = self.state.sending();
+        match self.inner.send((msg, t)) {
+            Ok(()) => {
+                self.state.sent(msg);
+                Ok(())
+            },
+            Err(SendError((_, t))) => {
+                self.state.unsent();
+                Err(SendError(t))
+            },
+        }
+    }
+}
+
+pub struct SyncSender<T> {
+    inner: mpsc::SyncSender<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Clone for SyncSender<T> {
+    fn clone(&self) -> Self {
+        SyncSender { inner: self.inner.clone(), state: self.state.clone() }
+    }
+}
+
+impl<T> SyncSender<T> {
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        let msg = self.state.sending();
+        match self.inner.send((msg, t)) {
+            Ok(()) => {
+                self.state.sent(msg);
+                Ok(())
+            },
+            Err(SendError((_, t))) => {
+                self.state.unsent();
+                Err(SendError(t))
+            },
+        }
+    }
+
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        let msg = self.state.sending();
+        match self.inner.try_send((msg, t)) {
+            Ok(()) => {
+                self.state.sent(msg);
+                Ok(())
+            },
+            Err(e) => {
+                self.state.unsent();
+                Err(match e {
+                    TrySendError::Full((_, t)) => TrySendError::Full(t),
+                    TrySendError::Disconnected((_, t)) => TrySendError::Disconnected(t),
+                })
+            },
+        }
+    }
+}
+
+pub struct Receiver<T> {
+    inner: mpsc::Receiver<(MessageId, T)>,
+    state: Arc<ChannelState>,
+}
+
+impl<T> Receiver<T> {
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let (msg, t) = self.inner.recv()?;
+        self.state.received(msg);
+        Ok(t)
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let (msg, t) = self.inner.try_recv()?;
+        self.state.received(msg);
+        Ok(t)
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let (msg, t) = self.inner.recv_timeout(timeout)?;
+        self.state.received(msg);
+        Ok(t)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { rx: self }
+    }
+}
+
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+lazy_static! {
+    static ref EPOCH: (SystemTime, Instant) = (SystemTime::now(), Instant::now());
+}
+
+/// Source of the timestamps in a trace.  Install one for the whole process with `set_clock`, or
+/// for just the current thread with `set_thread_clock`.
+pub trait Clock: Send + Sync {
+    /// Time since this clock's zero.
+    fn now(&self) -> Duration;
+
+    /// Wall-clock time, since the Unix epoch, at which `now` was zero.
+    fn epoch(&self) -> Duration;
+
+    /// Short description of the clock, recorded in the trace header.
+    fn name(&self) -> &str;
+}
+
+impl<C: Clock + ?Sized> Clock for Box<C> {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+
+    fn epoch(&self) -> Duration {
+        (**self).epoch()
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+}
+
+/// The default clock: `Instant`s relative to when tracing started.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> Duration {
+        let (_, epoch) = *EPOCH;
+        Instant::now().duration_since(epoch)
+    }
+
+    fn epoch(&self) -> Duration {
+        let (wall_clock, _) = *EPOCH;
+        wall_clock.duration_since(UNIX_EPOCH).unwrap_or_default()
+    }
+
+    fn name(&self) -> &str {
+        "monotonic"
+    }
+}
+
+/// Clock that only moves when told to, for deterministic tests and simulated executors.  Clones
+/// share the same time, so keep one around to drive a clock you've installed.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+    now: Arc<Mutex<Duration>>,
+    epoch: Duration,
+}
+
+impl ManualClock {
+    /// A clock starting at zero, with zero as its wall-clock epoch too.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A clock starting at zero, which maps to `epoch` since the Unix epoch in wall-clock time.
+    pub fn with_epoch(epoch: Duration) -> Self {
+        ManualClock { now: Arc::default(), epoch }
+    }
+
+    pub fn set(&self, now: Duration) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+
+    fn epoch(&self) -> Duration {
+        self.epoch
+    }
+
+    fn name(&self) -> &str {
+        "manual"
+    }
+}
+
+/// Clock that reads the CPU's timestamp counter, which is cheaper than `Instant::now` on x86.  It
+/// assumes an invariant TSC that's synchronized across cores, as on most modern x86 machines.  On
+/// other architectures it falls back to `RealClock`.
+#[derive(Clone, Copy, Debug)]
+pub struct TscClock {
+    // Counter reading at `base`, and counter ticks per nanosecond.
+    base_ticks: u64,
+    base: Duration,
+    ticks_per_nano: f64,
+}
+
+impl TscClock {
+    /// Calibrate the counter against `Instant`, which takes about ten milliseconds.
+    pub fn calibrate() -> Self {
+        let (start, start_ticks) = (Instant::now(), rdtsc());
+        thread::sleep(Duration::from_millis(10));
+        let (end, end_ticks) = (Instant::now(), rdtsc());
+        let nanos = end.duration_since(start).as_nanos() as f64;
+        TscClock {
+            base_ticks: end_ticks,
+            base: RealClock.now(),
+            ticks_per_nano: end_ticks.wrapping_sub(start_ticks) as f64 / nanos,
+        }
+    }
+}
+
+impl Clock for TscClock {
+    fn now(&self) -> Duration {
+        if !cfg!(target_arch = "x86_64") || self.ticks_per_nano <= 0.0 {
+            return RealClock.now();
+        }
+        let ticks = rdtsc().saturating_sub(self.base_ticks);
+        self.base + Duration::from_nanos((ticks as f64 / self.ticks_per_nano) as u64)
+    }
+
+    fn epoch(&self) -> Duration {
+        RealClock.epoch()
+    }
+
+    fn name(&self) -> &str {
+        "tsc"
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn rdtsc() -> u64 {
+    #[allow(unused_unsafe)]
+    unsafe { ::std::arch::x86_64::_rdtsc() }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn rdtsc() -> u64 {
+    0
+}
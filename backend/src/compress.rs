@@ -0,0 +1,121 @@
+//! Compressing writers for traces.  Wrap a file in one and hand it to `JsonWriter`:
+//!
+//! ```ignore
+//! let file = File::create("trace.log.zst")?;
+//! let logger = JsonWriter::new(ZstdWriter::new(file)?);
+//! ```
+//!
+//! Every `Logger::flush` ends the current frame, so everything logged before it can be
+//! decompressed even if the process dies right after.  Concatenated frames are still a valid
+//! gzip/zstd file, and the server reads them as they're written.
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use std::io::{self, Write};
+
+// Only if starting the next frame fails, which leaves nothing to write to.
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn lost() -> io::Error {
+    io::Error::other("compressed stream lost after a failed flush")
+}
+
+/// Writes a gzip stream, starting a new member on each flush.  A flush that fails can be
+/// retried.
+#[cfg(feature = "gzip")]
+pub struct GzipWriter<W: Write> {
+    encoder: Option<::flate2::write::GzEncoder<W>>,
+    level: ::flate2::Compression,
+    // Whether the current member has anything in it yet.
+    dirty: bool,
+}
+
+#[cfg(feature = "gzip")]
+impl<W: Write> GzipWriter<W> {
+    pub fn new(w: W) -> Self {
+        Self::with_level(w, ::flate2::Compression::default())
+    }
+
+    pub fn with_level(w: W, level: ::flate2::Compression) -> Self {
+        GzipWriter {
+            encoder: Some(::flate2::write::GzEncoder::new(w, level)),
+            level,
+            dirty: false,
+        }
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl<W: Write> Write for GzipWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.dirty = true;
+        self.encoder.as_mut().ok_or_else(lost)?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        // End the member in place, so the encoder is still there to retry with if this fails.
+        self.encoder.as_mut().ok_or_else(lost)?.try_finish()?;
+        let w = self.encoder.take().unwrap().finish()?;
+        self.encoder.get_or_insert(::flate2::write::GzEncoder::new(w, self.level)).get_mut().flush()?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl<W: Write> Drop for GzipWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Writes a zstd stream, ending the frame on each flush.  A flush that fails can be retried.
+#[cfg(feature = "zstd")]
+pub struct ZstdWriter<W: Write> {
+    encoder: Option<::zstd::stream::write::Encoder<'static, W>>,
+    level: i32,
+    dirty: bool,
+}
+
+#[cfg(feature = "zstd")]
+impl<W: Write> ZstdWriter<W> {
+    pub fn new(w: W) -> io::Result<Self> {
+        Self::with_level(w, ::zstd::DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    pub fn with_level(w: W, level: i32) -> io::Result<Self> {
+        Ok(ZstdWriter {
+            encoder: Some(::zstd::stream::write::Encoder::new(w, level)?),
+            level,
+            dirty: false,
+        })
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<W: Write> Write for ZstdWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.dirty = true;
+        self.encoder.as_mut().ok_or_else(lost)?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        // End the frame in place, so the encoder is still there to retry with if this fails.
+        self.encoder.as_mut().ok_or_else(lost)?.do_finish()?;
+        let w = self.encoder.take().unwrap().finish()?;
+        self.encoder.get_or_insert(::zstd::stream::write::Encoder::new(w, self.level)?).get_mut().flush()?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<W: Write> Drop for ZstdWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
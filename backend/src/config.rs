@@ -0,0 +1,43 @@
+use clock::Clock;
+use state::{self, Logger, UntracedPolicy};
+
+/// Process-wide tracing setup, installed with `init`.
+pub struct Config {
+    logger: Box<dyn Logger>,
+    untraced: UntracedPolicy,
+    clock: Option<Box<dyn Clock>>,
+}
+
+impl Config {
+    /// Log every thread's events to `logger`.  By default, threads that aren't explicitly traced
+    /// join in with an implicit thread root the first time they start a span.
+    pub fn new<L: Logger + 'static>(logger: L) -> Self {
+        Config {
+            logger: Box::new(logger),
+            untraced: UntracedPolicy::ImplicitRoot,
+            clock: None,
+        }
+    }
+
+    pub fn untraced(mut self, policy: UntracedPolicy) -> Self {
+        self.untraced = policy;
+        self
+    }
+
+    /// Timestamp events with `clock` instead of `RealClock`.
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+}
+
+/// Install `config`'s logger as the shared sink for all threads.  Threads created with
+/// `cyclotron::thread::spawn` are traced from the start; others follow `config`'s
+/// `UntracedPolicy`.
+pub fn init(config: Config) {
+    state::set_default_logger(config.logger);
+    state::set_untraced_policy(config.untraced);
+    if let Some(clock) = config.clock {
+        state::set_clock(clock);
+    }
+}
@@ -0,0 +1,55 @@
+use rand;
+use event::SpanId;
+use state::TRACER_STATE;
+
+/// Suggested name for the header carrying a serialized `SpanContext` between processes.
+pub const CONTEXT_HEADER: &str = "cyclotron-context";
+
+lazy_static! {
+    static ref PROCESS_ID: ProcessId = ProcessId(rand::random());
+}
+
+/// Identifies the process (and therefore the trace file) a span was recorded in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct ProcessId(pub u64);
+
+impl ProcessId {
+    pub fn current() -> Self {
+        *PROCESS_ID
+    }
+}
+
+/// A reference to a span that can be handed to another process, so spans created there can
+/// name it as their remote parent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct SpanContext {
+    pub process_id: ProcessId,
+    pub span_id: SpanId,
+}
+
+impl SpanContext {
+    /// Context of the span currently executing on this thread, if any.
+    pub fn current() -> Option<Self> {
+        TRACER_STATE.with(|c| {
+            c.borrow().current_span.map(|span_id| SpanContext {
+                process_id: ProcessId::current(),
+                span_id,
+            })
+        })
+    }
+
+    /// Serialize as `<process id>-<span id>`, both in hex.
+    pub fn to_header(&self) -> String {
+        format!("{:016x}-{:016x}", self.process_id.0, self.span_id.0)
+    }
+
+    pub fn from_header(header: &str) -> Option<Self> {
+        let mut parts = header.trim().splitn(2, '-');
+        let process_id = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let span_id = u64::from_str_radix(parts.next()?, 16).ok()?;
+        Some(SpanContext {
+            process_id: ProcessId(process_id),
+            span_id: SpanId(span_id),
+        })
+    }
+}
@@ -1,27 +1,117 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
 use std::time::Duration;
 use rand;
 use serde_json;
+use context::{ProcessId, SpanContext};
+
+/// Version of the trace format, recorded in each trace's `Header`.
+pub const SCHEMA_VERSION: u32 = 2;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
 pub struct SpanId(pub u64);
 
+#[allow(clippy::new_without_default)]
 impl SpanId {
     pub fn new() -> Self {
         SpanId(rand::random())
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Name of a span, channel or counter.  Writers that intern names, like `JsonWriter`, log each
+/// distinct name once in a `NameDef` event, then refer to it by id.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Name {
+    Ref(u32),
+    Str(Cow<'static, str>),
+}
+
+impl Name {
+    pub fn new<S: Into<Cow<'static, str>>>(name: S) -> Self {
+        Name::Str(name.into())
+    }
+
+    /// The name itself, or `None` if it's a reference to a `NameDef`.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Name::Ref(..) => None,
+            Name::Str(ref name) => Some(name),
+        }
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Name::Ref(id) => write!(f, "#{}", id),
+            Name::Str(ref name) => f.write_str(name),
+        }
+    }
+}
+
+/// Identifies a single message sent over a traced channel.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub struct MessageId(pub u64);
+
+#[allow(clippy::new_without_default)]
+impl MessageId {
+    pub fn new() -> Self {
+        MessageId(rand::random())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum AsyncOutcome {
     Success,
     Cancelled,
     Error(String),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Heap allocations made by a span, counted by `TracedAlloc`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+/// Which part of a lock's use a lock span covers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum LockPhase {
+    Wait,
+    Hold,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum TraceEvent {
+    // Defines `id` as shorthand for `name` in later events from the same writer.
+    NameDef {
+        id: u32,
+        name: Cow<'static, str>,
+    },
+
+    Header {
+        schema_version: u32,
+        // Wall-clock time corresponding to a `ts` of zero, since the Unix epoch.
+        wall_clock: Duration,
+        clock: String,
+        process_id: ProcessId,
+        pid: u32,
+        hostname: Option<String>,
+        executable: Option<String>,
+        command_line: Vec<String>,
+    },
+
     AsyncStart {
-        name: String,
+        name: Name,
         id: SpanId,
         parent_id: SpanId,
         ts: Duration,
@@ -34,15 +124,24 @@ pub enum TraceEvent {
     AsyncOffCPU {
         id: SpanId,
         ts: Duration,
+        // Allocations made during this poll, outside any spans nested in it, if `TracedAlloc` is
+        // installed.
+        alloc: Option<AllocStats>,
     },
     AsyncEnd {
         id: SpanId,
         ts: Duration,
         outcome: AsyncOutcome,
     },
+    AsyncMigrate {
+        id: SpanId,
+        poller: Option<SpanId>,
+        thread: String,
+        ts: Duration,
+    },
 
     SyncStart {
-        name: String,
+        name: Name,
         id: SpanId,
         parent_id: SpanId,
         ts: Duration,
@@ -51,12 +150,14 @@ pub enum TraceEvent {
     SyncEnd {
         id: SpanId,
         ts: Duration,
+        alloc: Option<AllocStats>,
     },
 
     ThreadStart {
-        name: String,
+        name: Name,
         id: SpanId,
         ts: Duration,
+        spawned_by: Option<SpanId>,
     },
     ThreadEnd {
         id: SpanId,
@@ -68,4 +169,95 @@ pub enum TraceEvent {
         parked_span: SpanId,
         ts: Duration,
     },
+    UntracedWakeup {
+        waking_thread: String,
+        parked_span: SpanId,
+        ts: Duration,
+    },
+
+    RemoteParent {
+        id: SpanId,
+        parent: SpanContext,
+        ts: Duration,
+    },
+
+    MessageSend {
+        msg: MessageId,
+        channel: Name,
+        span: Option<SpanId>,
+        ts: Duration,
+    },
+    MessageRecv {
+        msg: MessageId,
+        channel: Name,
+        span: Option<SpanId>,
+        ts: Duration,
+    },
+
+    LockStart {
+        name: Name,
+        id: SpanId,
+        parent_id: SpanId,
+        phase: LockPhase,
+        // For waits, the hold span that had the lock when we started waiting.
+        holder: Option<SpanId>,
+        ts: Duration,
+    },
+    LockEnd {
+        id: SpanId,
+        ts: Duration,
+    },
+
+    Counter {
+        name: Name,
+        value: u64,
+        ts: Duration,
+    },
+}
+
+impl TraceEvent {
+    /// The name this event carries, if any.
+    pub fn name_mut(&mut self) -> Option<&mut Name> {
+        match *self {
+            TraceEvent::AsyncStart { ref mut name, .. }
+            | TraceEvent::SyncStart { ref mut name, .. }
+            | TraceEvent::ThreadStart { ref mut name, .. }
+            | TraceEvent::LockStart { ref mut name, .. }
+            | TraceEvent::Counter { ref mut name, .. }
+            | TraceEvent::MessageSend { channel: ref mut name, .. }
+            | TraceEvent::MessageRecv { channel: ref mut name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// The names a writer has defined so far in one trace.
+#[derive(Default)]
+pub struct NameTable {
+    names: HashMap<Cow<'static, str>, u32>,
+}
+
+impl NameTable {
+    /// Swap `name` for a reference.  If this is the first time we've seen it, returns the
+    /// `NameDef` to write before the event that uses it.
+    pub fn intern(&mut self, name: &mut Name) -> Option<TraceEvent> {
+        let id = match *name {
+            Name::Ref(..) => return None,
+            Name::Str(ref s) => self.names.get(s.as_ref()).cloned(),
+        };
+        let (id, def) = match id {
+            Some(id) => (id, None),
+            None => {
+                let s = match mem::replace(name, Name::Ref(0)) {
+                    Name::Str(s) => s,
+                    Name::Ref(..) => unreachable!(),
+                };
+                let id = self.names.len() as u32;
+                self.names.insert(s.clone(), id);
+                (id, Some(TraceEvent::NameDef { id, name: s }))
+            },
+        };
+        *name = Name::Ref(id);
+        def
+    }
 }
@@ -0,0 +1,40 @@
+use std::borrow::Cow;
+use futures::Future;
+use futures::future::{Executor, ExecuteError};
+use async::{TraceFuture, TracedFuture};
+use state::current_span;
+
+/// Wraps a futures `Executor` so every task it runs is traced under a task-level span, parented
+/// to the span that spawned it rather than to whatever happens to poll it on the worker thread.
+pub struct TracedExecutor<E> {
+    inner: E,
+    name: Cow<'static, str>,
+}
+
+impl<E> TracedExecutor<E> {
+    pub fn new(inner: E) -> Self {
+        Self::with_name(inner, "task")
+    }
+
+    /// Name the spans of the tasks spawned on `inner` `name`.
+    pub fn with_name<S: Into<Cow<'static, str>>>(inner: E, name: S) -> Self {
+        TracedExecutor { inner, name: name.into() }
+    }
+
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E, F> Executor<F> for TracedExecutor<E>
+    where E: Executor<TracedFuture<F>>, F: Future<Item=(), Error=()>
+{
+    fn execute(&self, future: F) -> Result<(), ExecuteError<F>> {
+        let task = match current_span() {
+            Some(spawner) => TracedFuture::with_parent(future, self.name.clone(), spawner),
+            None => future.traced(self.name.clone()),
+        };
+        self.inner.execute(task)
+            .map_err(|e| ExecuteError::new(e.kind(), e.into_future().into_inner()))
+    }
+}
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::thread::{self, ThreadId};
+use serde_json;
+use alloc::{self, AllocMark};
+use context::{ProcessId, SpanContext};
+use event::{AsyncOutcome, Name, SpanId, TraceEvent};
+use state::TRACER_STATE;
+
+/// A span that's started and ended explicitly, for code where `SyncSpan`'s stack discipline
+/// doesn't fit: callbacks, FFI boundaries, state machines.  Handles can be entered and exited any
+/// number of times, and ended in any order relative to each other.  Dropping a handle without
+/// ending it records the span as cancelled.  A handle can be sent between threads, but should be
+/// exited on the thread that entered it.
+pub struct SpanHandle {
+    id: SpanId,
+    // The thread we're entered on, if we are.
+    entered: Option<ThreadId>,
+    ended: bool,
+    // Allocation totals when we were last entered.
+    alloc_start: Option<AllocMark>,
+}
+
+impl SpanHandle {
+    pub fn start<S: Into<Cow<'static, str>>>(name: S, parent: SpanId) -> Self {
+        Self::with_metadata(name, parent, serde_json::Value::Null)
+    }
+
+    pub fn with_metadata<S: Into<Cow<'static, str>>>(name: S, parent: SpanId, meta: serde_json::Value) -> Self {
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            // Make sure an untraced thread gets a logger, if the `UntracedPolicy` gives it one.
+            st.parent_span();
+            let span_id = SpanId::new();
+            let event = TraceEvent::AsyncStart {
+                name: Name::new(name),
+                id: span_id,
+                parent_id: parent,
+                ts: st.now(),
+                metadata: meta,
+            };
+            st.emit(event);
+
+            SpanHandle {
+                id: span_id,
+                entered: None,
+                ended: false,
+                alloc_start: None,
+            }
+        })
+    }
+
+    pub fn id(&self) -> SpanId {
+        self.id
+    }
+
+    pub fn context(&self) -> SpanContext {
+        SpanContext {
+            process_id: ProcessId::current(),
+            span_id: self.id,
+        }
+    }
+
+    /// Make this the current span on this thread, so spans started from here on are its children.
+    /// Entering a handle that's already entered does nothing.
+    pub fn enter(&mut self) {
+        if self.entered.is_some() || self.ended {
+            return;
+        }
+        self.entered = Some(thread::current().id());
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            let prev = st.current_span;
+            st.entered_handles.push((self.id, prev));
+            st.current_span = Some(self.id);
+
+            let event = TraceEvent::AsyncOnCPU {
+                id: self.id,
+                ts: st.now(),
+            };
+            st.emit(event);
+            self.alloc_start = alloc::snapshot();
+        })
+    }
+
+    /// Undo `enter`.  If another handle has been entered since, it stays current, and goes back
+    /// to whatever was current before us when it exits.  On a different thread than `enter` was
+    /// called on, this only records that the span went off-CPU: that thread's current span isn't
+    /// ours to change, and the thread that entered us keeps us as its current span.
+    pub fn exit(&mut self) {
+        let entered_on = match self.entered.take() {
+            Some(thread) => thread,
+            None => return,
+        };
+        let same_thread = entered_on == thread::current().id();
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            if !same_thread {
+                let event = TraceEvent::AsyncOffCPU {
+                    id: self.id,
+                    ts: st.now(),
+                    // Allocation counts are per thread, so ours aren't here to compare.
+                    alloc: None,
+                };
+                self.alloc_start = None;
+                return st.emit(event);
+            }
+            let position = st.entered_handles.iter().rposition(|&(id, _)| id == self.id);
+            if let Some(i) = position {
+                let (_, prev) = st.entered_handles.remove(i);
+                if i < st.entered_handles.len() {
+                    st.entered_handles[i].1 = prev;
+                } else if st.current_span == Some(self.id) {
+                    st.current_span = prev;
+                }
+            }
+
+            let event = TraceEvent::AsyncOffCPU {
+                id: self.id,
+                ts: st.now(),
+                alloc: alloc::since(self.alloc_start.take()),
+            };
+            st.emit(event);
+        })
+    }
+
+    pub fn end(mut self) {
+        self.finish(AsyncOutcome::Success);
+    }
+
+    pub fn end_with_error<E: Debug>(mut self, error: E) {
+        self.finish(AsyncOutcome::Error(format!("{:?}", error)));
+    }
+
+    fn finish(&mut self, outcome: AsyncOutcome) {
+        if self.ended {
+            return;
+        }
+        self.exit();
+        self.ended = true;
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            let event = TraceEvent::AsyncEnd {
+                id: self.id,
+                ts: st.now(),
+                outcome,
+            };
+            st.emit(event);
+        })
+    }
+}
+
+impl Drop for SpanHandle {
+    fn drop(&mut self) {
+        self.finish(AsyncOutcome::Cancelled);
+    }
+}
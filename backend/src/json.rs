@@ -2,24 +2,43 @@ use std::fs::File;
 use std::io::{BufWriter, Write};
 use serde_json;
 
-use event::TraceEvent;
-use state::Logger;
+use event::{NameTable, TraceEvent};
+use state::{self, Logger};
 
-pub struct JsonWriter {
-    file: BufWriter<File>,
+/// Writes events as JSON, one per line.  Each distinct name is written once, in a `NameDef`
+/// event, and referred to by id from then on.  Wrap the output in a `compress` writer to
+/// compress the trace.
+pub struct JsonWriter<W: Write = File> {
+    file: BufWriter<W>,
+    names: NameTable,
+    // Whether we've written the header yet.
+    started: bool,
 }
 
-impl JsonWriter {
-    pub fn new(f: File) -> Self {
-        JsonWriter { file: BufWriter::new(f) }
+impl<W: Write> JsonWriter<W> {
+    /// Start a trace in `f`.  It begins with a header describing this process, written along
+    /// with the first event so it describes the clock `init` installed after creating us.
+    pub fn new(f: W) -> Self {
+        JsonWriter { file: BufWriter::new(f), names: NameTable::default(), started: false }
     }
-}
 
-impl Logger for JsonWriter {
-    fn write(&mut self, event: TraceEvent) {
-        serde_json::to_writer(&mut self.file, &event)
+    fn write_line(&mut self, event: &TraceEvent) {
+        serde_json::to_writer(&mut self.file, event)
             .expect("Failed to write to logfile");
-        self.file.write(b"\n").expect("Failed to write newline");
+        self.file.write_all(b"\n").expect("Failed to write newline");
+    }
+}
+
+impl<W: Write + Send> Logger for JsonWriter<W> {
+    fn write(&mut self, mut event: TraceEvent) {
+        if !self.started {
+            self.started = true;
+            self.write_line(&state::header());
+        }
+        if let Some(def) = event.name_mut().and_then(|name| self.names.intern(name)) {
+            self.write_line(&def);
+        }
+        self.write_line(&event);
     }
     fn flush(&mut self) {
         self.file.flush().expect("Failed to flush");
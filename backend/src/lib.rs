@@ -1,23 +1,64 @@
+#[cfg(feature = "gzip")]
+extern crate flate2;
 extern crate futures;
 extern crate rand;
 extern crate serde;
 extern crate serde_json;
+#[cfg(feature = "zstd")]
+extern crate zstd;
 #[macro_use]
 extern crate lazy_static;
 #[allow(unused_imports)]
 #[macro_use]
 extern crate serde_derive;
 
+mod alloc;
 mod async;
+mod clock;
+mod config;
+mod context;
 mod event;
+mod executor;
+mod handle;
+mod lock;
 mod state;
 mod sync;
+pub mod channel;
+pub mod compress;
 pub mod json;
+pub mod net;
+pub mod testing;
+pub mod thread;
 
+pub use alloc::TracedAlloc;
 pub use async::{TraceFuture, TracedFuture};
-pub use event::TraceEvent;
+pub use clock::{Clock, ManualClock, RealClock, TscClock};
+pub use config::{init, Config};
+pub use context::{CONTEXT_HEADER, ProcessId, SpanContext};
+pub use event::{SCHEMA_VERSION, AllocStats, LockPhase, MessageId, Name, SpanId, TraceEvent};
+pub use executor::TracedExecutor;
+pub use handle::SpanHandle;
+pub use lock::{
+    TracedMutex,
+    TracedMutexGuard,
+    TracedRwLock,
+    TracedRwLockReadGuard,
+    TracedRwLockWriteGuard,
+};
 pub use sync::{TracedThread, SyncSpan};
-pub use state::{DebugLogger, NoopLogger, Logger};
+pub use state::{
+    clear_default_logger,
+    current_span,
+    reset_thread_clock,
+    set_clock,
+    set_default_logger,
+    set_thread_clock,
+    set_untraced_policy,
+    DebugLogger,
+    NoopLogger,
+    Logger,
+    UntracedPolicy,
+};
 
 #[cfg(test)]
 mod tests;
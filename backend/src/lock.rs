@@ -0,0 +1,279 @@
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+use std::sync::{
+    LockResult,
+    Mutex,
+    MutexGuard,
+    PoisonError,
+    RwLock,
+    RwLockReadGuard,
+    RwLockWriteGuard,
+    TryLockError,
+    TryLockResult,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use event::{LockPhase, Name, SpanId, TraceEvent};
+use state::TRACER_STATE;
+
+// Tracing shared by both kinds of lock.
+struct LockState {
+    name: Cow<'static, str>,
+    // Hold span of the last span to acquire the lock, or zero if it's free.
+    holder: AtomicU64,
+}
+
+impl LockState {
+    fn new(name: Cow<'static, str>) -> Self {
+        LockState { name, holder: AtomicU64::new(0) }
+    }
+
+    // Acquire the lock, logging a wait span if it's contended and a hold span once we have it.
+    // The hold span isn't made current: spans started while holding the lock stay children of
+    // the span that took it.
+    fn acquire<G>(&self,
+                  try_acquire: impl FnOnce() -> TryLockResult<G>,
+                  acquire: impl FnOnce() -> LockResult<G>) -> (G, bool, Option<SpanId>) {
+        let parent_id = match TRACER_STATE.with(|c| c.borrow_mut().parent_span()) {
+            Some(parent_id) => parent_id,
+            None => {
+                let (guard, poisoned) = unpoison(acquire());
+                return (guard, poisoned, None);
+            },
+        };
+        let (guard, poisoned) = match try_acquire() {
+            Ok(guard) => (guard, false),
+            Err(TryLockError::Poisoned(e)) => (e.into_inner(), true),
+            Err(TryLockError::WouldBlock) => {
+                let holder = match self.holder.load(Ordering::SeqCst) {
+                    0 => None,
+                    id => Some(SpanId(id)),
+                };
+                let wait = self.start(parent_id, LockPhase::Wait, holder);
+                let result = unpoison(acquire());
+                self.end(wait);
+                result
+            },
+        };
+        let hold = self.start(parent_id, LockPhase::Hold, None);
+        self.holder.store(hold.0, Ordering::SeqCst);
+        (guard, poisoned, Some(hold))
+    }
+
+    // Like `acquire`, but never waits.
+    fn try_acquire<G>(&self, try_acquire: impl FnOnce() -> TryLockResult<G>) -> Result<(G, bool, Option<SpanId>), ()> {
+        let (guard, poisoned) = match try_acquire() {
+            Ok(guard) => (guard, false),
+            Err(TryLockError::Poisoned(e)) => (e.into_inner(), true),
+            Err(TryLockError::WouldBlock) => return Err(()),
+        };
+        let hold = TRACER_STATE.with(|c| c.borrow_mut().parent_span())
+            .map(|parent_id| self.start(parent_id, LockPhase::Hold, None));
+        if let Some(hold) = hold {
+            self.holder.store(hold.0, Ordering::SeqCst);
+        }
+        Ok((guard, poisoned, hold))
+    }
+
+    fn release(&self, hold: Option<SpanId>) {
+        if let Some(hold) = hold {
+            // Readers can overlap, so only clear the holder if it's still us.
+            let _ = self.holder.compare_exchange(hold.0, 0, Ordering::SeqCst, Ordering::SeqCst);
+            self.end(hold);
+        }
+    }
+
+    fn start(&self, parent_id: SpanId, phase: LockPhase, holder: Option<SpanId>) -> SpanId {
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            let id = SpanId::new();
+            let event = TraceEvent::LockStart {
+                name: Name::Str(self.name.clone()),
+                id,
+                parent_id,
+                phase,
+                holder,
+                ts: st.now(),
+            };
+            st.emit(event);
+            id
+        })
+    }
+
+    fn end(&self, id: SpanId) {
+        TRACER_STATE.with(|c| {
+            let mut st = c.borrow_mut();
+            let event = TraceEvent::LockEnd { id, ts: st.now() };
+            st.emit(event);
+        })
+    }
+}
+
+fn unpoison<G>(result: LockResult<G>) -> (G, bool) {
+    match result {
+        Ok(guard) => (guard, false),
+        Err(e) => (e.into_inner(), true),
+    }
+}
+
+fn repoison<G>(guard: G, poisoned: bool) -> LockResult<G> {
+    if poisoned {
+        Err(PoisonError::new(guard))
+    } else {
+        Ok(guard)
+    }
+}
+
+fn try_repoison<G>(acquired: Result<(G, bool), ()>) -> TryLockResult<G> {
+    match acquired {
+        Ok((guard, false)) => Ok(guard),
+        Ok((guard, true)) => Err(TryLockError::Poisoned(PoisonError::new(guard))),
+        Err(()) => Err(TryLockError::WouldBlock),
+    }
+}
+
+/// `Mutex` that logs how long each span waits for it and holds it.  When a span has to wait, the
+/// wait records whichever span was holding the lock at the time.
+pub struct TracedMutex<T: ?Sized> {
+    state: LockState,
+    inner: Mutex<T>,
+}
+
+impl<T> TracedMutex<T> {
+    pub fn new<S: Into<Cow<'static, str>>>(name: S, t: T) -> Self {
+        TracedMutex { state: LockState::new(name.into()), inner: Mutex::new(t) }
+    }
+
+    pub fn into_inner(self) -> LockResult<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: ?Sized> TracedMutex<T> {
+    pub fn lock(&self) -> LockResult<TracedMutexGuard<'_, T>> {
+        let (guard, poisoned, hold) = self.state.acquire(|| self.inner.try_lock(), || self.inner.lock());
+        repoison(TracedMutexGuard { state: &self.state, hold, inner: guard }, poisoned)
+    }
+
+    pub fn try_lock(&self) -> TryLockResult<TracedMutexGuard<'_, T>> {
+        try_repoison(self.state.try_acquire(|| self.inner.try_lock()).map(|(guard, poisoned, hold)| {
+            (TracedMutexGuard { state: &self.state, hold, inner: guard }, poisoned)
+        }))
+    }
+
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        self.inner.get_mut()
+    }
+}
+
+pub struct TracedMutexGuard<'a, T: ?Sized + 'a> {
+    state: &'a LockState,
+    hold: Option<SpanId>,
+    inner: MutexGuard<'a, T>,
+}
+
+impl<'a, T: ?Sized> Deref for TracedMutexGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for TracedMutexGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<'a, T: ?Sized> Drop for TracedMutexGuard<'a, T> {
+    fn drop(&mut self) {
+        self.state.release(self.hold);
+    }
+}
+
+/// `RwLock` that logs waits and holds like `TracedMutex`, for readers and writers alike.
+pub struct TracedRwLock<T: ?Sized> {
+    state: LockState,
+    inner: RwLock<T>,
+}
+
+impl<T> TracedRwLock<T> {
+    pub fn new<S: Into<Cow<'static, str>>>(name: S, t: T) -> Self {
+        TracedRwLock { state: LockState::new(name.into()), inner: RwLock::new(t) }
+    }
+
+    pub fn into_inner(self) -> LockResult<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: ?Sized> TracedRwLock<T> {
+    pub fn read(&self) -> LockResult<TracedRwLockReadGuard<'_, T>> {
+        let (guard, poisoned, hold) = self.state.acquire(|| self.inner.try_read(), || self.inner.read());
+        repoison(TracedRwLockReadGuard { state: &self.state, hold, inner: guard }, poisoned)
+    }
+
+    pub fn try_read(&self) -> TryLockResult<TracedRwLockReadGuard<'_, T>> {
+        try_repoison(self.state.try_acquire(|| self.inner.try_read()).map(|(guard, poisoned, hold)| {
+            (TracedRwLockReadGuard { state: &self.state, hold, inner: guard }, poisoned)
+        }))
+    }
+
+    pub fn write(&self) -> LockResult<TracedRwLockWriteGuard<'_, T>> {
+        let (guard, poisoned, hold) = self.state.acquire(|| self.inner.try_write(), || self.inner.write());
+        repoison(TracedRwLockWriteGuard { state: &self.state, hold, inner: guard }, poisoned)
+    }
+
+    pub fn try_write(&self) -> TryLockResult<TracedRwLockWriteGuard<'_, T>> {
+        try_repoison(self.state.try_acquire(|| self.inner.try_write()).map(|(guard, poisoned, hold)| {
+            (TracedRwLockWriteGuard { state: &self.state, hold, inner: guard }, poisoned)
+        }))
+    }
+
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        self.inner.get_mut()
+    }
+}
+
+pub struct TracedRwLockReadGuard<'a, T: ?Sized + 'a> {
+    state: &'a LockState,
+    hold: Option<SpanId>,
+    inner: RwLockReadGuard<'a, T>,
+}
+
+impl<'a, T: ?Sized> Deref for TracedRwLockReadGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'a, T: ?Sized> Drop for TracedRwLockReadGuard<'a, T> {
+    fn drop(&mut self) {
+        self.state.release(self.hold);
+    }
+}
+
+pub struct TracedRwLockWriteGuard<'a, T: ?Sized + 'a> {
+    state: &'a LockState,
+    hold: Option<SpanId>,
+    inner: RwLockWriteGuard<'a, T>,
+}
+
+impl<'a, T: ?Sized> Deref for TracedRwLockWriteGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for TracedRwLockWriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<'a, T: ?Sized> Drop for TracedRwLockWriteGuard<'a, T> {
+    fn drop(&mut self) {
+        self.state.release(self.hold);
+    }
+}
@@ -0,0 +1,239 @@
+//! Streams events to a `cyclotron-server` started with `--ingest`, instead of writing a file:
+//!
+//! ```ignore
+//! cyclotron_backend::init(Config::new(NetLogger::tcp("viewer.local:3002")));
+//! ```
+//!
+//! Events are handed to a background thread, which sends them in batches.  If the server isn't
+//! up yet or the connection drops, events are buffered (up to a limit, after which the oldest are
+//! dropped) and sent once it reconnects.  Every connection starts with the trace header, so the
+//! server can tell which process it's hearing from.  Names are interned as `JsonWriter` does, but
+//! afresh on each connection, since the server forgets them when it sees a new header.
+
+use std::collections::VecDeque;
+use std::io::{self, BufWriter, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use serde_json;
+
+use event::{NameTable, TraceEvent};
+use state::{self, Logger};
+
+// Send a batch once this many events are waiting, or once the oldest has waited this long.
+const BATCH_EVENTS: usize = 1024;
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_CAPACITY: usize = 1 << 20;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+enum Endpoint {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl Endpoint {
+    fn connect(&self) -> io::Result<Box<dyn Write + Send>> {
+        match *self {
+            Endpoint::Tcp(ref addr) => {
+                let mut last_err = io::Error::new(io::ErrorKind::NotFound, "address didn't resolve");
+                for addr in addr.to_socket_addrs()? {
+                    match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                        Ok(stream) => {
+                            stream.set_nodelay(true)?;
+                            return Ok(Box::new(stream));
+                        },
+                        Err(e) => last_err = e,
+                    }
+                }
+                Err(last_err)
+            },
+            #[cfg(unix)]
+            Endpoint::Unix(ref path) => Ok(Box::new(::std::os::unix::net::UnixStream::connect(path)?)),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    // Taken when the first event is logged, so it describes the clock `init` installed.
+    header: Option<TraceEvent>,
+    events: VecDeque<TraceEvent>,
+    capacity: usize,
+    dropped: u64,
+    flush: bool,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+/// Logger that sends events to a server over TCP or a Unix domain socket.  Share it between
+/// threads with `Arc<Mutex<NetLogger>>` like any other logger.  Dropping it sends whatever's
+/// buffered, as long as the server is reachable.
+pub struct NetLogger {
+    shared: Arc<Shared>,
+    sender: Option<JoinHandle<()>>,
+}
+
+impl NetLogger {
+    /// Send events to the server listening at `addr`, e.g. `"localhost:3002"`.  The address is
+    /// resolved again on every reconnect.
+    pub fn tcp<A: Into<String>>(addr: A) -> Self {
+        Self::new(Endpoint::Tcp(addr.into()))
+    }
+
+    /// Send events to the server listening on the Unix socket at `path`.
+    #[cfg(unix)]
+    pub fn unix<P: Into<PathBuf>>(path: P) -> Self {
+        Self::new(Endpoint::Unix(path.into()))
+    }
+
+    fn new(endpoint: Endpoint) -> Self {
+        let shared = Arc::new(Shared::default());
+        shared.queue.lock().unwrap().capacity = DEFAULT_CAPACITY;
+        let sender = {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("cyclotron-net".to_string())
+                .spawn(move || send_loop(&shared, &endpoint))
+                .expect("Failed to spawn sender thread")
+        };
+        NetLogger { shared, sender: Some(sender) }
+    }
+
+    /// Buffer at most `events` events while disconnected, dropping the oldest beyond that.
+    pub fn with_capacity(self, events: usize) -> Self {
+        self.shared.queue.lock().unwrap().capacity = events;
+        self
+    }
+
+    /// How many events have been dropped because the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.shared.queue.lock().unwrap().dropped
+    }
+}
+
+impl Logger for NetLogger {
+    fn write(&mut self, event: TraceEvent) {
+        let mut queue = self.shared.queue.lock().unwrap();
+        if queue.header.is_none() {
+            queue.header = Some(state::header());
+        }
+        if queue.events.len() >= queue.capacity {
+            queue.events.pop_front();
+            queue.dropped += 1;
+        }
+        queue.events.push_back(event);
+        if queue.events.len() >= BATCH_EVENTS {
+            self.shared.ready.notify_one();
+        }
+    }
+
+    fn flush(&mut self) {
+        self.shared.queue.lock().unwrap().flush = true;
+        self.shared.ready.notify_one();
+    }
+}
+
+impl Drop for NetLogger {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.ready.notify_one();
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.join();
+        }
+    }
+}
+
+fn send_loop(shared: &Shared, endpoint: &Endpoint) {
+    // The connection, and the names we've defined on it.
+    let mut conn: Option<(BufWriter<Box<dyn Write + Send>>, NameTable)> = None;
+    let mut backoff = BATCH_INTERVAL;
+    loop {
+        // Wait for a full batch, a flush, or for the interval to pass with something to send.
+        let (closed, header) = {
+            let mut queue = shared.queue.lock().unwrap();
+            if queue.events.len() < BATCH_EVENTS && !queue.flush && !queue.closed {
+                queue = shared.ready.wait_timeout(queue, BATCH_INTERVAL).unwrap().0;
+            }
+            queue.flush = false;
+            if queue.events.is_empty() && !queue.closed {
+                continue;
+            }
+            (queue.closed, queue.header.clone())
+        };
+        // Nothing's ever been logged, so there's nothing to send.
+        let header = match header {
+            Some(header) => header,
+            None => return,
+        };
+
+        if conn.is_none() {
+            match endpoint.connect() {
+                Ok(stream) => {
+                    let mut stream = BufWriter::new(stream);
+                    if write_line(&mut stream, &header).is_err() {
+                        continue;
+                    }
+                    conn = Some((stream, NameTable::default()));
+                    backoff = BATCH_INTERVAL;
+                },
+                Err(..) if closed => return,
+                Err(..) => {
+                    // Back off, but wake up early if we're being dropped.
+                    let queue = shared.queue.lock().unwrap();
+                    if !queue.closed {
+                        let _ = shared.ready.wait_timeout(queue, backoff).unwrap();
+                    }
+                    backoff = ::std::cmp::min(backoff * 2, MAX_BACKOFF);
+                    continue;
+                },
+            }
+        }
+
+        // Take the batch, putting it back if the connection fails partway through.  The server
+        // ignores a partial last line, though it may see some events twice.  A connection the
+        // server has closed can take a write or two to notice, so a few events may be lost.
+        let batch = ::std::mem::take(&mut shared.queue.lock().unwrap().events);
+        let (stream, names) = conn.as_mut().unwrap();
+        let result = batch.iter()
+            .try_for_each(|event| write_event(stream, names, event.clone()))
+            .and_then(|_| stream.flush());
+        if result.is_err() {
+            conn = None;
+            let mut queue = shared.queue.lock().unwrap();
+            let newer = ::std::mem::replace(&mut queue.events, batch);
+            queue.events.extend(newer);
+            while queue.events.len() > queue.capacity {
+                queue.events.pop_front();
+                queue.dropped += 1;
+            }
+            if closed {
+                return;
+            }
+        } else if closed && shared.queue.lock().unwrap().events.is_empty() {
+            return;
+        }
+    }
+}
+
+// Write `event` as a line, preceded by a definition of its name if this connection hasn't had one.
+fn write_event<W: Write>(stream: &mut W, names: &mut NameTable, mut event: TraceEvent) -> io::Result<()> {
+    if let Some(def) = event.name_mut().and_then(|name| names.intern(name)) {
+        write_line(stream, &def)?;
+    }
+    write_line(stream, &event)
+}
+
+fn write_line<W: Write>(stream: &mut W, event: &TraceEvent) -> io::Result<()> {
+    serde_json::to_writer(&mut *stream, event)?;
+    stream.write_all(b"\n")
+}
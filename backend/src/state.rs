@@ -1,14 +1,72 @@
 use std::cell::RefCell;
-use std::time::{Duration, Instant, SystemTime};
-use std::sync::{Arc, Mutex};
+use std::env;
+use std::fs;
+use std::process;
+use std::thread;
+use std::time::Duration;
+use std::sync::{Arc, Mutex, RwLock};
 
-use event::{SpanId, TraceEvent};
+use alloc;
+use clock::{Clock, RealClock};
+use context::ProcessId;
+use event::{SCHEMA_VERSION, Name, SpanId, TraceEvent};
 
 thread_local! {
     pub static TRACER_STATE: RefCell<TracerState> = RefCell::new(TracerState::default());
 }
 lazy_static! {
-    static ref EPOCH: (SystemTime, Instant) = (SystemTime::now(), Instant::now());
+    static ref CLOCK: RwLock<Arc<dyn Clock>> = RwLock::new(Arc::new(RealClock));
+    static ref UNTRACED_POLICY: RwLock<UntracedPolicy> = RwLock::new(UntracedPolicy::Skip);
+    static ref DEFAULT_LOGGER: Mutex<Option<SharedLogger>> = Mutex::new(None);
+}
+
+// A logger shared by every thread that logs to it.
+type SharedLogger = Arc<Mutex<Box<dyn Logger>>>;
+
+/// What to do when a span is started on a thread that never created a `TracedThread`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum UntracedPolicy {
+    /// Run the code without tracing it, apart from futures with a parent of their own (see
+    /// `TracedFuture::with_parent`), which log to the default logger.
+    Skip,
+    /// Start an implicit thread root named after the OS thread, logging to the default logger
+    /// (see `set_default_logger`).  Without a default logger, this is the same as `Skip`.
+    ImplicitRoot,
+}
+
+pub fn set_untraced_policy(policy: UntracedPolicy) {
+    *UNTRACED_POLICY.write().unwrap() = policy;
+}
+
+/// Set the process-wide logger used by implicit thread roots.
+pub fn set_default_logger<L: Logger + 'static>(logger: L) {
+    let logger: Box<dyn Logger> = Box::new(logger);
+    *DEFAULT_LOGGER.lock().unwrap() = Some(Arc::new(Mutex::new(logger)));
+}
+
+/// Remove the default logger, so threads without a `TracedThread` go untraced again.
+pub fn clear_default_logger() {
+    *DEFAULT_LOGGER.lock().unwrap() = None;
+}
+
+/// Set the process-wide clock, used by threads that haven't set their own.
+pub fn set_clock<C: Clock + 'static>(clock: C) {
+    *CLOCK.write().unwrap() = Arc::new(clock);
+}
+
+/// Set the clock for spans on the current thread only, e.g. one driven by a simulated executor.
+pub fn set_thread_clock<C: Clock + 'static>(clock: C) {
+    TRACER_STATE.with(|c| c.borrow_mut().clock = Some(Arc::new(clock)));
+}
+
+/// Go back to the process-wide clock on the current thread.
+pub fn reset_thread_clock() {
+    TRACER_STATE.with(|c| c.borrow_mut().clock = None);
+}
+
+/// A handle on the process-wide logger, if one's been set.
+pub fn default_logger() -> Option<Box<dyn Logger>> {
+    DEFAULT_LOGGER.lock().unwrap().clone().map(|logger| Box::new(logger) as Box<dyn Logger>)
 }
 
 pub trait Logger: Send {
@@ -24,6 +82,15 @@ impl Logger for DebugLogger {
     }
 }
 
+impl<L: Logger + ?Sized> Logger for Box<L> {
+    fn write(&mut self, event: TraceEvent) {
+        (**self).write(event)
+    }
+    fn flush(&mut self) {
+        (**self).flush()
+    }
+}
+
 impl<T: Logger> Logger for Arc<Mutex<T>> {
     fn write(&mut self, event: TraceEvent) {
         self.lock().unwrap().write(event)
@@ -40,29 +107,18 @@ impl Logger for NoopLogger {
     }
 }
 
+#[derive(Default)]
 pub struct TracerState {
     pub current_span: Option<SpanId>,
     pub currently_logging_wakeup: bool,
+    // `SpanHandle`s entered on this thread, with the span that was current before each.
+    pub entered_handles: Vec<(SpanId, Option<SpanId>)>,
 
     pub writer: Option<Box<dyn Logger>>,
-
-    start: Instant,
-    since_epoch: Duration,
-}
-
-impl Default for TracerState {
-    fn default() -> Self {
-        let (_, epoch) = *EPOCH;
-        let now = Instant::now();
-        TracerState {
-            current_span: None,
-            currently_logging_wakeup: false,
-            writer: None,
-
-            since_epoch: now.duration_since(epoch),
-            start: now,
-        }
-    }
+    // Root span we created ourselves because this thread had no `TracedThread`.
+    implicit_root: Option<SpanId>,
+    // Overrides the process-wide clock for this thread.
+    clock: Option<Arc<dyn Clock>>,
 }
 
 impl TracerState {
@@ -71,14 +127,122 @@ impl TracerState {
         self.writer = Some(writer);
     }
 
+    /// Make sure there's something to log to, for a span with a parent of its own on a thread
+    /// the `UntracedPolicy` skips: the default logger, if there is one.
+    pub fn ensure_writer(&mut self) -> bool {
+        if self.writer.is_none() {
+            self.writer = default_logger();
+        }
+        self.writer.is_some()
+    }
+
     pub fn emit(&mut self, event: TraceEvent) {
         if let Some(ref mut w) = self.writer.as_mut() {
-            w.write(event);
+            // Keep the cost of tracing out of the spans' allocation counts.
+            alloc::untracked(|| w.write(event));
         }
     }
 
     pub fn now(&self) -> Duration {
-        // Duration relative to thread start + relative to process start
-        Instant::now().duration_since(self.start) + self.since_epoch
+        self.clock().now()
+    }
+
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        match self.clock {
+            Some(ref clock) => clock.clone(),
+            None => CLOCK.read().unwrap().clone(),
+        }
+    }
+
+    /// The span a new span should be parented to.  On a thread without a `TracedThread`, this
+    /// follows the `UntracedPolicy`, and `None` means the new span shouldn't be traced at all.
+    pub fn parent_span(&mut self) -> Option<SpanId> {
+        if self.current_span.is_some() {
+            return self.current_span;
+        }
+        if *UNTRACED_POLICY.read().unwrap() != UntracedPolicy::ImplicitRoot {
+            return None;
+        }
+        let logger = default_logger()?;
+        self.start(logger);
+
+        let span_id = SpanId::new();
+        let event = TraceEvent::ThreadStart {
+            name: Name::new(thread_name()),
+            id: span_id,
+            ts: self.now(),
+            spawned_by: None,
+        };
+        self.emit(event);
+        self.implicit_root = Some(span_id);
+        self.current_span = Some(span_id);
+        Some(span_id)
+    }
+
+    /// End the implicit thread root, if we started one, e.g. because the thread is exiting or
+    /// about to create a real `TracedThread`.
+    pub fn end_implicit_root(&mut self) {
+        if let Some(id) = self.implicit_root.take() {
+            if self.current_span == Some(id) {
+                self.current_span = None;
+            }
+            let event = TraceEvent::ThreadEnd {
+                id,
+                ts: self.now(),
+            };
+            self.emit(event);
+            if let Some(ref mut w) = self.writer.as_mut() {
+                w.flush();
+            }
+            self.writer = None;
+        }
+    }
+}
+
+impl Drop for TracerState {
+    fn drop(&mut self) {
+        self.end_implicit_root();
+    }
+}
+
+/// Name of the current OS thread, or its id if it's unnamed.
+pub fn thread_name() -> String {
+    let current = thread::current();
+    match current.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", current.id()),
     }
 }
+
+/// The span executing on this thread, if any, e.g. to pass as a `SpanHandle`'s parent.
+pub fn current_span() -> Option<SpanId> {
+    TRACER_STATE.with(|c| c.borrow().current_span)
+}
+
+/// The event a trace should begin with: which process it came from, and when its clock started.
+pub fn header() -> TraceEvent {
+    let clock = TRACER_STATE.try_with(|c| c.try_borrow().ok().map(|st| st.clock()))
+        .ok()
+        .and_then(|clock| clock)
+        .unwrap_or_else(|| CLOCK.read().unwrap().clone());
+    TraceEvent::Header {
+        schema_version: SCHEMA_VERSION,
+        wall_clock: clock.epoch(),
+        clock: clock.name().to_string(),
+        process_id: ProcessId::current(),
+        pid: process::id(),
+        hostname: hostname(),
+        executable: env::current_exe().ok().map(|p| p.display().to_string()),
+        command_line: env::args().collect(),
+    }
+}
+
+fn hostname() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| fs::read_to_string("/etc/hostname"))
+        .ok()
+        .map(|h| h.trim().to_string())
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .filter(|h| !h.is_empty())
+}
@@ -1,5 +1,9 @@
+use std::borrow::Cow;
+use std::thread::{self, ThreadId};
 use serde_json;
-use event::{SpanId, TraceEvent};
+use alloc::{self, AllocMark};
+use context::SpanContext;
+use event::{Name, SpanId, TraceEvent};
 use state::{TRACER_STATE, Logger};
 
 pub struct TracedThread {
@@ -7,9 +11,15 @@ pub struct TracedThread {
 }
 
 impl TracedThread {
-    pub fn new<S: Into<String>>(name: S, writer: Box<dyn Logger>) -> Self {
+    pub fn new<S: Into<Cow<'static, str>>>(name: S, writer: Box<dyn Logger>) -> Self {
+        Self::spawned_by(name, writer, None)
+    }
+
+    /// Like `new`, also recording the span that spawned this thread.
+    pub fn spawned_by<S: Into<Cow<'static, str>>>(name: S, writer: Box<dyn Logger>, spawner: Option<SpanId>) -> Self {
         TRACER_STATE.with(|c| {
             let mut st = c.borrow_mut();
+            st.end_implicit_root();
             st.start(writer);
             let span_id = SpanId::new();
 
@@ -17,9 +27,10 @@ impl TracedThread {
             st.current_span = Some(span_id);
 
             let event = TraceEvent::ThreadStart {
-                name: name.into(),
+                name: Name::new(name),
                 id: span_id,
                 ts: st.now(),
+                spawned_by: spawner,
             };
             st.emit(event);
 
@@ -44,35 +55,61 @@ impl Drop for TracedThread {
 }
 
 pub struct SyncSpan {
-    parent: SpanId,
-    id: SpanId,
+    // Parent and own id, or `None` if the thread isn't being traced.
+    span: Option<(SpanId, SpanId)>,
+    // Allocation totals when we started.
+    alloc_start: Option<AllocMark>,
+    // The thread we started on, whose current span we are.
+    thread: ThreadId,
 }
 
 impl SyncSpan {
-    pub fn new<S: Into<String>>(name: S) -> Self {
+    pub fn new<S: Into<Cow<'static, str>>>(name: S) -> Self {
         Self::with_metadata(name, serde_json::Value::Null)
     }
 
-    pub fn with_metadata<S: Into<String>>(name: S, meta: serde_json::Value) -> Self {
+    pub fn with_metadata<S: Into<Cow<'static, str>>>(name: S, meta: serde_json::Value) -> Self {
+        Self::start(name.into(), meta, None)
+    }
+
+    /// Start a span under the current one that also records `remote` (typically parsed from
+    /// an incoming request) as its parent in another process.
+    pub fn with_remote_parent<S: Into<Cow<'static, str>>>(name: S, remote: SpanContext) -> Self {
+        Self::start(name.into(), serde_json::Value::Null, Some(remote))
+    }
+
+    fn start(name: Cow<'static, str>, meta: serde_json::Value, remote: Option<SpanContext>) -> Self {
         TRACER_STATE.with(|c| {
             let mut st = c.borrow_mut();
 
+            let parent_id = match st.parent_span() {
+                Some(parent_id) => parent_id,
+                None => return SyncSpan { span: None, alloc_start: None, thread: thread::current().id() },
+            };
             let span_id = SpanId::new();
-            let parent_id = st.current_span.take().expect("Missing parent span");
             st.current_span = Some(span_id);
 
             let event = TraceEvent::SyncStart {
-                name: name.into(),
+                name: Name::Str(name),
                 id: span_id,
-                parent_id: parent_id,
+                parent_id,
                 ts: st.now(),
                 metadata: meta,
             };
             st.emit(event);
+            if let Some(remote) = remote {
+                let event = TraceEvent::RemoteParent {
+                    id: span_id,
+                    parent: remote,
+                    ts: st.now(),
+                };
+                st.emit(event);
+            }
 
             SyncSpan {
-                parent: parent_id,
-                id: span_id,
+                span: Some((parent_id, span_id)),
+                alloc_start: alloc::snapshot(),
+                thread: thread::current().id(),
             }
         })
     }
@@ -80,14 +117,24 @@ impl SyncSpan {
 
 impl Drop for SyncSpan {
     fn drop(&mut self) {
+        let (parent_id, span_id) = match self.span {
+            Some(span) => span,
+            None => return,
+        };
+        let same_thread = self.thread == thread::current().id();
         TRACER_STATE.with(|c| {
             let mut st = c.borrow_mut();
-            assert_eq!(st.current_span, Some(self.id), "Current span changed during SyncSpan");
-            st.current_span = Some(self.parent);
+            // If something left another span current (a `SpanHandle` still entered, or spans
+            // dropped out of order), go back to our parent anyway rather than panicking in a drop.
+            // If we've been moved to another thread, its spans have nothing to do with us.
+            if same_thread {
+                st.current_span = Some(parent_id);
+            }
 
             let event = TraceEvent::SyncEnd {
-                id: self.id,
+                id: span_id,
                 ts: st.now(),
+                alloc: if same_thread { alloc::since(self.alloc_start) } else { None },
             };
             st.emit(event);
         })
@@ -0,0 +1,233 @@
+//! Helpers for testing that code emits the spans you expect.  Install a `CapturingLogger`, run
+//! the code, then check the rebuilt `SpanTree`:
+//!
+//! ```ignore
+//! let logger = CapturingLogger::new();
+//! let _thread = TracedThread::new("test", Box::new(logger.clone()));
+//! do_work();
+//! let tree = logger.tree();
+//! tree.assert_child_of("parse", "request");
+//! assert_eq!(tree.snapshot(), include_str!("request.snap"));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use event::{AsyncOutcome, LockPhase, Name, SpanId, TraceEvent};
+use state::Logger;
+
+/// Logger that keeps every event in memory.  Clones share the same events, so keep one around to
+/// inspect after handing another to a `TracedThread`.
+#[derive(Clone, Default)]
+pub struct CapturingLogger {
+    events: Arc<Mutex<Vec<TraceEvent>>>,
+}
+
+impl CapturingLogger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<TraceEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.events.lock().unwrap().clear();
+    }
+
+    pub fn tree(&self) -> SpanTree {
+        SpanTree::new(&self.events.lock().unwrap())
+    }
+}
+
+impl Logger for CapturingLogger {
+    fn write(&mut self, event: TraceEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SpanKind {
+    Thread,
+    Sync,
+    Async,
+    Lock(LockPhase),
+}
+
+#[derive(Debug)]
+pub struct SpanNode {
+    pub id: SpanId,
+    pub name: String,
+    pub kind: SpanKind,
+    pub parent: Option<SpanId>,
+    pub children: Vec<SpanId>,
+    pub ended: bool,
+    // How an async span ended; `None` for other kinds of spans and for async spans still running.
+    pub outcome: Option<AsyncOutcome>,
+    // Spans that woke this one, in the order they did so.
+    pub woken_by: Vec<SpanId>,
+}
+
+/// Spans rebuilt from a list of events.
+#[derive(Debug, Default)]
+pub struct SpanTree {
+    nodes: HashMap<SpanId, SpanNode>,
+    // Every span, in the order it started.
+    order: Vec<SpanId>,
+}
+
+impl SpanTree {
+    pub fn new(events: &[TraceEvent]) -> Self {
+        let mut tree = SpanTree::default();
+        for event in events {
+            match *event {
+                TraceEvent::ThreadStart { ref name, id, .. } => {
+                    tree.add(id, name, SpanKind::Thread, None);
+                },
+                TraceEvent::SyncStart { ref name, id, parent_id, .. } => {
+                    tree.add(id, name, SpanKind::Sync, Some(parent_id));
+                },
+                TraceEvent::AsyncStart { ref name, id, parent_id, .. } => {
+                    tree.add(id, name, SpanKind::Async, Some(parent_id));
+                },
+                TraceEvent::LockStart { ref name, id, parent_id, phase, .. } => {
+                    tree.add(id, name, SpanKind::Lock(phase), Some(parent_id));
+                },
+                TraceEvent::ThreadEnd { id, .. }
+                | TraceEvent::SyncEnd { id, .. }
+                | TraceEvent::LockEnd { id, .. } => {
+                    if let Some(node) = tree.nodes.get_mut(&id) {
+                        node.ended = true;
+                    }
+                },
+                TraceEvent::AsyncEnd { id, ref outcome, .. } => {
+                    if let Some(node) = tree.nodes.get_mut(&id) {
+                        node.ended = true;
+                        node.outcome = Some(outcome.clone());
+                    }
+                },
+                TraceEvent::Wakeup { waking_span, parked_span, .. } => {
+                    if let Some(node) = tree.nodes.get_mut(&parked_span) {
+                        node.woken_by.push(waking_span);
+                    }
+                },
+                _ => (),
+            }
+        }
+        tree
+    }
+
+    fn add(&mut self, id: SpanId, name: &Name, kind: SpanKind, parent: Option<SpanId>) {
+        // Spans whose parent we never saw (e.g. logged elsewhere) become roots.
+        let parent = parent.filter(|p| self.nodes.contains_key(p));
+        if let Some(parent) = parent {
+            self.nodes.get_mut(&parent).unwrap().children.push(id);
+        }
+        self.nodes.insert(id, SpanNode {
+            id,
+            name: name.to_string(),
+            kind,
+            parent,
+            children: vec![],
+            ended: false,
+            outcome: None,
+            woken_by: vec![],
+        });
+        self.order.push(id);
+    }
+
+    pub fn get(&self, id: SpanId) -> Option<&SpanNode> {
+        self.nodes.get(&id)
+    }
+
+    /// All spans named `name`, in the order they started.
+    pub fn find(&self, name: &str) -> Vec<&SpanNode> {
+        self.order.iter()
+            .map(|id| &self.nodes[id])
+            .filter(|node| node.name == name)
+            .collect()
+    }
+
+    /// The first span named `name`.  Panics if there isn't one.
+    pub fn span(&self, name: &str) -> &SpanNode {
+        match self.find(name).into_iter().next() {
+            Some(node) => node,
+            None => panic!("no span named {:?}\n{}", name, self.snapshot()),
+        }
+    }
+
+    pub fn parent(&self, node: &SpanNode) -> Option<&SpanNode> {
+        node.parent.and_then(|p| self.get(p))
+    }
+
+    /// Assert that some span named `child` is a direct child of a span named `parent`.
+    pub fn assert_child_of(&self, child: &str, parent: &str) {
+        let found = self.find(child).into_iter()
+            .any(|node| self.parent(node).is_some_and(|p| p.name == parent));
+        assert!(found, "no span named {:?} is a child of one named {:?}\n{}", child, parent, self.snapshot());
+    }
+
+    /// Assert that some span named `name` ended with an error.
+    pub fn assert_error(&self, name: &str) {
+        let found = self.find(name).into_iter()
+            .any(|node| matches!(node.outcome, Some(AsyncOutcome::Error(..))));
+        assert!(found, "no span named {:?} ended with an error\n{}", name, self.snapshot());
+    }
+
+    /// Assert that some span named `name` was woken by a span named `waker`.
+    pub fn assert_woken_by(&self, name: &str, waker: &str) {
+        let found = self.find(name).into_iter()
+            .flat_map(|node| node.woken_by.iter())
+            .any(|w| self.get(*w).is_some_and(|w| w.name == waker));
+        assert!(found, "no span named {:?} was woken by one named {:?}\n{}", name, waker, self.snapshot());
+    }
+
+    /// The tree as indented text, one span per line, for comparing against a golden file.  Span
+    /// ids are replaced with their start order (`#0`, `#1`, ...) and timestamps are left out, so
+    /// the output is the same from run to run as long as the spans start in the same order.
+    pub fn snapshot(&self) -> String {
+        let ordinals: HashMap<SpanId, usize> = self.order.iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut out = String::new();
+        for id in &self.order {
+            if self.nodes[id].parent.is_none() {
+                self.write_node(&mut out, &ordinals, *id, 0);
+            }
+        }
+        out
+    }
+
+    fn write_node(&self, out: &mut String, ordinals: &HashMap<SpanId, usize>, id: SpanId, depth: usize) {
+        let node = &self.nodes[&id];
+        let indent = "  ".repeat(depth);
+        let kind = match node.kind {
+            SpanKind::Thread => "thread",
+            SpanKind::Sync => "sync",
+            SpanKind::Async => "async",
+            SpanKind::Lock(LockPhase::Wait) => "lock wait",
+            SpanKind::Lock(LockPhase::Hold) => "lock hold",
+        };
+        write!(out, "{}{} {:?} #{}", indent, kind, node.name, ordinals[&id]).unwrap();
+        match node.outcome {
+            Some(ref outcome) => write!(out, ": {:?}", outcome).unwrap(),
+            None if !node.ended => out.push_str(" (unfinished)"),
+            None => (),
+        }
+        out.push('\n');
+
+        // Spurious wakeups make the count vary, so only list each waker once.
+        let mut wakers: Vec<usize> = node.woken_by.iter().filter_map(|w| ordinals.get(w).cloned()).collect();
+        wakers.sort();
+        wakers.dedup();
+        for waker in wakers {
+            writeln!(out, "{}  woken by #{}", indent, waker).unwrap();
+        }
+
+        for child in &node.children {
+            self.write_node(out, ordinals, *child, depth + 1);
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use std::fs::File;
+use std::env;
+use std::fs::{self, File};
 use std::thread;
 use std::time::Duration;
 use futures::{
@@ -6,18 +7,73 @@ use futures::{
     Future,
     Stream,
 };
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use futures::executor::{self, Notify, NotifyHandle};
+use futures::future::{Executor, ExecuteError};
 use futures::sync::oneshot;
 use futures::stream::futures_unordered::FuturesUnordered;
 use state::Logger;
+use event::AsyncOutcome;
 use ::{
+    clear_default_logger,
+    current_span,
+    init,
+    reset_thread_clock,
+    set_thread_clock,
+    Clock,
+    ManualClock,
+    Name,
+    RealClock,
+    set_clock,
+    TscClock,
+    set_default_logger,
+    set_untraced_policy,
+    Config,
     DebugLogger,
+    SpanHandle,
+    TraceEvent,
+    SpanContext,
     TracedThread,
     SyncSpan,
     TraceFuture,
+    TracedFuture,
+    TracedAlloc,
+    TracedExecutor,
+    TracedMutex,
+    LockPhase,
+    UntracedPolicy,
 };
 
 use json::JsonWriter;
+use testing::{CapturingLogger, SpanKind};
+
+#[global_allocator]
+static ALLOC: TracedAlloc = TracedAlloc::system();
+
+lazy_static! {
+    // Held by tests that change the process-wide logger or untraced policy.
+    static ref GLOBAL_CONFIG: Mutex<()> = Mutex::new(());
+}
+
+// Exclusive use of the process-wide config, put back to the defaults when dropped, even if the
+// test fails.
+struct GlobalConfig {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl GlobalConfig {
+    fn lock() -> Self {
+        GlobalConfig { _guard: GLOBAL_CONFIG.lock().unwrap_or_else(|e| e.into_inner()) }
+    }
+}
+
+impl Drop for GlobalConfig {
+    fn drop(&mut self) {
+        set_untraced_policy(UntracedPolicy::Skip);
+        clear_default_logger();
+        set_clock(RealClock);
+    }
+}
 
 #[test]
 fn test_sync() {
@@ -26,6 +82,713 @@ fn test_sync() {
     let _second_span = SyncSpan::new("second_span");
 }
 
+#[test]
+fn test_remote_context() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_remote_context", Box::new(logger.clone()));
+    let _span = SyncSpan::new("client");
+
+    let context = SpanContext::current().unwrap();
+    let header = context.to_header();
+    assert_eq!(SpanContext::from_header(&header), Some(context));
+    assert_eq!(SpanContext::from_header("not a context"), None);
+
+    let _server = SyncSpan::with_remote_parent("server", context);
+    future::ok::<(), ()>(()).traced_remote("handler", context).wait().unwrap();
+
+    let tree = logger.tree();
+    assert_eq!(context.span_id, tree.span("client").id);
+    let links = logger.events().iter().filter_map(|e| match *e {
+        TraceEvent::RemoteParent { id, parent, .. } => Some((id, parent)),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(links, vec![(tree.span("server").id, context), (tree.span("handler").id, context)]);
+}
+
+#[test]
+fn test_untraced_wakeup() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_untraced_wakeup", Box::new(logger.clone()));
+
+    let (tx, rx) = oneshot::channel::<usize>();
+    let sender = thread::Builder::new()
+        .name("reactor".to_string())
+        .spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            tx.send(1).unwrap();
+        })
+        .unwrap();
+    assert_eq!(rx.traced("rx").wait().unwrap(), 1);
+    sender.join().unwrap();
+
+    let rx_id = logger.tree().span("rx").id;
+    assert!(logger.events().iter().any(|e| match *e {
+        TraceEvent::UntracedWakeup { ref waking_thread, parked_span, .. } => {
+            waking_thread == "reactor" && parked_span == rx_id
+        },
+        _ => false,
+    }));
+}
+
+struct NoopNotify;
+
+impl Notify for NoopNotify {
+    fn notify(&self, _: usize) {
+    }
+}
+
+#[test]
+fn test_migrating_future() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_migrating_future", Box::new(logger.clone()));
+
+    // Poll once here, then finish the future on another thread.
+    let (tx, rx) = oneshot::channel::<usize>();
+    let mut task = executor::spawn(rx.traced("migrant"));
+    let notify = NotifyHandle::from(Arc::new(NoopNotify));
+    assert!(task.poll_future_notify(&notify, 0).unwrap().is_not_ready());
+
+    let worker_logger = logger.clone();
+    let worker = thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(move || {
+            let _thread = TracedThread::new("worker", Box::new(worker_logger));
+            tx.send(1).unwrap();
+            task.wait_future().unwrap()
+        })
+        .unwrap();
+    assert_eq!(worker.join().unwrap(), 1);
+
+    let tree = logger.tree();
+    tree.assert_child_of("migrant", "test_migrating_future");
+    assert!(tree.span("migrant").ended);
+    let migrant = tree.span("migrant").id;
+    assert!(logger.events().iter().any(|e| match *e {
+        TraceEvent::AsyncMigrate { id, ref thread, .. } => id == migrant && thread == "worker",
+        _ => false,
+    }));
+}
+
+#[test]
+fn test_span_handle() {
+    let logger = CapturingLogger::new();
+    let thread = TracedThread::new("test_span_handle", Box::new(logger.clone()));
+    let root = current_span().unwrap();
+
+    let mut first = SpanHandle::start("first", root);
+    let mut second = SpanHandle::start("second", first.id());
+    first.enter();
+    second.enter();
+    // Out of order: `second` stays current after `first` exits.
+    first.exit();
+    assert_eq!(current_span(), Some(second.id()));
+    second.exit();
+    assert_eq!(current_span(), Some(root));
+
+    first.enter();
+    first.enter();
+    first.end();
+    second.end_with_error("timed out");
+    let third = SpanHandle::start("third", root);
+    drop(third);
+    drop(thread);
+
+    let tree = logger.tree();
+    tree.assert_child_of("first", "test_span_handle");
+    tree.assert_child_of("second", "first");
+    tree.assert_child_of("third", "test_span_handle");
+    assert!(matches!(tree.span("first").outcome, Some(AsyncOutcome::Success)));
+    tree.assert_error("second");
+    assert!(matches!(tree.span("third").outcome, Some(AsyncOutcome::Cancelled)));
+}
+
+#[test]
+fn test_span_handle_misuse() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_span_handle_misuse", Box::new(logger.clone()));
+    let root = current_span().unwrap();
+
+    // A handle left entered inside a sync span doesn't outlive it as the current span.
+    let mut handle = SpanHandle::start("leaked", root);
+    {
+        let _span = SyncSpan::new("sync");
+        handle.enter();
+    }
+    assert_eq!(current_span(), Some(root));
+
+    // Exiting or ending on another thread leaves that thread's current span alone.
+    let other = logger.clone();
+    thread::spawn(move || {
+        let _thread = TracedThread::new("other", Box::new(other));
+        let other_root = current_span();
+        handle.exit();
+        assert_eq!(current_span(), other_root);
+
+        let span = SyncSpan::new("moved");
+        thread::spawn(move || {
+            let _thread = TracedThread::new("another", Box::new(CapturingLogger::new()));
+            let another_root = current_span();
+            drop(span);
+            assert_eq!(current_span(), another_root);
+        }).join().unwrap();
+    }).join().unwrap();
+    let tree = logger.tree();
+    tree.assert_child_of("leaked", "test_span_handle_misuse");
+    tree.assert_child_of("moved", "other");
+    assert!(tree.span("leaked").ended);
+}
+
+#[test]
+fn test_untraced_thread() {
+    let run = |name: &str| {
+        thread::Builder::new()
+            .name(name.to_string())
+            .spawn(|| {
+                let _span = SyncSpan::new("sync");
+                future::ok::<(), ()>(()).traced("async").wait().unwrap();
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    };
+    let _config = GlobalConfig::lock();
+    let logger = CapturingLogger::new();
+    set_default_logger(logger.clone());
+
+    set_untraced_policy(UntracedPolicy::Skip);
+    run("skipped");
+    assert!(logger.events().is_empty());
+
+    set_untraced_policy(UntracedPolicy::ImplicitRoot);
+    run("implicit");
+
+    // The implicit root is started and ended around everything the thread did.
+    let events = logger.events();
+    assert!(matches!(events.first(), Some(TraceEvent::ThreadStart { .. })));
+    assert!(matches!(events.last(), Some(TraceEvent::ThreadEnd { .. })));
+    let tree = logger.tree();
+    let root = tree.span("implicit");
+    assert_eq!(root.kind, SpanKind::Thread);
+    assert!(root.ended);
+    assert!(root.parent.is_none());
+    tree.assert_child_of("sync", "implicit");
+    tree.assert_child_of("async", "sync");
+    assert!(tree.find("skipped").is_empty());
+}
+
+#[test]
+fn test_init_clock() {
+    let _config = GlobalConfig::lock();
+    let path = env::temp_dir().join("cyclotron_test_init_clock.log");
+    let clock = ManualClock::with_epoch(Duration::from_secs(1_500_000_000));
+    init(Config::new(JsonWriter::new(File::create(&path).unwrap())).clock(clock));
+    thread::spawn(|| {
+        let _span = SyncSpan::new("span");
+    }).join().unwrap();
+    clear_default_logger();
+
+    // The header describes the clock the events were timestamped with, though the writer was
+    // created before it was installed.
+    let trace = fs::read_to_string(&path).unwrap();
+    match serde_json::from_str(trace.lines().next().unwrap()).unwrap() {
+        TraceEvent::Header { clock, wall_clock, .. } => {
+            assert_eq!(clock, "manual");
+            assert_eq!(wall_clock, Duration::from_secs(1_500_000_000));
+        },
+        event => panic!("expected a header, got {:?}", event),
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_spawn() {
+    let _config = GlobalConfig::lock();
+    let logger = CapturingLogger::new();
+    init(Config::new(logger.clone()));
+
+    // The spawning thread joins in implicitly; the spawned one is traced from the start.
+    let spawner = thread::spawn(|| {
+        let span = SyncSpan::new("spawner");
+        let spawner_id = current_span();
+        let worker = ::thread::Builder::new()
+            .name("worker".to_string())
+            .spawn(|| {
+                let _span = SyncSpan::new("work");
+            })
+            .unwrap();
+        worker.join().unwrap();
+        drop(span);
+        spawner_id
+    }).join().unwrap();
+
+    let tree = logger.tree();
+    tree.assert_child_of("work", "worker");
+    assert_eq!(spawner, Some(tree.span("spawner").id));
+    assert!(logger.events().iter().any(|e| match *e {
+        TraceEvent::ThreadStart { ref name, spawned_by, .. } => name == "worker" && spawned_by == spawner,
+        _ => false,
+    }));
+}
+
+// Runs each future to completion on a fresh traced thread.
+struct ThreadExecutor(CapturingLogger);
+
+impl<F: Future<Item=(), Error=()> + Send + 'static> Executor<F> for ThreadExecutor {
+    fn execute(&self, future: F) -> Result<(), ExecuteError<F>> {
+        let logger = self.0.clone();
+        thread::spawn(move || {
+            let _thread = TracedThread::new("worker", Box::new(logger));
+            future.wait().unwrap();
+        });
+        Ok(())
+    }
+}
+
+#[test]
+fn test_traced_executor() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_traced_executor", Box::new(logger.clone()));
+    let executor = TracedExecutor::with_name(ThreadExecutor(logger.clone()), "spawned");
+
+    let (tx, rx) = oneshot::channel::<usize>();
+    {
+        let _span = SyncSpan::new("spawner");
+        executor.execute(future::lazy(move || tx.send(1).map_err(|_| ()))).unwrap();
+    }
+    assert_eq!(rx.wait().unwrap(), 1);
+
+    logger.tree().assert_child_of("spawned", "spawner");
+}
+
+#[test]
+fn test_with_parent_on_untraced_thread() {
+    let _config = GlobalConfig::lock();
+    let logger = CapturingLogger::new();
+    set_default_logger(logger.clone());
+    set_untraced_policy(UntracedPolicy::Skip);
+    let _thread = TracedThread::new("test_with_parent_on_untraced_thread", Box::new(logger.clone()));
+    let spawner = current_span().unwrap();
+
+    // A worker thread the policy skips still traces a task that brings its own parent, but
+    // nothing else.
+    thread::spawn(move || {
+        let task = future::lazy(|| {
+            let _span = SyncSpan::new("inside");
+            Ok::<_, ()>(())
+        });
+        TracedFuture::with_parent(task, "task", spawner).wait().unwrap();
+        future::ok::<(), ()>(()).traced("skipped").wait().unwrap();
+    }).join().unwrap();
+
+    let tree = logger.tree();
+    tree.assert_child_of("task", "test_with_parent_on_untraced_thread");
+    tree.assert_child_of("inside", "task");
+    assert!(tree.span("task").ended);
+    assert!(tree.find("skipped").is_empty());
+}
+
+#[test]
+fn test_channels() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_channels", Box::new(logger.clone()));
+
+    let (tx, rx) = ::channel::std_mpsc::channel("requests");
+    {
+        let _span = SyncSpan::new("sender");
+        tx.send(1).unwrap();
+    }
+    let (otx, orx) = ::channel::oneshot::channel("reply");
+    future::lazy(move || {
+        otx.send(rx.recv().unwrap() + 1).unwrap();
+        Ok::<_, ()>(())
+    }).traced("receiver").wait().unwrap();
+    assert_eq!(orx.wait().unwrap(), 2);
+
+    let tree = logger.tree();
+    let events = logger.events();
+    let sent = events.iter().filter_map(|e| match *e {
+        TraceEvent::MessageSend { msg, ref channel, span, .. } if channel == "requests" => Some((msg, span)),
+        _ => None,
+    }).collect::<Vec<_>>();
+    let received = events.iter().filter_map(|e| match *e {
+        TraceEvent::MessageRecv { msg, ref channel, span, .. } if channel == "requests" => Some((msg, span)),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(received.len(), 1);
+    assert_eq!(sent[0].0, received[0].0);
+    assert_eq!(sent[0].1, Some(tree.span("sender").id));
+    assert_eq!(received[0].1, Some(tree.span("receiver").id));
+
+    let depths = events.iter().filter_map(|e| match *e {
+        TraceEvent::Counter { ref name, value, .. } if name == "requests" => Some(value),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(depths, vec![1, 0]);
+
+    // Sends that don't go through aren't logged, however often they're retried.
+    logger.clear();
+    let (tx, rx) = ::channel::std_mpsc::sync_channel("bounded", 1);
+    tx.try_send(1).unwrap();
+    for _ in 0..3 {
+        assert!(matches!(tx.try_send(2), Err(::channel::std_mpsc::TrySendError::Full(2))));
+    }
+    drop(rx);
+    assert!(tx.try_send(3).is_err());
+    let events = logger.events();
+    let sends = events.iter().filter(|e| matches!(e, TraceEvent::MessageSend { .. })).count();
+    assert_eq!(sends, 1);
+    let depths = events.iter().filter_map(|e| match *e {
+        TraceEvent::Counter { ref name, value, .. } if name == "bounded" => Some(value),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(depths, vec![1]);
+}
+
+#[test]
+fn test_lock_contention() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_lock_contention", Box::new(logger.clone()));
+    let lock = Arc::new(TracedMutex::new("counter", 0));
+
+    let guard = lock.lock().unwrap();
+    let waiter = {
+        let (lock, logger) = (lock.clone(), logger.clone());
+        thread::spawn(move || {
+            let _thread = TracedThread::new("waiter", Box::new(logger));
+            *lock.lock().unwrap() += 1;
+        })
+    };
+    // Give the waiter a chance to block on us.
+    while !logger.tree().find("counter").iter().any(|node| node.kind == SpanKind::Lock(LockPhase::Wait)) {
+        thread::sleep(Duration::from_millis(1));
+    }
+    drop(guard);
+    waiter.join().unwrap();
+    assert_eq!(*lock.lock().unwrap(), 1);
+
+    let tree = logger.tree();
+    let holds = tree.find("counter").into_iter()
+        .filter(|node| node.kind == SpanKind::Lock(LockPhase::Hold))
+        .map(|node| node.id)
+        .collect::<Vec<_>>();
+    assert_eq!(holds.len(), 3);
+    assert!(tree.find("counter").iter().all(|node| node.ended));
+    let holders = logger.events().iter().filter_map(|e| match *e {
+        TraceEvent::LockStart { phase: LockPhase::Wait, holder, .. } => Some(holder),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(holders, vec![Some(holds[0])]);
+}
+
+#[test]
+fn test_alloc() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_alloc", Box::new(logger.clone()));
+    {
+        let _span = SyncSpan::new("allocating");
+        let buf = vec![0u8; 4096];
+        assert_eq!(buf.len(), 4096);
+        // Counted against the nested span only.
+        let _nested = SyncSpan::new("nested");
+        let buf = vec![0u8; 1024];
+        assert_eq!(buf.len(), 1024);
+    }
+    future::lazy(|| Ok::<_, ()>(Box::new(7))).traced("boxing").wait().unwrap();
+
+    let tree = logger.tree();
+    let events = logger.events();
+    let sync_alloc = |name: &str| {
+        let span = tree.span(name).id;
+        events.iter().filter_map(|e| match *e {
+            TraceEvent::SyncEnd { id, alloc, .. } if id == span => alloc,
+            _ => None,
+        }).next().unwrap()
+    };
+    let (allocating, nested) = (sync_alloc("allocating"), sync_alloc("nested"));
+    assert_eq!(allocating.count, 1);
+    assert!(allocating.bytes >= 4096 && allocating.bytes < 4096 + 1024);
+    assert_eq!(nested.count, 1);
+    assert!(nested.bytes >= 1024);
+
+    let boxing = tree.span("boxing").id;
+    let async_alloc = events.iter().filter_map(|e| match *e {
+        TraceEvent::AsyncOffCPU { id, alloc, .. } if id == boxing => alloc,
+        _ => None,
+    }).next().unwrap();
+    assert!(async_alloc.count >= 1);
+}
+
+#[test]
+fn test_capturing_logger() {
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_capturing_logger", Box::new(logger.clone()));
+    {
+        let _request = SyncSpan::new("request");
+        let (tx, rx) = oneshot::channel::<usize>();
+        let mut task = executor::spawn(rx.traced("rx"));
+        let notify = NotifyHandle::from(Arc::new(NoopNotify));
+        assert!(task.poll_future_notify(&notify, 0).unwrap().is_not_ready());
+        {
+            let _send = SyncSpan::new("send");
+            tx.send(1).unwrap();
+        }
+        assert!(task.poll_future_notify(&notify, 0).unwrap().is_ready());
+        future::err::<(), _>("boom").traced("fails").wait().unwrap_err();
+    }
+
+    let tree = logger.tree();
+    tree.assert_child_of("rx", "request");
+    tree.assert_woken_by("rx", "send");
+    tree.assert_error("fails");
+    assert_eq!(tree.snapshot(), r#"thread "test_capturing_logger" #0 (unfinished)
+  sync "request" #1
+    async "rx" #2: Success
+      woken by #3
+    sync "send" #3
+    async "fails" #4: Error("\"boom\"")
+"#);
+}
+
+#[test]
+fn test_manual_clock() {
+    let clock = ManualClock::new();
+    set_thread_clock(clock.clone());
+    let logger = CapturingLogger::new();
+    let _thread = TracedThread::new("test_manual_clock", Box::new(logger.clone()));
+    {
+        let _span = SyncSpan::new("span");
+        clock.advance(Duration::from_millis(5));
+    }
+    match ::state::header() {
+        TraceEvent::Header { ref clock, wall_clock, .. } => {
+            assert_eq!(clock, "manual");
+            assert_eq!(wall_clock, Duration::from_secs(0));
+        },
+        _ => unreachable!(),
+    }
+    reset_thread_clock();
+
+    let timestamps = logger.events().iter().filter_map(|e| match *e {
+        TraceEvent::SyncStart { ts, .. } | TraceEvent::SyncEnd { ts, .. } => Some(ts),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(timestamps, vec![Duration::from_millis(0), Duration::from_millis(5)]);
+}
+
+#[test]
+fn test_tsc_clock() {
+    let clock = TscClock::calibrate();
+    let first = clock.now();
+    thread::sleep(Duration::from_millis(5));
+    let second = clock.now();
+    assert!(second >= first + Duration::from_millis(4));
+}
+
+#[test]
+fn test_json_interning() {
+    let path = env::temp_dir().join("cyclotron_test_json_interning.log");
+    let mut logger = Arc::new(Mutex::new(JsonWriter::new(File::create(&path).unwrap())));
+    {
+        let _thread = TracedThread::new("test_json_interning", Box::new(logger.clone()));
+        for _ in 0..3 {
+            let _span = SyncSpan::new("repeated");
+        }
+    }
+    logger.flush();
+
+    let events = fs::read_to_string(&path).unwrap().lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect::<Vec<TraceEvent>>();
+    fs::remove_file(&path).unwrap();
+    let defs = events.iter().filter_map(|e| match *e {
+        TraceEvent::NameDef { id, ref name } => Some((id, name.to_string())),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(defs, vec![(0, "test_json_interning".to_string()), (1, "repeated".to_string())]);
+    let names = events.iter().filter_map(|e| match *e {
+        TraceEvent::SyncStart { ref name, .. } => Some(name.clone()),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(names, vec![Name::Ref(1); 3]);
+}
+
+#[cfg(all(feature = "gzip", feature = "zstd"))]
+#[test]
+fn test_compressed_frames() {
+    use std::io::Read;
+    use compress::{GzipWriter, ZstdWriter};
+
+    fn decode_gzip(bytes: &[u8]) -> String {
+        let mut out = String::new();
+        ::flate2::read::MultiGzDecoder::new(bytes).read_to_string(&mut out).unwrap();
+        out
+    }
+    fn decode_zstd(bytes: &[u8]) -> String {
+        String::from_utf8(::zstd::stream::decode_all(bytes).unwrap()).unwrap()
+    }
+
+    let gzip_path = env::temp_dir().join("cyclotron_test_compressed_frames.log.gz");
+    let zstd_path = env::temp_dir().join("cyclotron_test_compressed_frames.log.zst");
+    let mut gzip = JsonWriter::new(GzipWriter::new(File::create(&gzip_path).unwrap()));
+    let mut zstd = JsonWriter::new(ZstdWriter::new(File::create(&zstd_path).unwrap()).unwrap());
+
+    // Each flush ends a frame, so everything so far decodes while the writers are still open.
+    let counter = TraceEvent::Counter { name: Name::new("queue"), value: 3, ts: Duration::from_secs(1) };
+    gzip.write(counter.clone());
+    zstd.write(counter.clone());
+    gzip.flush();
+    zstd.flush();
+    let gzip_lines = decode_gzip(&fs::read(&gzip_path).unwrap());
+    let zstd_lines = decode_zstd(&fs::read(&zstd_path).unwrap());
+    assert_eq!(gzip_lines.lines().count(), 3);
+    assert_eq!(gzip_lines, zstd_lines);
+
+    gzip.write(counter.clone());
+    zstd.write(counter);
+    gzip.flush();
+    zstd.flush();
+    // Flushing again without writing anything doesn't add an empty frame.
+    let gzip_len = fs::metadata(&gzip_path).unwrap().len();
+    gzip.flush();
+    assert_eq!(fs::metadata(&gzip_path).unwrap().len(), gzip_len);
+    drop(gzip);
+    drop(zstd);
+
+    let gzip_lines = decode_gzip(&fs::read(&gzip_path).unwrap());
+    let zstd_lines = decode_zstd(&fs::read(&zstd_path).unwrap());
+    fs::remove_file(&gzip_path).unwrap();
+    fs::remove_file(&zstd_path).unwrap();
+    assert_eq!(gzip_lines, zstd_lines);
+    let events = gzip_lines.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect::<Vec<TraceEvent>>();
+    assert_eq!(events.len(), 4);
+    match events[3] {
+        TraceEvent::Counter { ref name, value: 3, .. } => assert_eq!(*name, Name::Ref(0)),
+        ref e => panic!("unexpected event {:?}", e),
+    }
+}
+
+#[test]
+fn test_compressed_flush_retry() {
+    use std::io::{self, Read, Write};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use compress::{GzipWriter, ZstdWriter};
+
+    // A file on a disk that's full until `full` is cleared.
+    #[derive(Clone, Default)]
+    struct Disk {
+        bytes: Arc<Mutex<Vec<u8>>>,
+        full: Arc<AtomicBool>,
+    }
+    impl Write for Disk {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.full.load(Ordering::SeqCst) {
+                return Err(io::Error::other("disk full"));
+            }
+            self.bytes.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    // Write two frames, the first of which only goes out on the second try.
+    fn write_frames<W: Write>(mut writer: W, disk: &Disk) {
+        writer.write_all(b"first\n").unwrap();
+        disk.full.store(true, Ordering::SeqCst);
+        assert!(writer.flush().is_err());
+        disk.full.store(false, Ordering::SeqCst);
+        writer.flush().unwrap();
+        writer.write_all(b"second\n").unwrap();
+        writer.flush().unwrap();
+    }
+
+    let gzip = Disk::default();
+    write_frames(GzipWriter::new(gzip.clone()), &gzip);
+    let mut out = String::new();
+    ::flate2::read::MultiGzDecoder::new(&gzip.bytes.lock().unwrap()[..]).read_to_string(&mut out).unwrap();
+    assert_eq!(out, "first\nsecond\n");
+
+    let zstd = Disk::default();
+    write_frames(ZstdWriter::new(zstd.clone()).unwrap(), &zstd);
+    let out = ::zstd::stream::decode_all(&zstd.bytes.lock().unwrap()[..]).unwrap();
+    assert_eq!(out, b"first\nsecond\n");
+}
+
+#[test]
+fn test_net_logger() {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use net::NetLogger;
+
+    fn read_events(reader: &mut BufReader<::std::net::TcpStream>, n: usize) -> Vec<TraceEvent> {
+        (0..n).map(|_| {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        }).collect()
+    }
+    fn counter(value: u64) -> TraceEvent {
+        TraceEvent::Counter { name: Name::new("depth"), value, ts: Duration::from_secs(value) }
+    }
+
+    // Events logged before the server is listening are buffered until it is.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    let mut logger = NetLogger::tcp(addr.to_string());
+    logger.write(counter(1));
+    logger.flush();
+    let listener = TcpListener::bind(addr).unwrap();
+
+    // Each connection starts with a header, and names are defined before they're used.
+    let (conn, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(conn);
+    let events = read_events(&mut reader, 3);
+    assert!(matches!(events[0], TraceEvent::Header { .. }));
+    assert!(matches!(events[1], TraceEvent::NameDef { id: 0, ref name } if name == "depth"));
+    assert!(matches!(events[2], TraceEvent::Counter { name: Name::Ref(0), value: 1, .. }));
+
+    // After the server hangs up, the logger only notices once a write fails, so keep logging
+    // until something arrives on the new connection.  Then log one last event.
+    drop(reader);
+    let (stop, stopped) = mpsc::channel();
+    let reader = thread::scope(|s| {
+        let logger = &mut logger;
+        s.spawn(move || {
+            let mut value = 2;
+            loop {
+                logger.write(counter(value));
+                logger.flush();
+                value += 1;
+                if stopped.recv_timeout(Duration::from_millis(10)).is_ok() {
+                    break;
+                }
+            }
+            logger.write(counter(1000));
+        });
+        let (conn, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(conn);
+        let events = read_events(&mut reader, 3);
+        stop.send(()).unwrap();
+        // The new connection defines its names again.
+        assert!(matches!(events[0], TraceEvent::Header { .. }));
+        assert!(matches!(events[1], TraceEvent::NameDef { id: 0, ref name } if name == "depth"));
+        assert!(matches!(events[2], TraceEvent::Counter { name: Name::Ref(0), .. }));
+        reader
+    });
+    drop(logger);
+
+    let values = reader.lines().map(|line| match serde_json::from_str(&line.unwrap()).unwrap() {
+        TraceEvent::Counter { name: Name::Ref(0), value, .. } => value,
+        ref e => panic!("unexpected event {:?}", e),
+    }).collect::<Vec<_>>();
+    assert_eq!(values.last(), Some(&1000));
+}
+
 #[test]
 fn test_async() {
     let mut logger = Arc::new(Mutex::new(JsonWriter::new(File::create("/tmp/test.log").unwrap())));
@@ -0,0 +1,52 @@
+//! Wrappers around `std::thread` that trace the threads they spawn, logging to the logger
+//! installed with `cyclotron::init`.
+
+use std::io;
+use std::thread::{self, JoinHandle};
+use state::{self, current_span};
+use sync::TracedThread;
+
+/// Like `std::thread::spawn`, but the new thread starts a `TracedThread` named after itself
+/// that records the span it was spawned from.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+    where F: FnOnce() -> T + Send + 'static, T: Send + 'static
+{
+    Builder::new().spawn(f).expect("failed to spawn thread")
+}
+
+/// Like `std::thread::Builder`, for spawning traced threads.
+#[derive(Debug)]
+pub struct Builder {
+    inner: thread::Builder,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Builder { inner: thread::Builder::new() }
+    }
+
+    pub fn name(self, name: String) -> Self {
+        Builder { inner: self.inner.name(name) }
+    }
+
+    pub fn stack_size(self, size: usize) -> Self {
+        Builder { inner: self.inner.stack_size(size) }
+    }
+
+    pub fn spawn<F, T>(self, f: F) -> io::Result<JoinHandle<T>>
+        where F: FnOnce() -> T + Send + 'static, T: Send + 'static
+    {
+        let spawner = current_span();
+        self.inner.spawn(move || {
+            let _thread = state::default_logger()
+                .map(|logger| TracedThread::spawned_by(state::thread_name(), logger, spawner));
+            f()
+        })
+    }
+}
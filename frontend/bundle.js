@@ -325,6 +325,8 @@ exports.nextCombination = function(v) {
 },{}],3:[function(require,module,exports){
 
 },{}],4:[function(require,module,exports){
+arguments[4][3][0].apply(exports,arguments)
+},{"dup":3}],5:[function(require,module,exports){
 /*!
  * The buffer module from node.js, for the browser.
  *
@@ -2062,7 +2064,7 @@ function numberIsNaN (obj) {
   return obj !== obj // eslint-disable-line no-self-compare
 }
 
-},{"base64-js":1,"ieee754":42}],5:[function(require,module,exports){
+},{"base64-js":1,"ieee754":44}],6:[function(require,module,exports){
 module.exports = {
   "100": "Continue",
   "101": "Switching Protocols",
@@ -2128,7 +2130,7 @@ module.exports = {
   "511": "Network Authentication Required"
 }
 
-},{}],6:[function(require,module,exports){
+},{}],7:[function(require,module,exports){
 (function (Buffer){
 // Copyright Joyent, Inc. and other Node contributors.
 //
@@ -2239,7 +2241,7 @@ function objectToString(o) {
 }
 
 }).call(this,{"isBuffer":require("../../is-buffer/index.js")})
-},{"../../is-buffer/index.js":44}],7:[function(require,module,exports){
+},{"../../is-buffer/index.js":46}],8:[function(require,module,exports){
 // https://d3js.org/d3-array/ Version 1.2.1. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -2831,7 +2833,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],8:[function(require,module,exports){
+},{}],9:[function(require,module,exports){
 // https://d3js.org/d3-axis/ Version 1.0.8. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -3026,7 +3028,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],9:[function(require,module,exports){
+},{}],10:[function(require,module,exports){
 // https://d3js.org/d3-brush/ Version 1.0.4. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-dispatch'), require('d3-drag'), require('d3-interpolate'), require('d3-selection'), require('d3-transition')) :
@@ -3595,7 +3597,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-dispatch":13,"d3-drag":14,"d3-interpolate":21,"d3-selection":29,"d3-transition":34}],10:[function(require,module,exports){
+},{"d3-dispatch":14,"d3-drag":15,"d3-interpolate":22,"d3-selection":30,"d3-transition":35}],11:[function(require,module,exports){
 // https://d3js.org/d3-chord/ Version 1.0.4. Copyright 2017 Mike Bostock.
 (function (global, factory) {
   typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-array'), require('d3-path')) :
@@ -3827,7 +3829,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-array":7,"d3-path":22}],11:[function(require,module,exports){
+},{"d3-array":8,"d3-path":23}],12:[function(require,module,exports){
 // https://d3js.org/d3-collection/ Version 1.0.4. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -4046,7 +4048,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],12:[function(require,module,exports){
+},{}],13:[function(require,module,exports){
 // https://d3js.org/d3-color/ Version 1.0.3. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -4571,7 +4573,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],13:[function(require,module,exports){
+},{}],14:[function(require,module,exports){
 // https://d3js.org/d3-dispatch/ Version 1.0.3. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -4668,7 +4670,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],14:[function(require,module,exports){
+},{}],15:[function(require,module,exports){
 // https://d3js.org/d3-drag/ Version 1.2.1. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-dispatch'), require('d3-selection')) :
@@ -4904,7 +4906,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-dispatch":13,"d3-selection":29}],15:[function(require,module,exports){
+},{"d3-dispatch":14,"d3-selection":30}],16:[function(require,module,exports){
 // https://d3js.org/d3-dsv/ Version 1.0.8. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -5068,7 +5070,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],16:[function(require,module,exports){
+},{}],17:[function(require,module,exports){
 // https://d3js.org/d3-ease/ Version 1.0.3. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -5329,7 +5331,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],17:[function(require,module,exports){
+},{}],18:[function(require,module,exports){
 // https://d3js.org/d3-force/ Version 1.1.0. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-quadtree'), require('d3-collection'), require('d3-dispatch'), require('d3-timer')) :
@@ -5991,7 +5993,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-collection":11,"d3-dispatch":13,"d3-quadtree":24,"d3-timer":33}],18:[function(require,module,exports){
+},{"d3-collection":12,"d3-dispatch":14,"d3-quadtree":25,"d3-timer":34}],19:[function(require,module,exports){
 // https://d3js.org/d3-format/ Version 1.2.2. Copyright 2018 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -6324,7 +6326,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],19:[function(require,module,exports){
+},{}],20:[function(require,module,exports){
 // https://d3js.org/d3-geo/ Version 1.9.1. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-array')) :
@@ -9371,7 +9373,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-array":7}],20:[function(require,module,exports){
+},{"d3-array":8}],21:[function(require,module,exports){
 // https://d3js.org/d3-hierarchy/ Version 1.1.5. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -10660,7 +10662,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],21:[function(require,module,exports){
+},{}],22:[function(require,module,exports){
 // https://d3js.org/d3-interpolate/ Version 1.1.6. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-color')) :
@@ -11207,7 +11209,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-color":12}],22:[function(require,module,exports){
+},{"d3-color":13}],23:[function(require,module,exports){
 // https://d3js.org/d3-path/ Version 1.0.5. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -11350,7 +11352,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],23:[function(require,module,exports){
+},{}],24:[function(require,module,exports){
 // https://d3js.org/d3-polygon/ Version 1.0.3. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -11502,7 +11504,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],24:[function(require,module,exports){
+},{}],25:[function(require,module,exports){
 // https://d3js.org/d3-quadtree/ Version 1.0.3. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -11939,7 +11941,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],25:[function(require,module,exports){
+},{}],26:[function(require,module,exports){
 // https://d3js.org/d3-queue/ Version 3.0.7. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -12075,7 +12077,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],26:[function(require,module,exports){
+},{}],27:[function(require,module,exports){
 // https://d3js.org/d3-random/ Version 1.1.0. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -12192,7 +12194,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],27:[function(require,module,exports){
+},{}],28:[function(require,module,exports){
 'use strict';
 
 var XMLHttpRequest = require('xmlhttprequest').XMLHttpRequest;
@@ -12409,7 +12411,7 @@ exports.xml = xml;
 exports.csv = csv;
 exports.tsv = tsv;
 
-},{"d3-collection":11,"d3-dispatch":13,"d3-dsv":15,"xmlhttprequest":285}],28:[function(require,module,exports){
+},{"d3-collection":12,"d3-dispatch":14,"d3-dsv":16,"xmlhttprequest":285}],29:[function(require,module,exports){
 // https://d3js.org/d3-scale/ Version 1.0.7. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-array'), require('d3-collection'), require('d3-interpolate'), require('d3-format'), require('d3-time'), require('d3-time-format'), require('d3-color')) :
@@ -13336,7 +13338,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-array":7,"d3-collection":11,"d3-color":12,"d3-format":18,"d3-interpolate":21,"d3-time":32,"d3-time-format":31}],29:[function(require,module,exports){
+},{"d3-array":8,"d3-collection":12,"d3-color":13,"d3-format":19,"d3-interpolate":22,"d3-time":33,"d3-time-format":32}],30:[function(require,module,exports){
 // https://d3js.org/d3-selection/ Version 1.3.0. Copyright 2018 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -14333,7 +14335,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],30:[function(require,module,exports){
+},{}],31:[function(require,module,exports){
 // https://d3js.org/d3-shape/ Version 1.2.0. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-path')) :
@@ -16270,7 +16272,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-path":22}],31:[function(require,module,exports){
+},{"d3-path":23}],32:[function(require,module,exports){
 // https://d3js.org/d3-time-format/ Version 2.1.1. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-time')) :
@@ -16960,7 +16962,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-time":32}],32:[function(require,module,exports){
+},{"d3-time":33}],33:[function(require,module,exports){
 // https://d3js.org/d3-time/ Version 1.0.8. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -17347,7 +17349,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],33:[function(require,module,exports){
+},{}],34:[function(require,module,exports){
 // https://d3js.org/d3-timer/ Version 1.0.7. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -17498,7 +17500,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],34:[function(require,module,exports){
+},{}],35:[function(require,module,exports){
 // https://d3js.org/d3-transition/ Version 1.1.1. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-selection'), require('d3-dispatch'), require('d3-timer'), require('d3-interpolate'), require('d3-color'), require('d3-ease')) :
@@ -18287,7 +18289,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-color":12,"d3-dispatch":13,"d3-ease":16,"d3-interpolate":21,"d3-selection":29,"d3-timer":33}],35:[function(require,module,exports){
+},{"d3-color":13,"d3-dispatch":14,"d3-ease":17,"d3-interpolate":22,"d3-selection":30,"d3-timer":34}],36:[function(require,module,exports){
 // https://d3js.org/d3-voronoi/ Version 1.1.2. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports) :
@@ -19288,7 +19290,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{}],36:[function(require,module,exports){
+},{}],37:[function(require,module,exports){
 // https://d3js.org/d3-zoom/ Version 1.7.1. Copyright 2017 Mike Bostock.
 (function (global, factory) {
 	typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('d3-dispatch'), require('d3-drag'), require('d3-interpolate'), require('d3-selection'), require('d3-transition')) :
@@ -19792,7 +19794,7 @@ Object.defineProperty(exports, '__esModule', { value: true });
 
 })));
 
-},{"d3-dispatch":13,"d3-drag":14,"d3-interpolate":21,"d3-selection":29,"d3-transition":34}],37:[function(require,module,exports){
+},{"d3-dispatch":14,"d3-drag":15,"d3-interpolate":22,"d3-selection":30,"d3-transition":35}],38:[function(require,module,exports){
 'use strict';
 
 Object.defineProperty(exports, '__esModule', { value: true });
@@ -19863,7 +19865,7 @@ Object.keys(d3Voronoi).forEach(function (key) { exports[key] = d3Voronoi[key]; }
 Object.keys(d3Zoom).forEach(function (key) { exports[key] = d3Zoom[key]; });
 Object.defineProperty(exports, "event", {get: function() { return d3Selection.event; }});
 
-},{"d3-array":7,"d3-axis":8,"d3-brush":9,"d3-chord":10,"d3-collection":11,"d3-color":12,"d3-dispatch":13,"d3-drag":14,"d3-dsv":15,"d3-ease":16,"d3-force":17,"d3-format":18,"d3-geo":19,"d3-hierarchy":20,"d3-interpolate":21,"d3-path":22,"d3-polygon":23,"d3-quadtree":24,"d3-queue":25,"d3-random":26,"d3-request":27,"d3-scale":28,"d3-selection":29,"d3-shape":30,"d3-time":32,"d3-time-format":31,"d3-timer":33,"d3-transition":34,"d3-voronoi":35,"d3-zoom":36}],38:[function(require,module,exports){
+},{"d3-array":8,"d3-axis":9,"d3-brush":10,"d3-chord":11,"d3-collection":12,"d3-color":13,"d3-dispatch":14,"d3-drag":15,"d3-dsv":16,"d3-ease":17,"d3-force":18,"d3-format":19,"d3-geo":20,"d3-hierarchy":21,"d3-interpolate":22,"d3-path":23,"d3-polygon":24,"d3-quadtree":25,"d3-queue":26,"d3-random":27,"d3-request":28,"d3-scale":29,"d3-selection":30,"d3-shape":31,"d3-time":33,"d3-time-format":32,"d3-timer":34,"d3-transition":35,"d3-voronoi":36,"d3-zoom":37}],39:[function(require,module,exports){
 'use strict';
 
 module.exports = earcut;
@@ -20515,7 +20517,7 @@ earcut.flatten = function (data) {
     return result;
 };
 
-},{}],39:[function(require,module,exports){
+},{}],40:[function(require,module,exports){
 'use strict';
 
 var has = Object.prototype.hasOwnProperty
@@ -20828,7 +20830,528 @@ if ('undefined' !== typeof module) {
   module.exports = EventEmitter;
 }
 
-},{}],40:[function(require,module,exports){
+},{}],41:[function(require,module,exports){
+// Copyright Joyent, Inc. and other Node contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a
+// copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to permit
+// persons to whom the Software is furnished to do so, subject to the
+// following conditions:
+//
+// The above copyright notice and this permission notice shall be included
+// in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN
+// NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE
+// USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+var objectCreate = Object.create || objectCreatePolyfill
+var objectKeys = Object.keys || objectKeysPolyfill
+var bind = Function.prototype.bind || functionBindPolyfill
+
+function EventEmitter() {
+  if (!this._events || !Object.prototype.hasOwnProperty.call(this, '_events')) {
+    this._events = objectCreate(null);
+    this._eventsCount = 0;
+  }
+
+  this._maxListeners = this._maxListeners || undefined;
+}
+module.exports = EventEmitter;
+
+// Backwards-compat with node 0.10.x
+EventEmitter.EventEmitter = EventEmitter;
+
+EventEmitter.prototype._events = undefined;
+EventEmitter.prototype._maxListeners = undefined;
+
+// By default EventEmitters will print a warning if more than 10 listeners are
+// added to it. This is a useful default which helps finding memory leaks.
+var defaultMaxListeners = 10;
+
+var hasDefineProperty;
+try {
+  var o = {};
+  if (Object.defineProperty) Object.defineProperty(o, 'x', { value: 0 });
+  hasDefineProperty = o.x === 0;
+} catch (err) { hasDefineProperty = false }
+if (hasDefineProperty) {
+  Object.defineProperty(EventEmitter, 'defaultMaxListeners', {
+    enumerable: true,
+    get: function() {
+      return defaultMaxListeners;
+    },
+    set: function(arg) {
+      // check whether the input is a positive number (whose value is zero or
+      // greater and not a NaN).
+      if (typeof arg !== 'number' || arg < 0 || arg !== arg)
+        throw new TypeError('"defaultMaxListeners" must be a positive number');
+      defaultMaxListeners = arg;
+    }
+  });
+} else {
+  EventEmitter.defaultMaxListeners = defaultMaxListeners;
+}
+
+// Obviously not all Emitters should be limited to 10. This function allows
+// that to be increased. Set to zero for unlimited.
+EventEmitter.prototype.setMaxListeners = function setMaxListeners(n) {
+  if (typeof n !== 'number' || n < 0 || isNaN(n))
+    throw new TypeError('"n" argument must be a positive number');
+  this._maxListeners = n;
+  return this;
+};
+
+function $getMaxListeners(that) {
+  if (that._maxListeners === undefined)
+    return EventEmitter.defaultMaxListeners;
+  return that._maxListeners;
+}
+
+EventEmitter.prototype.getMaxListeners = function getMaxListeners() {
+  return $getMaxListeners(this);
+};
+
+// These standalone emit* functions are used to optimize calling of event
+// handlers for fast cases because emit() itself often has a variable number of
+// arguments and can be deoptimized because of that. These functions always have
+// the same number of arguments and thus do not get deoptimized, so the code
+// inside them can execute faster.
+function emitNone(handler, isFn, self) {
+  if (isFn)
+    handler.call(self);
+  else {
+    var len = handler.length;
+    var listeners = arrayClone(handler, len);
+    for (var i = 0; i < len; ++i)
+      listeners[i].call(self);
+  }
+}
+function emitOne(handler, isFn, self, arg1) {
+  if (isFn)
+    handler.call(self, arg1);
+  else {
+    var len = handler.length;
+    var listeners = arrayClone(handler, len);
+    for (var i = 0; i < len; ++i)
+      listeners[i].call(self, arg1);
+  }
+}
+function emitTwo(handler, isFn, self, arg1, arg2) {
+  if (isFn)
+    handler.call(self, arg1, arg2);
+  else {
+    var len = handler.length;
+    var listeners = arrayClone(handler, len);
+    for (var i = 0; i < len; ++i)
+      listeners[i].call(self, arg1, arg2);
+  }
+}
+function emitThree(handler, isFn, self, arg1, arg2, arg3) {
+  if (isFn)
+    handler.call(self, arg1, arg2, arg3);
+  else {
+    var len = handler.length;
+    var listeners = arrayClone(handler, len);
+    for (var i = 0; i < len; ++i)
+      listeners[i].call(self, arg1, arg2, arg3);
+  }
+}
+
+function emitMany(handler, isFn, self, args) {
+  if (isFn)
+    handler.apply(self, args);
+  else {
+    var len = handler.length;
+    var listeners = arrayClone(handler, len);
+    for (var i = 0; i < len; ++i)
+      listeners[i].apply(self, args);
+  }
+}
+
+EventEmitter.prototype.emit = function emit(type) {
+  var er, handler, len, args, i, events;
+  var doError = (type === 'error');
+
+  events = this._events;
+  if (events)
+    doError = (doError && events.error == null);
+  else if (!doError)
+    return false;
+
+  // If there is no 'error' event listener then throw.
+  if (doError) {
+    if (arguments.length > 1)
+      er = arguments[1];
+    if (er instanceof Error) {
+      throw er; // Unhandled 'error' event
+    } else {
+      // At least give some kind of context to the user
+      var err = new Error('Unhandled "error" event. (' + er + ')');
+      err.context = er;
+      throw err;
+    }
+    return false;
+  }
+
+  handler = events[type];
+
+  if (!handler)
+    return false;
+
+  var isFn = typeof handler === 'function';
+  len = arguments.length;
+  switch (len) {
+      // fast cases
+    case 1:
+      emitNone(handler, isFn, this);
+      break;
+    case 2:
+      emitOne(handler, isFn, this, arguments[1]);
+      break;
+    case 3:
+      emitTwo(handler, isFn, this, arguments[1], arguments[2]);
+      break;
+    case 4:
+      emitThree(handler, isFn, this, arguments[1], arguments[2], arguments[3]);
+      break;
+      // slower
+    default:
+      args = new Array(len - 1);
+      for (i = 1; i < len; i++)
+        args[i - 1] = arguments[i];
+      emitMany(handler, isFn, this, args);
+  }
+
+  return true;
+};
+
+function _addListener(target, type, listener, prepend) {
+  var m;
+  var events;
+  var existing;
+
+  if (typeof listener !== 'function')
+    throw new TypeError('"listener" argument must be a function');
+
+  events = target._events;
+  if (!events) {
+    events = target._events = objectCreate(null);
+    target._eventsCount = 0;
+  } else {
+    // To avoid recursion in the case that type === "newListener"! Before
+    // adding it to the listeners, first emit "newListener".
+    if (events.newListener) {
+      target.emit('newListener', type,
+          listener.listener ? listener.listener : listener);
+
+      // Re-assign `events` because a newListener handler could have caused the
+      // this._events to be assigned to a new object
+      events = target._events;
+    }
+    existing = events[type];
+  }
+
+  if (!existing) {
+    // Optimize the case of one listener. Don't need the extra array object.
+    existing = events[type] = listener;
+    ++target._eventsCount;
+  } else {
+    if (typeof existing === 'function') {
+      // Adding the second element, need to change to array.
+      existing = events[type] =
+          prepend ? [listener, existing] : [existing, listener];
+    } else {
+      // If we've already got an array, just append.
+      if (prepend) {
+        existing.unshift(listener);
+      } else {
+        existing.push(listener);
+      }
+    }
+
+    // Check for listener leak
+    if (!existing.warned) {
+      m = $getMaxListeners(target);
+      if (m && m > 0 && existing.length > m) {
+        existing.warned = true;
+        var w = new Error('Possible EventEmitter memory leak detected. ' +
+            existing.length + ' "' + String(type) + '" listeners ' +
+            'added. Use emitter.setMaxListeners() to ' +
+            'increase limit.');
+        w.name = 'MaxListenersExceededWarning';
+        w.emitter = target;
+        w.type = type;
+        w.count = existing.length;
+        if (typeof console === 'object' && console.warn) {
+          console.warn('%s: %s', w.name, w.message);
+        }
+      }
+    }
+  }
+
+  return target;
+}
+
+EventEmitter.prototype.addListener = function addListener(type, listener) {
+  return _addListener(this, type, listener, false);
+};
+
+EventEmitter.prototype.on = EventEmitter.prototype.addListener;
+
+EventEmitter.prototype.prependListener =
+    function prependListener(type, listener) {
+      return _addListener(this, type, listener, true);
+    };
+
+function onceWrapper() {
+  if (!this.fired) {
+    this.target.removeListener(this.type, this.wrapFn);
+    this.fired = true;
+    switch (arguments.length) {
+      case 0:
+        return this.listener.call(this.target);
+      case 1:
+        return this.listener.call(this.target, arguments[0]);
+      case 2:
+        return this.listener.call(this.target, arguments[0], arguments[1]);
+      case 3:
+        return this.listener.call(this.target, arguments[0], arguments[1],
+            arguments[2]);
+      default:
+        var args = new Array(arguments.length);
+        for (var i = 0; i < args.length; ++i)
+          args[i] = arguments[i];
+        this.listener.apply(this.target, args);
+    }
+  }
+}
+
+function _onceWrap(target, type, listener) {
+  var state = { fired: false, wrapFn: undefined, target: target, type: type, listener: listener };
+  var wrapped = bind.call(onceWrapper, state);
+  wrapped.listener = listener;
+  state.wrapFn = wrapped;
+  return wrapped;
+}
+
+EventEmitter.prototype.once = function once(type, listener) {
+  if (typeof listener !== 'function')
+    throw new TypeError('"listener" argument must be a function');
+  this.on(type, _onceWrap(this, type, listener));
+  return this;
+};
+
+EventEmitter.prototype.prependOnceListener =
+    function prependOnceListener(type, listener) {
+      if (typeof listener !== 'function')
+        throw new TypeError('"listener" argument must be a function');
+      this.prependListener(type, _onceWrap(this, type, listener));
+      return this;
+    };
+
+// Emits a 'removeListener' event if and only if the listener was removed.
+EventEmitter.prototype.removeListener =
+    function removeListener(type, listener) {
+      var list, events, position, i, originalListener;
+
+      if (typeof listener !== 'function')
+        throw new TypeError('"listener" argument must be a function');
+
+      events = this._events;
+      if (!events)
+        return this;
+
+      list = events[type];
+      if (!list)
+        return this;
+
+      if (list === listener || list.listener === listener) {
+        if (--this._eventsCount === 0)
+          this._events = objectCreate(null);
+        else {
+          delete events[type];
+          if (events.removeListener)
+            this.emit('removeListener', type, list.listener || listener);
+        }
+      } else if (typeof list !== 'function') {
+        position = -1;
+
+        for (i = list.length - 1; i >= 0; i--) {
+          if (list[i] === listener || list[i].listener === listener) {
+            originalListener = list[i].listener;
+            position = i;
+            break;
+          }
+        }
+
+        if (position < 0)
+          return this;
+
+        if (position === 0)
+          list.shift();
+        else
+          spliceOne(list, position);
+
+        if (list.length === 1)
+          events[type] = list[0];
+
+        if (events.removeListener)
+          this.emit('removeListener', type, originalListener || listener);
+      }
+
+      return this;
+    };
+
+EventEmitter.prototype.removeAllListeners =
+    function removeAllListeners(type) {
+      var listeners, events, i;
+
+      events = this._events;
+      if (!events)
+        return this;
+
+      // not listening for removeListener, no need to emit
+      if (!events.removeListener) {
+        if (arguments.length === 0) {
+          this._events = objectCreate(null);
+          this._eventsCount = 0;
+        } else if (events[type]) {
+          if (--this._eventsCount === 0)
+            this._events = objectCreate(null);
+          else
+            delete events[type];
+        }
+        return this;
+      }
+
+      // emit removeListener for all listeners on all events
+      if (arguments.length === 0) {
+        var keys = objectKeys(events);
+        var key;
+        for (i = 0; i < keys.length; ++i) {
+          key = keys[i];
+          if (key === 'removeListener') continue;
+          this.removeAllListeners(key);
+        }
+        this.removeAllListeners('removeListener');
+        this._events = objectCreate(null);
+        this._eventsCount = 0;
+        return this;
+      }
+
+      listeners = events[type];
+
+      if (typeof listeners === 'function') {
+        this.removeListener(type, listeners);
+      } else if (listeners) {
+        // LIFO order
+        for (i = listeners.length - 1; i >= 0; i--) {
+          this.removeListener(type, listeners[i]);
+        }
+      }
+
+      return this;
+    };
+
+EventEmitter.prototype.listeners = function listeners(type) {
+  var evlistener;
+  var ret;
+  var events = this._events;
+
+  if (!events)
+    ret = [];
+  else {
+    evlistener = events[type];
+    if (!evlistener)
+      ret = [];
+    else if (typeof evlistener === 'function')
+      ret = [evlistener.listener || evlistener];
+    else
+      ret = unwrapListeners(evlistener);
+  }
+
+  return ret;
+};
+
+EventEmitter.listenerCount = function(emitter, type) {
+  if (typeof emitter.listenerCount === 'function') {
+    return emitter.listenerCount(type);
+  } else {
+    return listenerCount.call(emitter, type);
+  }
+};
+
+EventEmitter.prototype.listenerCount = listenerCount;
+function listenerCount(type) {
+  var events = this._events;
+
+  if (events) {
+    var evlistener = events[type];
+
+    if (typeof evlistener === 'function') {
+      return 1;
+    } else if (evlistener) {
+      return evlistener.length;
+    }
+  }
+
+  return 0;
+}
+
+EventEmitter.prototype.eventNames = function eventNames() {
+  return this._eventsCount > 0 ? Reflect.ownKeys(this._events) : [];
+};
+
+// About 1.5x faster than the two-arg version of Array#splice().
+function spliceOne(list, index) {
+  for (var i = index, k = i + 1, n = list.length; k < n; i += 1, k += 1)
+    list[i] = list[k];
+  list.pop();
+}
+
+function arrayClone(arr, n) {
+  var copy = new Array(n);
+  for (var i = 0; i < n; ++i)
+    copy[i] = arr[i];
+  return copy;
+}
+
+function unwrapListeners(arr) {
+  var ret = new Array(arr.length);
+  for (var i = 0; i < ret.length; ++i) {
+    ret[i] = arr[i].listener || arr[i];
+  }
+  return ret;
+}
+
+function objectCreatePolyfill(proto) {
+  var F = function() {};
+  F.prototype = proto;
+  return new F;
+}
+function objectKeysPolyfill(obj) {
+  var keys = [];
+  for (var k in obj) if (Object.prototype.hasOwnProperty.call(obj, k)) {
+    keys.push(k);
+  }
+  return k;
+}
+function functionBindPolyfill(context) {
+  var fn = this;
+  return function () {
+    return fn.apply(context, arguments);
+  };
+}
+
+},{}],42:[function(require,module,exports){
 module.exports = exists;
 
 module.exports.allExist = allExist;
@@ -20841,7 +21364,7 @@ function allExist (/* vals */) {
   var vals = Array.prototype.slice.call(arguments);
   return vals.every(exists);
 }
-},{}],41:[function(require,module,exports){
+},{}],43:[function(require,module,exports){
 var http = require('http')
 var url = require('url')
 
@@ -20874,7 +21397,7 @@ function validateParams (params) {
   return params
 }
 
-},{"http":274,"url":280}],42:[function(require,module,exports){
+},{"http":276,"url":282}],44:[function(require,module,exports){
 exports.read = function (buffer, offset, isLE, mLen, nBytes) {
   var e, m
   var eLen = nBytes * 8 - mLen - 1
@@ -20960,7 +21483,7 @@ exports.write = function (buffer, value, offset, isLE, mLen, nBytes) {
   buffer[offset + i - d] |= s * 128
 }
 
-},{}],43:[function(require,module,exports){
+},{}],45:[function(require,module,exports){
 if (typeof Object.create === 'function') {
   // implementation from standard node.js 'util' module
   module.exports = function inherits(ctor, superCtor) {
@@ -20985,7 +21508,7 @@ if (typeof Object.create === 'function') {
   }
 }
 
-},{}],44:[function(require,module,exports){
+},{}],46:[function(require,module,exports){
 /*!
  * Determine if an object is a Buffer
  *
@@ -21008,14 +21531,14 @@ function isSlowBuffer (obj) {
   return typeof obj.readFloatLE === 'function' && typeof obj.slice === 'function' && isBuffer(obj.slice(0, 0))
 }
 
-},{}],45:[function(require,module,exports){
+},{}],47:[function(require,module,exports){
 var toString = {}.toString;
 
 module.exports = Array.isArray || function (arr) {
   return toString.call(arr) == '[object Array]';
 };
 
-},{}],46:[function(require,module,exports){
+},{}],48:[function(require,module,exports){
 /**
  * isMobile.js v0.4.1
  *
@@ -21154,7 +21677,7 @@ module.exports = Array.isArray || function (arr) {
 
 })(this);
 
-},{}],47:[function(require,module,exports){
+},{}],49:[function(require,module,exports){
 'use strict';
 
 Object.defineProperty(exports, '__esModule', {
@@ -21321,7 +21844,7 @@ MiniSignal.MiniSignalBinding = MiniSignalBinding;
 exports['default'] = MiniSignal;
 module.exports = exports['default'];
 
-},{}],48:[function(require,module,exports){
+},{}],50:[function(require,module,exports){
 /*
 object-assign
 (c) Sindre Sorhus
@@ -21413,7 +21936,7 @@ module.exports = shouldUseNative() ? Object.assign : function (target, source) {
 	return to;
 };
 
-},{}],49:[function(require,module,exports){
+},{}],51:[function(require,module,exports){
 'use strict'
 
 module.exports = function parseURI (str, opts) {
@@ -21445,7 +21968,7 @@ module.exports = function parseURI (str, opts) {
   return uri
 }
 
-},{}],50:[function(require,module,exports){
+},{}],52:[function(require,module,exports){
 (function (process){
 // Copyright Joyent, Inc. and other Node contributors.
 //
@@ -21673,7 +22196,7 @@ var substr = 'ab'.substr(-1) === 'b'
 ;
 
 }).call(this,require('_process'))
-},{"_process":244}],51:[function(require,module,exports){
+},{"_process":246}],53:[function(require,module,exports){
 
 /*
 	Copyright © 2001 Robert Penner
@@ -21941,7 +22464,7 @@ var substr = 'ab'.substr(-1) === 'b'
 
 }).call(this);
 
-},{}],52:[function(require,module,exports){
+},{}],54:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -22029,7 +22552,7 @@ var angle = function (_wait) {
 
 module.exports = angle;
 
-},{"./wait":62}],53:[function(require,module,exports){
+},{"./wait":64}],55:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -22116,7 +22639,7 @@ var face = function (_wait) {
 
 module.exports = face;
 
-},{"./wait":62,"yy-angle":287}],54:[function(require,module,exports){
+},{"./wait":64,"yy-angle":287}],56:[function(require,module,exports){
 'use strict';
 
 var list = require('./list');
@@ -22134,7 +22657,7 @@ module.exports = {
     load: require('./load')
 };
 
-},{"./angle":52,"./face":53,"./list":55,"./load":56,"./movie":57,"./shake":58,"./target":59,"./tint":60,"./to":61,"./wait":62}],55:[function(require,module,exports){
+},{"./angle":54,"./face":55,"./list":57,"./load":58,"./movie":59,"./shake":60,"./target":61,"./tint":62,"./to":63,"./wait":64}],57:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -22485,7 +23008,7 @@ var Ease = function (_Events) {
 
 module.exports = Ease;
 
-},{"./angle":52,"./face":53,"./load":56,"./movie":57,"./shake":58,"./target":59,"./tint":60,"./to":61,"./wait":62,"eventemitter3":63,"pixi.js":209}],56:[function(require,module,exports){
+},{"./angle":54,"./face":55,"./load":58,"./movie":59,"./shake":60,"./target":61,"./tint":62,"./to":63,"./wait":64,"eventemitter3":65,"pixi.js":211}],58:[function(require,module,exports){
 'use strict';
 
 var wait = require('./wait');
@@ -22528,7 +23051,7 @@ function load(object, load) {
 
 module.exports = load;
 
-},{"./angle":52,"./face":53,"./movie":57,"./shake":58,"./target":59,"./tint":60,"./to":61,"./wait":62}],57:[function(require,module,exports){
+},{"./angle":54,"./face":55,"./movie":59,"./shake":60,"./target":61,"./tint":62,"./to":63,"./wait":64}],59:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -22647,7 +23170,7 @@ var movie = function (_wait) {
 
 module.exports = movie;
 
-},{"./wait":62}],58:[function(require,module,exports){
+},{"./wait":64}],60:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -22763,7 +23286,7 @@ var shake = function (_wait) {
 
 module.exports = shake;
 
-},{"./wait":62}],59:[function(require,module,exports){
+},{"./wait":64}],61:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -22852,7 +23375,7 @@ var target = function (_wait) {
 
 module.exports = target;
 
-},{"./wait":62}],60:[function(require,module,exports){
+},{"./wait":64}],62:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -23014,7 +23537,7 @@ var tint = function (_wait) {
 
 module.exports = tint;
 
-},{"./wait":62,"yy-color":288}],61:[function(require,module,exports){
+},{"./wait":64,"yy-color":288}],63:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -23213,7 +23736,7 @@ var to = function (_wait) {
 
 module.exports = to;
 
-},{"./wait":62}],62:[function(require,module,exports){
+},{"./wait":64}],64:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -23422,7 +23945,7 @@ var wait = function (_EventEmitter) {
 
 module.exports = wait;
 
-},{"eventemitter3":63,"penner":51}],63:[function(require,module,exports){
+},{"eventemitter3":65,"penner":53}],65:[function(require,module,exports){
 'use strict';
 
 var has = Object.prototype.hasOwnProperty
@@ -23760,7 +24283,7 @@ if ('undefined' !== typeof module) {
   module.exports = EventEmitter;
 }
 
-},{}],64:[function(require,module,exports){
+},{}],66:[function(require,module,exports){
 var EMPTY_ARRAY_BUFFER = new ArrayBuffer(0);
 
 /**
@@ -23881,7 +24404,7 @@ Buffer.prototype.destroy = function(){
 
 module.exports = Buffer;
 
-},{}],65:[function(require,module,exports){
+},{}],67:[function(require,module,exports){
 
 var Texture = require('./GLTexture');
 
@@ -24109,7 +24632,7 @@ Framebuffer.createFloat32 = function(gl, width, height, data)
 
 module.exports = Framebuffer;
 
-},{"./GLTexture":67}],66:[function(require,module,exports){
+},{"./GLTexture":69}],68:[function(require,module,exports){
 
 var compileProgram = require('./shader/compileProgram'),
 	extractAttributes = require('./shader/extractAttributes'),
@@ -24205,7 +24728,7 @@ Shader.prototype.destroy = function()
 
 module.exports = Shader;
 
-},{"./shader/compileProgram":72,"./shader/extractAttributes":74,"./shader/extractUniforms":75,"./shader/generateUniformAccessObject":76,"./shader/setPrecision":80}],67:[function(require,module,exports){
+},{"./shader/compileProgram":74,"./shader/extractAttributes":76,"./shader/extractUniforms":77,"./shader/generateUniformAccessObject":78,"./shader/setPrecision":82}],69:[function(require,module,exports){
 
 /**
  * Helper class to create a WebGL Texture
@@ -24540,7 +25063,7 @@ Texture.fromData = function(gl, data, width, height)
 
 module.exports = Texture;
 
-},{}],68:[function(require,module,exports){
+},{}],70:[function(require,module,exports){
 
 // state object//
 var setVertexAttribArrays = require( './setVertexAttribArrays' );
@@ -24808,7 +25331,7 @@ VertexArrayObject.prototype.getSize = function()
     return attrib.buffer.data.length / (( attrib.stride/4 ) || attrib.attribute.size);
 };
 
-},{"./setVertexAttribArrays":71}],69:[function(require,module,exports){
+},{"./setVertexAttribArrays":73}],71:[function(require,module,exports){
 
 /**
  * Helper class to create a webGL Context
@@ -24836,7 +25359,7 @@ var createContext = function(canvas, options)
 
 module.exports = createContext;
 
-},{}],70:[function(require,module,exports){
+},{}],72:[function(require,module,exports){
 var gl = {
     createContext:          require('./createContext'),
     setVertexAttribArrays:  require('./setVertexAttribArrays'),
@@ -24863,7 +25386,7 @@ if (typeof window !== 'undefined')
     window.PIXI.glCore = gl;
 }
 
-},{"./GLBuffer":64,"./GLFramebuffer":65,"./GLShader":66,"./GLTexture":67,"./VertexArrayObject":68,"./createContext":69,"./setVertexAttribArrays":71,"./shader":77}],71:[function(require,module,exports){
+},{"./GLBuffer":66,"./GLFramebuffer":67,"./GLShader":68,"./GLTexture":69,"./VertexArrayObject":70,"./createContext":71,"./setVertexAttribArrays":73,"./shader":79}],73:[function(require,module,exports){
 // var GL_MAP = {};
 
 /**
@@ -24920,7 +25443,7 @@ var setVertexAttribArrays = function (gl, attribs, state)
 
 module.exports = setVertexAttribArrays;
 
-},{}],72:[function(require,module,exports){
+},{}],74:[function(require,module,exports){
 
 /**
  * @class
@@ -25002,7 +25525,7 @@ var compileShader = function (gl, type, src)
 
 module.exports = compileProgram;
 
-},{}],73:[function(require,module,exports){
+},{}],75:[function(require,module,exports){
 /**
  * @class
  * @memberof PIXI.glCore.shader
@@ -25082,7 +25605,7 @@ var booleanArray = function(size)
 
 module.exports = defaultValue;
 
-},{}],74:[function(require,module,exports){
+},{}],76:[function(require,module,exports){
 
 var mapType = require('./mapType');
 var mapSize = require('./mapSize');
@@ -25125,7 +25648,7 @@ var pointer = function(type, normalized, stride, start){
 
 module.exports = extractAttributes;
 
-},{"./mapSize":78,"./mapType":79}],75:[function(require,module,exports){
+},{"./mapSize":80,"./mapType":81}],77:[function(require,module,exports){
 var mapType = require('./mapType');
 var defaultValue = require('./defaultValue');
 
@@ -25162,7 +25685,7 @@ var extractUniforms = function(gl, program)
 
 module.exports = extractUniforms;
 
-},{"./defaultValue":73,"./mapType":79}],76:[function(require,module,exports){
+},{"./defaultValue":75,"./mapType":81}],78:[function(require,module,exports){
 /**
  * Extracts the attributes
  * @class
@@ -25285,7 +25808,7 @@ function getUniformGroup(nameTokens, uniform)
 
 module.exports = generateUniformAccessObject;
 
-},{}],77:[function(require,module,exports){
+},{}],79:[function(require,module,exports){
 module.exports = {
     compileProgram: require('./compileProgram'),
     defaultValue: require('./defaultValue'),
@@ -25296,7 +25819,7 @@ module.exports = {
     mapSize: require('./mapSize'),
     mapType: require('./mapType')
 };
-},{"./compileProgram":72,"./defaultValue":73,"./extractAttributes":74,"./extractUniforms":75,"./generateUniformAccessObject":76,"./mapSize":78,"./mapType":79,"./setPrecision":80}],78:[function(require,module,exports){
+},{"./compileProgram":74,"./defaultValue":75,"./extractAttributes":76,"./extractUniforms":77,"./generateUniformAccessObject":78,"./mapSize":80,"./mapType":81,"./setPrecision":82}],80:[function(require,module,exports){
 /**
  * @class
  * @memberof PIXI.glCore.shader
@@ -25334,7 +25857,7 @@ var GLSL_TO_SIZE = {
 
 module.exports = mapSize;
 
-},{}],79:[function(require,module,exports){
+},{}],81:[function(require,module,exports){
 
 
 var mapType = function(gl, type) 
@@ -25382,7 +25905,7 @@ var GL_TO_GLSL_TYPES = {
 
 module.exports = mapType;
 
-},{}],80:[function(require,module,exports){
+},{}],82:[function(require,module,exports){
 /**
  * Sets the float precision on the shader. If the precision is already present this function will do nothing
  * @param {string} src       the shader source
@@ -25402,7 +25925,7 @@ var setPrecision = function(src, precision)
 
 module.exports = setPrecision;
 
-},{}],81:[function(require,module,exports){
+},{}],83:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -25603,7 +26126,7 @@ module.exports = function (_Plugin) {
     return Bounce;
 }(Plugin);
 
-},{"./plugin":89,"exists":40,"pixi-ease":54}],82:[function(require,module,exports){
+},{"./plugin":91,"exists":42,"pixi-ease":56}],84:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -25677,7 +26200,7 @@ module.exports = function (_Plugin) {
     return ClampZoom;
 }(Plugin);
 
-},{"./plugin":89}],83:[function(require,module,exports){
+},{"./plugin":91}],85:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -25799,7 +26322,7 @@ module.exports = function (_Plugin) {
     return clamp;
 }(Plugin);
 
-},{"./plugin":89}],84:[function(require,module,exports){
+},{"./plugin":91}],86:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -25950,7 +26473,7 @@ module.exports = function (_Plugin) {
     return Decelerate;
 }(Plugin);
 
-},{"./plugin":89,"exists":40}],85:[function(require,module,exports){
+},{"./plugin":91,"exists":42}],87:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26159,7 +26682,7 @@ module.exports = function (_Plugin) {
     return Drag;
 }(Plugin);
 
-},{"./plugin":89,"exists":40}],86:[function(require,module,exports){
+},{"./plugin":91,"exists":42}],88:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26235,7 +26758,7 @@ module.exports = function (_Plugin) {
     return Follow;
 }(Plugin);
 
-},{"./plugin":89}],87:[function(require,module,exports){
+},{"./plugin":91}],89:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26410,7 +26933,7 @@ module.exports = function (_Plugin) {
     return MouseEdges;
 }(Plugin);
 
-},{"./plugin":89,"exists":40,"yy-angle":287}],88:[function(require,module,exports){
+},{"./plugin":91,"exists":42,"yy-angle":287}],90:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26527,7 +27050,7 @@ module.exports = function (_Plugin) {
     return Pinch;
 }(Plugin);
 
-},{"./plugin":89}],89:[function(require,module,exports){
+},{"./plugin":91}],91:[function(require,module,exports){
 "use strict";
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26578,7 +27101,7 @@ module.exports = function () {
     return Plugin;
 }();
 
-},{}],90:[function(require,module,exports){
+},{}],92:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26723,7 +27246,7 @@ module.exports = function (_Plugin) {
     return SnapZoom;
 }(Plugin);
 
-},{"./plugin":89,"exists":40,"pixi-ease":54}],91:[function(require,module,exports){
+},{"./plugin":91,"exists":42,"pixi-ease":56}],93:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -26842,7 +27365,7 @@ module.exports = function (_Plugin) {
     return Snap;
 }(Plugin);
 
-},{"./plugin":89,"exists":40,"pixi-ease":54}],92:[function(require,module,exports){
+},{"./plugin":91,"exists":42,"pixi-ease":56}],94:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -28184,7 +28707,7 @@ var Viewport = function (_PIXI$Container) {
 
 module.exports = Viewport;
 
-},{"./bounce":81,"./clamp":83,"./clamp-zoom":82,"./decelerate":84,"./drag":85,"./follow":86,"./mouse-edges":87,"./pinch":88,"./snap":91,"./snap-zoom":90,"./wheel":93,"exists":40,"pixi.js":209}],93:[function(require,module,exports){
+},{"./bounce":83,"./clamp":85,"./clamp-zoom":84,"./decelerate":86,"./drag":87,"./follow":88,"./mouse-edges":89,"./pinch":90,"./snap":93,"./snap-zoom":92,"./wheel":95,"exists":42,"pixi.js":211}],95:[function(require,module,exports){
 'use strict';
 
 var _createClass = function () { function defineProperties(target, props) { for (var i = 0; i < props.length; i++) { var descriptor = props[i]; descriptor.enumerable = descriptor.enumerable || false; descriptor.configurable = true; if ("value" in descriptor) descriptor.writable = true; Object.defineProperty(target, descriptor.key, descriptor); } } return function (Constructor, protoProps, staticProps) { if (protoProps) defineProperties(Constructor.prototype, protoProps); if (staticProps) defineProperties(Constructor, staticProps); return Constructor; }; }();
@@ -28262,7 +28785,7 @@ module.exports = function (_Plugin) {
     return Wheel;
 }(Plugin);
 
-},{"./plugin":89}],94:[function(require,module,exports){
+},{"./plugin":91}],96:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -28756,7 +29279,7 @@ exports.default = AccessibilityManager;
 core.WebGLRenderer.registerPlugin('accessibility', AccessibilityManager);
 core.CanvasRenderer.registerPlugin('accessibility', AccessibilityManager);
 
-},{"../core":119,"./accessibleTarget":95,"ismobilejs":46}],95:[function(require,module,exports){
+},{"../core":121,"./accessibleTarget":97,"ismobilejs":48}],97:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -28814,7 +29337,7 @@ exports.default = {
   _accessibleDiv: false
 };
 
-},{}],96:[function(require,module,exports){
+},{}],98:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -28839,7 +29362,7 @@ Object.defineProperty(exports, 'AccessibilityManager', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./AccessibilityManager":94,"./accessibleTarget":95}],97:[function(require,module,exports){
+},{"./AccessibilityManager":96,"./accessibleTarget":97}],99:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -29062,7 +29585,7 @@ var Application = function () {
 
 exports.default = Application;
 
-},{"./autoDetectRenderer":99,"./const":100,"./display/Container":102,"./settings":155,"./ticker":175}],98:[function(require,module,exports){
+},{"./autoDetectRenderer":101,"./const":102,"./display/Container":104,"./settings":157,"./ticker":177}],100:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -29129,7 +29652,7 @@ var Shader = function (_GLShader) {
 
 exports.default = Shader;
 
-},{"./settings":155,"pixi-gl-core":70}],99:[function(require,module,exports){
+},{"./settings":157,"pixi-gl-core":72}],101:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -29198,7 +29721,7 @@ function autoDetectRenderer(options, arg1, arg2, arg3) {
     return new _CanvasRenderer2.default(options, arg1, arg2);
 }
 
-},{"./renderers/canvas/CanvasRenderer":131,"./renderers/webgl/WebGLRenderer":138,"./utils":179}],100:[function(require,module,exports){
+},{"./renderers/canvas/CanvasRenderer":133,"./renderers/webgl/WebGLRenderer":140,"./utils":181}],102:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -29541,7 +30064,7 @@ var UPDATE_PRIORITY = exports.UPDATE_PRIORITY = {
   UTILITY: -50
 };
 
-},{}],101:[function(require,module,exports){
+},{}],103:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -29884,7 +30407,7 @@ var Bounds = function () {
 
 exports.default = Bounds;
 
-},{"../math":124}],102:[function(require,module,exports){
+},{"../math":126}],104:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -30502,7 +31025,7 @@ var Container = function (_DisplayObject) {
 exports.default = Container;
 Container.prototype.containerUpdateTransform = Container.prototype.updateTransform;
 
-},{"../utils":179,"./DisplayObject":103}],103:[function(require,module,exports){
+},{"../utils":181,"./DisplayObject":105}],105:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -31196,7 +31719,7 @@ var DisplayObject = function (_EventEmitter) {
 exports.default = DisplayObject;
 DisplayObject.prototype.displayObjectUpdateTransform = DisplayObject.prototype.updateTransform;
 
-},{"../const":100,"../math":124,"../settings":155,"./Bounds":101,"./Transform":104,"./TransformStatic":106,"eventemitter3":39}],104:[function(require,module,exports){
+},{"../const":102,"../math":126,"../settings":157,"./Bounds":103,"./Transform":106,"./TransformStatic":108,"eventemitter3":40}],106:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -31377,7 +31900,7 @@ var Transform = function (_TransformBase) {
 
 exports.default = Transform;
 
-},{"../math":124,"./TransformBase":105}],105:[function(require,module,exports){
+},{"../math":126,"./TransformBase":107}],107:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -31464,7 +31987,7 @@ TransformBase.prototype.updateWorldTransform = TransformBase.prototype.updateTra
 
 TransformBase.IDENTITY = new TransformBase();
 
-},{"../math":124}],106:[function(require,module,exports){
+},{"../math":126}],108:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -31674,7 +32197,7 @@ var TransformStatic = function (_TransformBase) {
 
 exports.default = TransformStatic;
 
-},{"../math":124,"./TransformBase":105}],107:[function(require,module,exports){
+},{"../math":126,"./TransformBase":107}],109:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -32879,7 +33402,7 @@ exports.default = Graphics;
 
 Graphics._SPRITE_TEXTURE = null;
 
-},{"../const":100,"../display/Bounds":101,"../display/Container":102,"../math":124,"../renderers/canvas/CanvasRenderer":131,"../sprites/Sprite":156,"../textures/RenderTexture":167,"../textures/Texture":169,"../utils":179,"./GraphicsData":108,"./utils/bezierCurveTo":110}],108:[function(require,module,exports){
+},{"../const":102,"../display/Bounds":103,"../display/Container":104,"../math":126,"../renderers/canvas/CanvasRenderer":133,"../sprites/Sprite":158,"../textures/RenderTexture":169,"../textures/Texture":171,"../utils":181,"./GraphicsData":110,"./utils/bezierCurveTo":112}],110:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -33015,7 +33538,7 @@ var GraphicsData = function () {
 
 exports.default = GraphicsData;
 
-},{}],109:[function(require,module,exports){
+},{}],111:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -33284,7 +33807,7 @@ exports.default = CanvasGraphicsRenderer;
 
 _CanvasRenderer2.default.registerPlugin('graphics', CanvasGraphicsRenderer);
 
-},{"../../const":100,"../../renderers/canvas/CanvasRenderer":131}],110:[function(require,module,exports){
+},{"../../const":102,"../../renderers/canvas/CanvasRenderer":133}],112:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -33334,7 +33857,7 @@ function bezierCurveTo(fromX, fromY, cpX, cpY, cpX2, cpY2, toX, toY) {
     return path;
 }
 
-},{}],111:[function(require,module,exports){
+},{}],113:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -33599,7 +34122,7 @@ exports.default = GraphicsRenderer;
 
 _WebGLRenderer2.default.registerPlugin('graphics', GraphicsRenderer);
 
-},{"../../const":100,"../../renderers/webgl/WebGLRenderer":138,"../../renderers/webgl/utils/ObjectRenderer":148,"../../utils":179,"./WebGLGraphicsData":112,"./shaders/PrimitiveShader":113,"./utils/buildCircle":114,"./utils/buildPoly":116,"./utils/buildRectangle":117,"./utils/buildRoundedRectangle":118}],112:[function(require,module,exports){
+},{"../../const":102,"../../renderers/webgl/WebGLRenderer":140,"../../renderers/webgl/utils/ObjectRenderer":150,"../../utils":181,"./WebGLGraphicsData":114,"./shaders/PrimitiveShader":115,"./utils/buildCircle":116,"./utils/buildPoly":118,"./utils/buildRectangle":119,"./utils/buildRoundedRectangle":120}],114:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -33742,7 +34265,7 @@ var WebGLGraphicsData = function () {
 
 exports.default = WebGLGraphicsData;
 
-},{"pixi-gl-core":70}],113:[function(require,module,exports){
+},{"pixi-gl-core":72}],115:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -33787,7 +34310,7 @@ var PrimitiveShader = function (_Shader) {
 
 exports.default = PrimitiveShader;
 
-},{"../../../Shader":98}],114:[function(require,module,exports){
+},{"../../../Shader":100}],116:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -33880,7 +34403,7 @@ function buildCircle(graphicsData, webGLData, webGLDataNativeLines) {
     }
 }
 
-},{"../../../const":100,"../../../utils":179,"./buildLine":115}],115:[function(require,module,exports){
+},{"../../../const":102,"../../../utils":181,"./buildLine":117}],117:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -34150,7 +34673,7 @@ function buildNativeLine(graphicsData, webGLData) {
     }
 }
 
-},{"../../../math":124,"../../../utils":179}],116:[function(require,module,exports){
+},{"../../../math":126,"../../../utils":181}],118:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -34236,7 +34759,7 @@ function buildPoly(graphicsData, webGLData, webGLDataNativeLines) {
     }
 }
 
-},{"../../../utils":179,"./buildLine":115,"earcut":38}],117:[function(require,module,exports){
+},{"../../../utils":181,"./buildLine":117,"earcut":39}],119:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -34312,7 +34835,7 @@ function buildRectangle(graphicsData, webGLData, webGLDataNativeLines) {
     }
 }
 
-},{"../../../utils":179,"./buildLine":115}],118:[function(require,module,exports){
+},{"../../../utils":181,"./buildLine":117}],120:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -34468,7 +34991,7 @@ function quadraticBezierCurve(fromX, fromY, cpX, cpY, toX, toY) {
     return points;
 }
 
-},{"../../../utils":179,"./buildLine":115,"earcut":38}],119:[function(require,module,exports){
+},{"../../../utils":181,"./buildLine":117,"earcut":39}],121:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -34854,7 +35377,7 @@ exports.WebGLRenderer = _WebGLRenderer2.default; /**
                                                   * @namespace PIXI
                                                   */
 
-},{"./Application":97,"./Shader":98,"./autoDetectRenderer":99,"./const":100,"./display/Bounds":101,"./display/Container":102,"./display/DisplayObject":103,"./display/Transform":104,"./display/TransformBase":105,"./display/TransformStatic":106,"./graphics/Graphics":107,"./graphics/GraphicsData":108,"./graphics/canvas/CanvasGraphicsRenderer":109,"./graphics/webgl/GraphicsRenderer":111,"./math":124,"./renderers/canvas/CanvasRenderer":131,"./renderers/canvas/utils/CanvasRenderTarget":133,"./renderers/webgl/WebGLRenderer":138,"./renderers/webgl/filters/Filter":140,"./renderers/webgl/filters/spriteMask/SpriteMaskFilter":143,"./renderers/webgl/managers/WebGLManager":147,"./renderers/webgl/utils/ObjectRenderer":148,"./renderers/webgl/utils/Quad":149,"./renderers/webgl/utils/RenderTarget":150,"./settings":155,"./sprites/Sprite":156,"./sprites/canvas/CanvasSpriteRenderer":157,"./sprites/canvas/CanvasTinter":158,"./sprites/webgl/SpriteRenderer":160,"./text/Text":162,"./text/TextMetrics":163,"./text/TextStyle":164,"./textures/BaseRenderTexture":165,"./textures/BaseTexture":166,"./textures/RenderTexture":167,"./textures/Spritesheet":168,"./textures/Texture":169,"./textures/TextureMatrix":170,"./textures/TextureUvs":171,"./textures/VideoBaseTexture":172,"./ticker":175,"./utils":179,"pixi-gl-core":70}],120:[function(require,module,exports){
+},{"./Application":99,"./Shader":100,"./autoDetectRenderer":101,"./const":102,"./display/Bounds":103,"./display/Container":104,"./display/DisplayObject":105,"./display/Transform":106,"./display/TransformBase":107,"./display/TransformStatic":108,"./graphics/Graphics":109,"./graphics/GraphicsData":110,"./graphics/canvas/CanvasGraphicsRenderer":111,"./graphics/webgl/GraphicsRenderer":113,"./math":126,"./renderers/canvas/CanvasRenderer":133,"./renderers/canvas/utils/CanvasRenderTarget":135,"./renderers/webgl/WebGLRenderer":140,"./renderers/webgl/filters/Filter":142,"./renderers/webgl/filters/spriteMask/SpriteMaskFilter":145,"./renderers/webgl/managers/WebGLManager":149,"./renderers/webgl/utils/ObjectRenderer":150,"./renderers/webgl/utils/Quad":151,"./renderers/webgl/utils/RenderTarget":152,"./settings":157,"./sprites/Sprite":158,"./sprites/canvas/CanvasSpriteRenderer":159,"./sprites/canvas/CanvasTinter":160,"./sprites/webgl/SpriteRenderer":162,"./text/Text":164,"./text/TextMetrics":165,"./text/TextStyle":166,"./textures/BaseRenderTexture":167,"./textures/BaseTexture":168,"./textures/RenderTexture":169,"./textures/Spritesheet":170,"./textures/Texture":171,"./textures/TextureMatrix":172,"./textures/TextureUvs":173,"./textures/VideoBaseTexture":174,"./ticker":177,"./utils":181,"pixi-gl-core":72}],122:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -35047,7 +35570,7 @@ var GroupD8 = {
 
 exports.default = GroupD8;
 
-},{"./Matrix":121}],121:[function(require,module,exports){
+},{"./Matrix":123}],123:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -35578,7 +36101,7 @@ var Matrix = function () {
 
 exports.default = Matrix;
 
-},{"./Point":123}],122:[function(require,module,exports){
+},{"./Point":125}],124:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -35695,7 +36218,7 @@ var ObservablePoint = function () {
 
 exports.default = ObservablePoint;
 
-},{}],123:[function(require,module,exports){
+},{}],125:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -35786,7 +36309,7 @@ var Point = function () {
 
 exports.default = Point;
 
-},{}],124:[function(require,module,exports){
+},{}],126:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -35874,7 +36397,7 @@ Object.defineProperty(exports, 'RoundedRectangle', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./GroupD8":120,"./Matrix":121,"./ObservablePoint":122,"./Point":123,"./shapes/Circle":125,"./shapes/Ellipse":126,"./shapes/Polygon":127,"./shapes/Rectangle":128,"./shapes/RoundedRectangle":129}],125:[function(require,module,exports){
+},{"./GroupD8":122,"./Matrix":123,"./ObservablePoint":124,"./Point":125,"./shapes/Circle":127,"./shapes/Ellipse":128,"./shapes/Polygon":129,"./shapes/Rectangle":130,"./shapes/RoundedRectangle":131}],127:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -35988,7 +36511,7 @@ var Circle = function () {
 
 exports.default = Circle;
 
-},{"../../const":100,"./Rectangle":128}],126:[function(require,module,exports){
+},{"../../const":102,"./Rectangle":130}],128:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -36110,7 +36633,7 @@ var Ellipse = function () {
 
 exports.default = Ellipse;
 
-},{"../../const":100,"./Rectangle":128}],127:[function(require,module,exports){
+},{"../../const":102,"./Rectangle":130}],129:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -36241,7 +36764,7 @@ var Polygon = function () {
 
 exports.default = Polygon;
 
-},{"../../const":100,"../Point":123}],128:[function(require,module,exports){
+},{"../../const":102,"../Point":125}],130:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -36504,7 +37027,7 @@ var Rectangle = function () {
 
 exports.default = Rectangle;
 
-},{"../../const":100}],129:[function(require,module,exports){
+},{"../../const":102}],131:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -36637,7 +37160,7 @@ var RoundedRectangle = function () {
 
 exports.default = RoundedRectangle;
 
-},{"../../const":100}],130:[function(require,module,exports){
+},{"../../const":102}],132:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37002,7 +37525,7 @@ var SystemRenderer = function (_EventEmitter) {
 
 exports.default = SystemRenderer;
 
-},{"../const":100,"../display/Container":102,"../math":124,"../settings":155,"../textures/RenderTexture":167,"../utils":179,"eventemitter3":39}],131:[function(require,module,exports){
+},{"../const":102,"../display/Container":104,"../math":126,"../settings":157,"../textures/RenderTexture":169,"../utils":181,"eventemitter3":40}],133:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37367,7 +37890,7 @@ var CanvasRenderer = function (_SystemRenderer) {
 exports.default = CanvasRenderer;
 _utils.pluginTarget.mixin(CanvasRenderer);
 
-},{"../../const":100,"../../settings":155,"../../utils":179,"../SystemRenderer":130,"./utils/CanvasMaskManager":132,"./utils/CanvasRenderTarget":133,"./utils/mapCanvasBlendModesToPixi":135}],132:[function(require,module,exports){
+},{"../../const":102,"../../settings":157,"../../utils":181,"../SystemRenderer":132,"./utils/CanvasMaskManager":134,"./utils/CanvasRenderTarget":135,"./utils/mapCanvasBlendModesToPixi":137}],134:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37536,7 +38059,7 @@ var CanvasMaskManager = function () {
 
 exports.default = CanvasMaskManager;
 
-},{"../../../const":100}],133:[function(require,module,exports){
+},{"../../../const":102}],135:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37660,7 +38183,7 @@ var CanvasRenderTarget = function () {
 
 exports.default = CanvasRenderTarget;
 
-},{"../../../settings":155}],134:[function(require,module,exports){
+},{"../../../settings":157}],136:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37721,7 +38244,7 @@ function canUseNewCanvasBlendModes() {
     return data[0] === 255 && data[1] === 0 && data[2] === 0;
 }
 
-},{}],135:[function(require,module,exports){
+},{}],137:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37793,7 +38316,7 @@ function mapCanvasBlendModesToPixi() {
     return array;
 }
 
-},{"../../../const":100,"./canUseNewCanvasBlendModes":134}],136:[function(require,module,exports){
+},{"../../../const":102,"./canUseNewCanvasBlendModes":136}],138:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -37913,7 +38436,7 @@ var TextureGarbageCollector = function () {
 
 exports.default = TextureGarbageCollector;
 
-},{"../../const":100,"../../settings":155}],137:[function(require,module,exports){
+},{"../../const":102,"../../settings":157}],139:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -38169,7 +38692,7 @@ var TextureManager = function () {
 
 exports.default = TextureManager;
 
-},{"../../const":100,"../../utils":179,"./utils/RenderTarget":150,"pixi-gl-core":70}],138:[function(require,module,exports){
+},{"../../const":102,"../../utils":181,"./utils/RenderTarget":152,"pixi-gl-core":72}],140:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -38986,7 +39509,7 @@ var WebGLRenderer = function (_SystemRenderer) {
 exports.default = WebGLRenderer;
 _utils.pluginTarget.mixin(WebGLRenderer);
 
-},{"../../const":100,"../../textures/BaseTexture":166,"../../utils":179,"../SystemRenderer":130,"./TextureGarbageCollector":136,"./TextureManager":137,"./WebGLState":139,"./managers/FilterManager":144,"./managers/MaskManager":145,"./managers/StencilManager":146,"./utils/ObjectRenderer":148,"./utils/RenderTarget":150,"./utils/mapWebGLDrawModesToPixi":153,"./utils/validateContext":154,"pixi-gl-core":70}],139:[function(require,module,exports){
+},{"../../const":102,"../../textures/BaseTexture":168,"../../utils":181,"../SystemRenderer":132,"./TextureGarbageCollector":138,"./TextureManager":139,"./WebGLState":141,"./managers/FilterManager":146,"./managers/MaskManager":147,"./managers/StencilManager":148,"./utils/ObjectRenderer":150,"./utils/RenderTarget":152,"./utils/mapWebGLDrawModesToPixi":155,"./utils/validateContext":156,"pixi-gl-core":72}],141:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -39266,7 +39789,7 @@ var WebGLState = function () {
 
 exports.default = WebGLState;
 
-},{"./utils/mapWebGLBlendModesToPixi":152}],140:[function(require,module,exports){
+},{"./utils/mapWebGLBlendModesToPixi":154}],142:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -39462,7 +39985,7 @@ var Filter = function () {
 
 exports.default = Filter;
 
-},{"../../../const":100,"../../../settings":155,"../../../utils":179,"./extractUniformsFromSrc":141}],141:[function(require,module,exports){
+},{"../../../const":102,"../../../settings":157,"../../../utils":181,"./extractUniformsFromSrc":143}],143:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -39524,7 +40047,7 @@ function extractUniformsFromString(string) {
     return uniforms;
 }
 
-},{"pixi-gl-core":70}],142:[function(require,module,exports){
+},{"pixi-gl-core":72}],144:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -39584,7 +40107,7 @@ function calculateSpriteMatrix(outputMatrix, filterArea, textureSize, sprite) {
     return mappedMatrix;
 }
 
-},{"../../../math":124}],143:[function(require,module,exports){
+},{"../../../math":126}],145:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -39672,7 +40195,7 @@ var SpriteMaskFilter = function (_Filter) {
 
 exports.default = SpriteMaskFilter;
 
-},{"../../../../math":124,"../../../../textures/TextureMatrix":170,"../Filter":140,"path":50}],144:[function(require,module,exports){
+},{"../../../../math":126,"../../../../textures/TextureMatrix":172,"../Filter":142,"path":52}],146:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -40266,7 +40789,7 @@ var FilterManager = function (_WebGLManager) {
 
 exports.default = FilterManager;
 
-},{"../../../Shader":98,"../../../math":124,"../filters/filterTransforms":142,"../utils/Quad":149,"../utils/RenderTarget":150,"./WebGLManager":147,"bit-twiddle":2}],145:[function(require,module,exports){
+},{"../../../Shader":100,"../../../math":126,"../filters/filterTransforms":144,"../utils/Quad":151,"../utils/RenderTarget":152,"./WebGLManager":149,"bit-twiddle":2}],147:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -40476,7 +40999,7 @@ var MaskManager = function (_WebGLManager) {
 
 exports.default = MaskManager;
 
-},{"../filters/spriteMask/SpriteMaskFilter":143,"./WebGLManager":147}],146:[function(require,module,exports){
+},{"../filters/spriteMask/SpriteMaskFilter":145,"./WebGLManager":149}],148:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -40629,7 +41152,7 @@ var StencilManager = function (_WebGLManager) {
 
 exports.default = StencilManager;
 
-},{"./WebGLManager":147}],147:[function(require,module,exports){
+},{"./WebGLManager":149}],149:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -40684,7 +41207,7 @@ var WebGLManager = function () {
 
 exports.default = WebGLManager;
 
-},{}],148:[function(require,module,exports){
+},{}],150:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -40762,7 +41285,7 @@ var ObjectRenderer = function (_WebGLManager) {
 
 exports.default = ObjectRenderer;
 
-},{"../managers/WebGLManager":147}],149:[function(require,module,exports){
+},{"../managers/WebGLManager":149}],151:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -40943,7 +41466,7 @@ var Quad = function () {
 
 exports.default = Quad;
 
-},{"../../../utils/createIndicesForQuads":177,"pixi-gl-core":70}],150:[function(require,module,exports){
+},{"../../../utils/createIndicesForQuads":179,"pixi-gl-core":72}],152:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -41270,7 +41793,7 @@ var RenderTarget = function () {
 
 exports.default = RenderTarget;
 
-},{"../../../const":100,"../../../math":124,"../../../settings":155,"pixi-gl-core":70}],151:[function(require,module,exports){
+},{"../../../const":102,"../../../math":126,"../../../settings":157,"pixi-gl-core":72}],153:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -41345,7 +41868,7 @@ function generateIfTestSrc(maxIfs) {
     return src;
 }
 
-},{"pixi-gl-core":70}],152:[function(require,module,exports){
+},{"pixi-gl-core":72}],154:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -41394,7 +41917,7 @@ function mapWebGLBlendModesToPixi(gl) {
     return array;
 }
 
-},{"../../../const":100}],153:[function(require,module,exports){
+},{"../../../const":102}],155:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -41426,7 +41949,7 @@ function mapWebGLDrawModesToPixi(gl) {
   return object;
 }
 
-},{"../../../const":100}],154:[function(require,module,exports){
+},{"../../../const":102}],156:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -41442,7 +41965,7 @@ function validateContext(gl) {
     }
 }
 
-},{}],155:[function(require,module,exports){
+},{}],157:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -41687,7 +42210,7 @@ exports.default = {
   MESH_CANVAS_PADDING: 0
 };
 
-},{"./utils/canUploadSameBuffer":176,"./utils/maxRecommendedTextures":181}],156:[function(require,module,exports){
+},{"./utils/canUploadSameBuffer":178,"./utils/maxRecommendedTextures":183}],158:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -42310,7 +42833,7 @@ var Sprite = function (_Container) {
 
 exports.default = Sprite;
 
-},{"../const":100,"../display/Container":102,"../math":124,"../textures/Texture":169,"../utils":179}],157:[function(require,module,exports){
+},{"../const":102,"../display/Container":104,"../math":126,"../textures/Texture":171,"../utils":181}],159:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -42463,7 +42986,7 @@ exports.default = CanvasSpriteRenderer;
 
 _CanvasRenderer2.default.registerPlugin('sprite', CanvasSpriteRenderer);
 
-},{"../../const":100,"../../math":124,"../../renderers/canvas/CanvasRenderer":131,"./CanvasTinter":158}],158:[function(require,module,exports){
+},{"../../const":102,"../../math":126,"../../renderers/canvas/CanvasRenderer":133,"./CanvasTinter":160}],160:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -42714,7 +43237,7 @@ CanvasTinter.tintMethod = CanvasTinter.canUseMultiply ? CanvasTinter.tintWithMul
 
 exports.default = CanvasTinter;
 
-},{"../../renderers/canvas/utils/canUseNewCanvasBlendModes":134,"../../utils":179}],159:[function(require,module,exports){
+},{"../../renderers/canvas/utils/canUseNewCanvasBlendModes":136,"../../utils":181}],161:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -42767,7 +43290,7 @@ var Buffer = function () {
 
 exports.default = Buffer;
 
-},{}],160:[function(require,module,exports){
+},{}],162:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -43318,7 +43841,7 @@ exports.default = SpriteRenderer;
 
 _WebGLRenderer2.default.registerPlugin('sprite', SpriteRenderer);
 
-},{"../../renderers/webgl/WebGLRenderer":138,"../../renderers/webgl/utils/ObjectRenderer":148,"../../renderers/webgl/utils/checkMaxIfStatmentsInShader":151,"../../settings":155,"../../utils":179,"../../utils/createIndicesForQuads":177,"./BatchBuffer":159,"./generateMultiTextureShader":161,"bit-twiddle":2,"pixi-gl-core":70}],161:[function(require,module,exports){
+},{"../../renderers/webgl/WebGLRenderer":140,"../../renderers/webgl/utils/ObjectRenderer":150,"../../renderers/webgl/utils/checkMaxIfStatmentsInShader":153,"../../settings":157,"../../utils":181,"../../utils/createIndicesForQuads":179,"./BatchBuffer":161,"./generateMultiTextureShader":163,"bit-twiddle":2,"pixi-gl-core":72}],163:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -43381,7 +43904,7 @@ function generateSampleSrc(maxTextures) {
     return src;
 }
 
-},{"../../Shader":98,"path":50}],162:[function(require,module,exports){
+},{"../../Shader":100,"path":52}],164:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -44036,7 +44559,7 @@ var Text = function (_Sprite) {
 
 exports.default = Text;
 
-},{"../const":100,"../math":124,"../settings":155,"../sprites/Sprite":156,"../textures/Texture":169,"../utils":179,"../utils/trimCanvas":184,"./TextMetrics":163,"./TextStyle":164}],163:[function(require,module,exports){
+},{"../const":102,"../math":126,"../settings":157,"../sprites/Sprite":158,"../textures/Texture":171,"../utils":181,"../utils/trimCanvas":186,"./TextMetrics":165,"./TextStyle":166}],165:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -44339,7 +44862,7 @@ TextMetrics._context = canvas.getContext('2d');
  */
 TextMetrics._fonts = {};
 
-},{}],164:[function(require,module,exports){
+},{}],166:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -45141,7 +45664,7 @@ function deepCopyProperties(target, source, propertyObj) {
     }
 }
 
-},{"../const":100,"../utils":179}],165:[function(require,module,exports){
+},{"../const":102,"../utils":181}],167:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -45303,7 +45826,7 @@ var BaseRenderTexture = function (_BaseTexture) {
 
 exports.default = BaseRenderTexture;
 
-},{"../settings":155,"./BaseTexture":166}],166:[function(require,module,exports){
+},{"../settings":157,"./BaseTexture":168}],168:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -46149,7 +46672,7 @@ var BaseTexture = function (_EventEmitter) {
 
 exports.default = BaseTexture;
 
-},{"../settings":155,"../utils":179,"../utils/determineCrossOrigin":178,"bit-twiddle":2,"eventemitter3":39}],167:[function(require,module,exports){
+},{"../settings":157,"../utils":181,"../utils/determineCrossOrigin":180,"bit-twiddle":2,"eventemitter3":40}],169:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -46303,7 +46826,7 @@ var RenderTexture = function (_Texture) {
 
 exports.default = RenderTexture;
 
-},{"./BaseRenderTexture":165,"./Texture":169}],168:[function(require,module,exports){
+},{"./BaseRenderTexture":167,"./Texture":171}],170:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -46568,7 +47091,7 @@ var Spritesheet = function () {
 
 exports.default = Spritesheet;
 
-},{"../":119,"../utils":179}],169:[function(require,module,exports){
+},{"../":121,"../utils":181}],171:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -47260,7 +47783,7 @@ Texture.WHITE = createWhiteTexture();
 removeAllHandlers(Texture.WHITE);
 removeAllHandlers(Texture.WHITE.baseTexture);
 
-},{"../math":124,"../settings":155,"../utils":179,"./BaseTexture":166,"./TextureUvs":171,"./VideoBaseTexture":172,"eventemitter3":39}],170:[function(require,module,exports){
+},{"../math":126,"../settings":157,"../utils":181,"./BaseTexture":168,"./TextureUvs":173,"./VideoBaseTexture":174,"eventemitter3":40}],172:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -47424,7 +47947,7 @@ var TextureMatrix = function () {
 
 exports.default = TextureMatrix;
 
-},{"../math/Matrix":121}],171:[function(require,module,exports){
+},{"../math/Matrix":123}],173:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -47529,7 +48052,7 @@ var TextureUvs = function () {
 
 exports.default = TextureUvs;
 
-},{"../math/GroupD8":120}],172:[function(require,module,exports){
+},{"../math/GroupD8":122}],174:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -47871,7 +48394,7 @@ function createSource(path, type) {
     return source;
 }
 
-},{"../const":100,"../ticker":175,"../utils":179,"../utils/determineCrossOrigin":178,"./BaseTexture":166}],173:[function(require,module,exports){
+},{"../const":102,"../ticker":177,"../utils":181,"../utils/determineCrossOrigin":180,"./BaseTexture":168}],175:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -48344,7 +48867,7 @@ var Ticker = function () {
 
 exports.default = Ticker;
 
-},{"../const":100,"../settings":155,"./TickerListener":174}],174:[function(require,module,exports){
+},{"../const":102,"../settings":157,"./TickerListener":176}],176:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -48518,7 +49041,7 @@ var TickerListener = function () {
 
 exports.default = TickerListener;
 
-},{}],175:[function(require,module,exports){
+},{}],177:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -48598,7 +49121,7 @@ shared.destroy = function () {
 exports.shared = shared;
 exports.Ticker = _Ticker2.default;
 
-},{"./Ticker":173}],176:[function(require,module,exports){
+},{"./Ticker":175}],178:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -48612,7 +49135,7 @@ function canUploadSameBuffer() {
 	return !ios;
 }
 
-},{}],177:[function(require,module,exports){
+},{}],179:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -48646,7 +49169,7 @@ function createIndicesForQuads(size) {
     return indices;
 }
 
-},{}],178:[function(require,module,exports){
+},{}],180:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -48702,7 +49225,7 @@ function determineCrossOrigin(url) {
     return '';
 }
 
-},{"url":280}],179:[function(require,module,exports){
+},{"url":282}],181:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -49179,7 +49702,7 @@ function premultiplyTintToRgba(tint, alpha, out, premultiply) {
     return out;
 }
 
-},{"../const":100,"../settings":155,"./mapPremultipliedBlendModes":180,"./mixin":182,"./pluginTarget":183,"eventemitter3":39,"ismobilejs":46,"remove-array-items":258}],180:[function(require,module,exports){
+},{"../const":102,"../settings":157,"./mapPremultipliedBlendModes":182,"./mixin":184,"./pluginTarget":185,"eventemitter3":40,"ismobilejs":48,"remove-array-items":260}],182:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -49222,7 +49745,7 @@ function mapPremultipliedBlendModes() {
     return array;
 }
 
-},{"../const":100}],181:[function(require,module,exports){
+},{"../const":102}],183:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -49244,7 +49767,7 @@ function maxRecommendedTextures(max) {
     return max;
 }
 
-},{"ismobilejs":46}],182:[function(require,module,exports){
+},{"ismobilejs":48}],184:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -49306,7 +49829,7 @@ function performMixins() {
     mixins.length = 0;
 }
 
-},{}],183:[function(require,module,exports){
+},{}],185:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -49372,7 +49895,7 @@ exports.default = {
     }
 };
 
-},{}],184:[function(require,module,exports){
+},{}],186:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -49448,7 +49971,7 @@ function trimCanvas(canvas) {
     };
 }
 
-},{}],185:[function(require,module,exports){
+},{}],187:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -50592,7 +51115,7 @@ function deprecation(core) {
     }
 }
 
-},{}],186:[function(require,module,exports){
+},{}],188:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -50772,7 +51295,7 @@ exports.default = CanvasExtract;
 
 core.CanvasRenderer.registerPlugin('extract', CanvasExtract);
 
-},{"../../core":119}],187:[function(require,module,exports){
+},{"../../core":121}],189:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -50797,7 +51320,7 @@ Object.defineProperty(exports, 'canvas', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./canvas/CanvasExtract":186,"./webgl/WebGLExtract":188}],188:[function(require,module,exports){
+},{"./canvas/CanvasExtract":188,"./webgl/WebGLExtract":190}],190:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -51032,7 +51555,7 @@ exports.default = WebGLExtract;
 
 core.WebGLRenderer.registerPlugin('extract', WebGLExtract);
 
-},{"../../core":119}],189:[function(require,module,exports){
+},{"../../core":121}],191:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -51441,7 +51964,7 @@ var AnimatedSprite = function (_core$Sprite) {
 
 exports.default = AnimatedSprite;
 
-},{"../core":119}],190:[function(require,module,exports){
+},{"../core":121}],192:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -52032,7 +52555,7 @@ exports.default = BitmapText;
 
 BitmapText.fonts = {};
 
-},{"../core":119,"../core/math/ObservablePoint":122,"../core/settings":155,"../core/utils":179}],191:[function(require,module,exports){
+},{"../core":121,"../core/math/ObservablePoint":124,"../core/settings":157,"../core/utils":181}],193:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -52478,7 +53001,7 @@ var TilingSprite = function (_core$Sprite) {
 
 exports.default = TilingSprite;
 
-},{"../core":119,"../core/sprites/canvas/CanvasTinter":158}],192:[function(require,module,exports){
+},{"../core":121,"../core/sprites/canvas/CanvasTinter":160}],194:[function(require,module,exports){
 'use strict';
 
 var _core = require('../core');
@@ -52882,7 +53405,7 @@ DisplayObject.prototype._cacheAsBitmapDestroy = function _cacheAsBitmapDestroy(o
     this.destroy(options);
 };
 
-},{"../core":119,"../core/textures/BaseTexture":166,"../core/textures/Texture":169,"../core/utils":179}],193:[function(require,module,exports){
+},{"../core":121,"../core/textures/BaseTexture":168,"../core/textures/Texture":171,"../core/utils":181}],195:[function(require,module,exports){
 'use strict';
 
 var _core = require('../core');
@@ -52917,7 +53440,7 @@ core.Container.prototype.getChildByName = function getChildByName(name) {
     return null;
 };
 
-},{"../core":119}],194:[function(require,module,exports){
+},{"../core":121}],196:[function(require,module,exports){
 'use strict';
 
 var _core = require('../core');
@@ -52951,7 +53474,7 @@ core.DisplayObject.prototype.getGlobalPosition = function getGlobalPosition() {
     return point;
 };
 
-},{"../core":119}],195:[function(require,module,exports){
+},{"../core":121}],197:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53003,7 +53526,7 @@ function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { de
 
 // imported for side effect of extending the prototype only, contains no exports
 
-},{"./AnimatedSprite":189,"./BitmapText":190,"./TilingSprite":191,"./cacheAsBitmap":192,"./getChildByName":193,"./getGlobalPosition":194,"./webgl/TilingSpriteRenderer":196}],196:[function(require,module,exports){
+},{"./AnimatedSprite":191,"./BitmapText":192,"./TilingSprite":193,"./cacheAsBitmap":194,"./getChildByName":195,"./getGlobalPosition":196,"./webgl/TilingSpriteRenderer":198}],198:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53165,7 +53688,7 @@ exports.default = TilingSpriteRenderer;
 
 core.WebGLRenderer.registerPlugin('tilingSprite', TilingSpriteRenderer);
 
-},{"../../core":119,"../../core/const":100,"path":50}],197:[function(require,module,exports){
+},{"../../core":121,"../../core/const":102,"path":52}],199:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53249,7 +53772,7 @@ var AlphaFilter = function (_core$Filter) {
 
 exports.default = AlphaFilter;
 
-},{"../../core":119,"path":50}],198:[function(require,module,exports){
+},{"../../core":121,"path":52}],200:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53423,7 +53946,7 @@ var BlurFilter = function (_core$Filter) {
 
 exports.default = BlurFilter;
 
-},{"../../core":119,"./BlurXFilter":199,"./BlurYFilter":200}],199:[function(require,module,exports){
+},{"../../core":121,"./BlurXFilter":201,"./BlurYFilter":202}],201:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53589,7 +54112,7 @@ var BlurXFilter = function (_core$Filter) {
 
 exports.default = BlurXFilter;
 
-},{"../../core":119,"./generateBlurFragSource":201,"./generateBlurVertSource":202,"./getMaxBlurKernelSize":203}],200:[function(require,module,exports){
+},{"../../core":121,"./generateBlurFragSource":203,"./generateBlurVertSource":204,"./getMaxBlurKernelSize":205}],202:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53754,7 +54277,7 @@ var BlurYFilter = function (_core$Filter) {
 
 exports.default = BlurYFilter;
 
-},{"../../core":119,"./generateBlurFragSource":201,"./generateBlurVertSource":202,"./getMaxBlurKernelSize":203}],201:[function(require,module,exports){
+},{"../../core":121,"./generateBlurFragSource":203,"./generateBlurVertSource":204,"./getMaxBlurKernelSize":205}],203:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53801,7 +54324,7 @@ function generateFragBlurSource(kernelSize) {
     return fragSource;
 }
 
-},{}],202:[function(require,module,exports){
+},{}],204:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -53845,7 +54368,7 @@ function generateVertBlurSource(kernelSize, x) {
     return vertSource;
 }
 
-},{}],203:[function(require,module,exports){
+},{}],205:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -53861,7 +54384,7 @@ function getMaxKernelSize(gl) {
     return kernelSize;
 }
 
-},{}],204:[function(require,module,exports){
+},{}],206:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -54412,7 +54935,7 @@ var ColorMatrixFilter = function (_core$Filter) {
 exports.default = ColorMatrixFilter;
 ColorMatrixFilter.prototype.grayscale = ColorMatrixFilter.prototype.greyscale;
 
-},{"../../core":119,"path":50}],205:[function(require,module,exports){
+},{"../../core":121,"path":52}],207:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -54520,7 +55043,7 @@ var DisplacementFilter = function (_core$Filter) {
 
 exports.default = DisplacementFilter;
 
-},{"../../core":119,"path":50}],206:[function(require,module,exports){
+},{"../../core":121,"path":52}],208:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -54574,7 +55097,7 @@ var FXAAFilter = function (_core$Filter) {
 
 exports.default = FXAAFilter;
 
-},{"../../core":119,"path":50}],207:[function(require,module,exports){
+},{"../../core":121,"path":52}],209:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -54653,7 +55176,7 @@ Object.defineProperty(exports, 'AlphaFilter', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./alpha/AlphaFilter":197,"./blur/BlurFilter":198,"./blur/BlurXFilter":199,"./blur/BlurYFilter":200,"./colormatrix/ColorMatrixFilter":204,"./displacement/DisplacementFilter":205,"./fxaa/FXAAFilter":206,"./noise/NoiseFilter":208}],208:[function(require,module,exports){
+},{"./alpha/AlphaFilter":199,"./blur/BlurFilter":200,"./blur/BlurXFilter":201,"./blur/BlurYFilter":202,"./colormatrix/ColorMatrixFilter":206,"./displacement/DisplacementFilter":207,"./fxaa/FXAAFilter":208,"./noise/NoiseFilter":210}],210:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -54750,7 +55273,7 @@ var NoiseFilter = function (_core$Filter) {
 
 exports.default = NoiseFilter;
 
-},{"../../core":119,"path":50}],209:[function(require,module,exports){
+},{"../../core":121,"path":52}],211:[function(require,module,exports){
 (function (global){
 'use strict';
 
@@ -54864,7 +55387,7 @@ if (typeof _deprecation2.default === 'function') {
 global.PIXI = exports; // eslint-disable-line
 
 }).call(this,typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{"./accessibility":96,"./core":119,"./deprecation":185,"./extract":187,"./extras":195,"./filters":207,"./interaction":214,"./loaders":217,"./mesh":226,"./particles":229,"./polyfill":235,"./prepare":239}],210:[function(require,module,exports){
+},{"./accessibility":98,"./core":121,"./deprecation":187,"./extract":189,"./extras":197,"./filters":209,"./interaction":216,"./loaders":219,"./mesh":228,"./particles":231,"./polyfill":237,"./prepare":241}],212:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -55088,7 +55611,7 @@ var InteractionData = function () {
 
 exports.default = InteractionData;
 
-},{"../core":119}],211:[function(require,module,exports){
+},{"../core":121}],213:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -55173,7 +55696,7 @@ var InteractionEvent = function () {
 
 exports.default = InteractionEvent;
 
-},{}],212:[function(require,module,exports){
+},{}],214:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -56944,7 +57467,7 @@ exports.default = InteractionManager;
 core.WebGLRenderer.registerPlugin('interaction', InteractionManager);
 core.CanvasRenderer.registerPlugin('interaction', InteractionManager);
 
-},{"../core":119,"./InteractionData":210,"./InteractionEvent":211,"./InteractionTrackingData":213,"./interactiveTarget":215,"eventemitter3":39}],213:[function(require,module,exports){
+},{"../core":121,"./InteractionData":212,"./InteractionEvent":213,"./InteractionTrackingData":215,"./interactiveTarget":217,"eventemitter3":40}],215:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -57120,7 +57643,7 @@ InteractionTrackingData.FLAGS = Object.freeze({
     RIGHT_DOWN: 1 << 2
 });
 
-},{}],214:[function(require,module,exports){
+},{}],216:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57172,7 +57695,7 @@ Object.defineProperty(exports, 'InteractionEvent', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./InteractionData":210,"./InteractionEvent":211,"./InteractionManager":212,"./InteractionTrackingData":213,"./interactiveTarget":215}],215:[function(require,module,exports){
+},{"./InteractionData":212,"./InteractionEvent":213,"./InteractionManager":214,"./InteractionTrackingData":215,"./interactiveTarget":217}],217:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57289,7 +57812,7 @@ exports.default = {
   _trackedPointers: undefined
 };
 
-},{}],216:[function(require,module,exports){
+},{}],218:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57381,7 +57904,7 @@ function parse(resource, texture) {
     resource.bitmapFont = _extras.BitmapText.registerFont(resource.data, texture);
 }
 
-},{"../core":119,"../extras":195,"path":50,"resource-loader":263}],217:[function(require,module,exports){
+},{"../core":121,"../extras":197,"path":52,"resource-loader":265}],219:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57509,7 +58032,7 @@ AppPrototype.destroy = function destroy(removeView) {
     this._parentDestroy(removeView);
 };
 
-},{"../core/Application":97,"./bitmapFontParser":216,"./loader":218,"./spritesheetParser":219,"./textureParser":220,"resource-loader":263}],218:[function(require,module,exports){
+},{"../core/Application":99,"./bitmapFontParser":218,"./loader":220,"./spritesheetParser":221,"./textureParser":222,"resource-loader":265}],220:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57680,7 +58203,7 @@ var Resource = _resourceLoader2.default.Resource;
 
 Resource.setExtensionXhrType('fnt', Resource.XHR_RESPONSE_TYPE.DOCUMENT);
 
-},{"./bitmapFontParser":216,"./spritesheetParser":219,"./textureParser":220,"eventemitter3":39,"resource-loader":263,"resource-loader/lib/middlewares/parsing/blob":264}],219:[function(require,module,exports){
+},{"./bitmapFontParser":218,"./spritesheetParser":221,"./textureParser":222,"eventemitter3":40,"resource-loader":265,"resource-loader/lib/middlewares/parsing/blob":266}],221:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57744,7 +58267,7 @@ function getResourcePath(resource, baseUrl) {
     return _url2.default.resolve(resource.url.replace(baseUrl, ''), resource.data.meta.image);
 }
 
-},{"../core":119,"resource-loader":263,"url":280}],220:[function(require,module,exports){
+},{"../core":121,"resource-loader":265,"url":282}],222:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -57767,7 +58290,7 @@ var _Texture2 = _interopRequireDefault(_Texture);
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"../core/textures/Texture":169,"resource-loader":263}],221:[function(require,module,exports){
+},{"../core/textures/Texture":171,"resource-loader":265}],223:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -58136,7 +58659,7 @@ Mesh.DRAW_MODES = {
   TRIANGLES: 1
 };
 
-},{"../core":119,"../core/textures/Texture":169}],222:[function(require,module,exports){
+},{"../core":121,"../core/textures/Texture":171}],224:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -58523,7 +59046,7 @@ var NineSlicePlane = function (_Plane) {
 
 exports.default = NineSlicePlane;
 
-},{"./Plane":223}],223:[function(require,module,exports){
+},{"./Plane":225}],225:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -58664,7 +59187,7 @@ var Plane = function (_Mesh) {
 
 exports.default = Plane;
 
-},{"./Mesh":221}],224:[function(require,module,exports){
+},{"./Mesh":223}],226:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -58900,7 +59423,7 @@ var Rope = function (_Mesh) {
 
 exports.default = Rope;
 
-},{"./Mesh":221}],225:[function(require,module,exports){
+},{"./Mesh":223}],227:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -59186,7 +59709,7 @@ exports.default = MeshSpriteRenderer;
 
 core.CanvasRenderer.registerPlugin('mesh', MeshSpriteRenderer);
 
-},{"../../core":119,"../Mesh":221}],226:[function(require,module,exports){
+},{"../../core":121,"../Mesh":223}],228:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -59247,7 +59770,7 @@ Object.defineProperty(exports, 'Rope', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./Mesh":221,"./NineSlicePlane":222,"./Plane":223,"./Rope":224,"./canvas/CanvasMeshRenderer":225,"./webgl/MeshRenderer":227}],227:[function(require,module,exports){
+},{"./Mesh":223,"./NineSlicePlane":224,"./Plane":225,"./Rope":226,"./canvas/CanvasMeshRenderer":227,"./webgl/MeshRenderer":229}],229:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -59398,7 +59921,7 @@ exports.default = MeshRenderer;
 
 core.WebGLRenderer.registerPlugin('mesh', MeshRenderer);
 
-},{"../../core":119,"../Mesh":221,"path":50,"pixi-gl-core":70}],228:[function(require,module,exports){
+},{"../../core":121,"../Mesh":223,"path":52,"pixi-gl-core":72}],230:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -59778,7 +60301,7 @@ var ParticleContainer = function (_core$Container) {
 
 exports.default = ParticleContainer;
 
-},{"../core":119,"../core/utils":179}],229:[function(require,module,exports){
+},{"../core":121,"../core/utils":181}],231:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -59803,7 +60326,7 @@ Object.defineProperty(exports, 'ParticleRenderer', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./ParticleContainer":228,"./webgl/ParticleRenderer":231}],230:[function(require,module,exports){
+},{"./ParticleContainer":230,"./webgl/ParticleRenderer":233}],232:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -60050,7 +60573,7 @@ var ParticleBuffer = function () {
 
 exports.default = ParticleBuffer;
 
-},{"../../core/utils/createIndicesForQuads":177,"pixi-gl-core":70}],231:[function(require,module,exports){
+},{"../../core/utils/createIndicesForQuads":179,"pixi-gl-core":72}],233:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -60526,7 +61049,7 @@ exports.default = ParticleRenderer;
 
 core.WebGLRenderer.registerPlugin('particle', ParticleRenderer);
 
-},{"../../core":119,"../../core/utils":179,"./ParticleBuffer":230,"./ParticleShader":232}],232:[function(require,module,exports){
+},{"../../core":121,"../../core/utils":181,"./ParticleBuffer":232,"./ParticleShader":234}],234:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -60569,7 +61092,7 @@ var ParticleShader = function (_Shader) {
 
 exports.default = ParticleShader;
 
-},{"../../core/Shader":98}],233:[function(require,module,exports){
+},{"../../core/Shader":100}],235:[function(require,module,exports){
 "use strict";
 
 // References:
@@ -60587,7 +61110,7 @@ if (!Math.sign) {
     };
 }
 
-},{}],234:[function(require,module,exports){
+},{}],236:[function(require,module,exports){
 'use strict';
 
 var _objectAssign = require('object-assign');
@@ -60602,7 +61125,7 @@ if (!Object.assign) {
 // https://github.com/sindresorhus/object-assign
 // https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/assign
 
-},{"object-assign":48}],235:[function(require,module,exports){
+},{"object-assign":50}],237:[function(require,module,exports){
 'use strict';
 
 require('./Object.assign');
@@ -60627,7 +61150,7 @@ if (!window.Uint16Array) {
     window.Uint16Array = Array;
 }
 
-},{"./Math.sign":233,"./Object.assign":234,"./requestAnimationFrame":236}],236:[function(require,module,exports){
+},{"./Math.sign":235,"./Object.assign":236,"./requestAnimationFrame":238}],238:[function(require,module,exports){
 (function (global){
 'use strict';
 
@@ -60704,7 +61227,7 @@ if (!global.cancelAnimationFrame) {
 }
 
 }).call(this,typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{}],237:[function(require,module,exports){
+},{}],239:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -61192,7 +61715,7 @@ function findTextStyle(item, queue) {
     return false;
 }
 
-},{"../core":119,"./limiters/CountLimiter":240}],238:[function(require,module,exports){
+},{"../core":121,"./limiters/CountLimiter":242}],240:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -61312,7 +61835,7 @@ function uploadBaseTextures(prepare, item) {
 
 core.CanvasRenderer.registerPlugin('prepare', CanvasPrepare);
 
-},{"../../core":119,"../BasePrepare":237}],239:[function(require,module,exports){
+},{"../../core":121,"../BasePrepare":239}],241:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -61364,7 +61887,7 @@ Object.defineProperty(exports, 'TimeLimiter', {
 
 function _interopRequireDefault(obj) { return obj && obj.__esModule ? obj : { default: obj }; }
 
-},{"./BasePrepare":237,"./canvas/CanvasPrepare":238,"./limiters/CountLimiter":240,"./limiters/TimeLimiter":241,"./webgl/WebGLPrepare":242}],240:[function(require,module,exports){
+},{"./BasePrepare":239,"./canvas/CanvasPrepare":240,"./limiters/CountLimiter":242,"./limiters/TimeLimiter":243,"./webgl/WebGLPrepare":244}],242:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -61422,7 +61945,7 @@ var CountLimiter = function () {
 
 exports.default = CountLimiter;
 
-},{}],241:[function(require,module,exports){
+},{}],243:[function(require,module,exports){
 "use strict";
 
 exports.__esModule = true;
@@ -61480,7 +62003,7 @@ var TimeLimiter = function () {
 
 exports.default = TimeLimiter;
 
-},{}],242:[function(require,module,exports){
+},{}],244:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -61602,7 +62125,7 @@ function findGraphics(item, queue) {
 
 core.WebGLRenderer.registerPlugin('prepare', WebGLPrepare);
 
-},{"../../core":119,"../BasePrepare":237}],243:[function(require,module,exports){
+},{"../../core":121,"../BasePrepare":239}],245:[function(require,module,exports){
 (function (process){
 'use strict';
 
@@ -61650,7 +62173,7 @@ function nextTick(fn, arg1, arg2, arg3) {
 
 
 }).call(this,require('_process'))
-},{"_process":244}],244:[function(require,module,exports){
+},{"_process":246}],246:[function(require,module,exports){
 // shim for using process in browser
 var process = module.exports = {};
 
@@ -61836,7 +62359,7 @@ process.chdir = function (dir) {
 };
 process.umask = function() { return 0; };
 
-},{}],245:[function(require,module,exports){
+},{}],247:[function(require,module,exports){
 (function (global){
 /*! https://mths.be/punycode v1.4.1 by @mathias */
 ;(function(root) {
@@ -62373,7 +62896,7 @@ process.umask = function() { return 0; };
 }(this));
 
 }).call(this,typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{}],246:[function(require,module,exports){
+},{}],248:[function(require,module,exports){
 // Copyright Joyent, Inc. and other Node contributors.
 //
 // Permission is hereby granted, free of charge, to any person obtaining a
@@ -62459,7 +62982,7 @@ var isArray = Array.isArray || function (xs) {
   return Object.prototype.toString.call(xs) === '[object Array]';
 };
 
-},{}],247:[function(require,module,exports){
+},{}],249:[function(require,module,exports){
 // Copyright Joyent, Inc. and other Node contributors.
 //
 // Permission is hereby granted, free of charge, to any person obtaining a
@@ -62546,13 +63069,13 @@ var objectKeys = Object.keys || function (obj) {
   return res;
 };
 
-},{}],248:[function(require,module,exports){
+},{}],250:[function(require,module,exports){
 'use strict';
 
 exports.decode = exports.parse = require('./decode');
 exports.encode = exports.stringify = require('./encode');
 
-},{"./decode":246,"./encode":247}],249:[function(require,module,exports){
+},{"./decode":248,"./encode":249}],251:[function(require,module,exports){
 // Copyright Joyent, Inc. and other Node contributors.
 //
 // Permission is hereby granted, free of charge, to any person obtaining a
@@ -62677,7 +63200,7 @@ function forEach(xs, f) {
     f(xs[i], i);
   }
 }
-},{"./_stream_readable":251,"./_stream_writable":253,"core-util-is":6,"inherits":43,"process-nextick-args":243}],250:[function(require,module,exports){
+},{"./_stream_readable":253,"./_stream_writable":255,"core-util-is":7,"inherits":45,"process-nextick-args":245}],252:[function(require,module,exports){
 // Copyright Joyent, Inc. and other Node contributors.
 //
 // Permission is hereby granted, free of charge, to any person obtaining a
@@ -62725,7 +63248,7 @@ function PassThrough(options) {
 PassThrough.prototype._transform = function (chunk, encoding, cb) {
   cb(null, chunk);
 };
-},{"./_stream_transform":252,"core-util-is":6,"inherits":43}],251:[function(require,module,exports){
+},{"./_stream_transform":254,"core-util-is":7,"inherits":45}],253:[function(require,module,exports){
 (function (process,global){
 // Copyright Joyent, Inc. and other Node contributors.
 //
@@ -63743,7 +64266,7 @@ function indexOf(xs, x) {
   return -1;
 }
 }).call(this,require('_process'),typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{"./_stream_duplex":249,"./internal/streams/BufferList":254,"./internal/streams/destroy":255,"./internal/streams/stream":256,"_process":244,"core-util-is":6,"events":284,"inherits":43,"isarray":45,"process-nextick-args":243,"safe-buffer":265,"string_decoder/":278,"util":3}],252:[function(require,module,exports){
+},{"./_stream_duplex":251,"./internal/streams/BufferList":256,"./internal/streams/destroy":257,"./internal/streams/stream":258,"_process":246,"core-util-is":7,"events":41,"inherits":45,"isarray":47,"process-nextick-args":245,"safe-buffer":267,"string_decoder/":280,"util":3}],254:[function(require,module,exports){
 // Copyright Joyent, Inc. and other Node contributors.
 //
 // Permission is hereby granted, free of charge, to any person obtaining a
@@ -63958,7 +64481,7 @@ function done(stream, er, data) {
 
   return stream.push(null);
 }
-},{"./_stream_duplex":249,"core-util-is":6,"inherits":43}],253:[function(require,module,exports){
+},{"./_stream_duplex":251,"core-util-is":7,"inherits":45}],255:[function(require,module,exports){
 (function (process,global){
 // Copyright Joyent, Inc. and other Node contributors.
 //
@@ -64638,7 +65161,7 @@ Writable.prototype._destroy = function (err, cb) {
   cb(err);
 };
 }).call(this,require('_process'),typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{"./_stream_duplex":249,"./internal/streams/destroy":255,"./internal/streams/stream":256,"_process":244,"core-util-is":6,"inherits":43,"process-nextick-args":243,"safe-buffer":265,"util-deprecate":282}],254:[function(require,module,exports){
+},{"./_stream_duplex":251,"./internal/streams/destroy":257,"./internal/streams/stream":258,"_process":246,"core-util-is":7,"inherits":45,"process-nextick-args":245,"safe-buffer":267,"util-deprecate":284}],256:[function(require,module,exports){
 'use strict';
 
 function _classCallCheck(instance, Constructor) { if (!(instance instanceof Constructor)) { throw new TypeError("Cannot call a class as a function"); } }
@@ -64718,7 +65241,7 @@ if (util && util.inspect && util.inspect.custom) {
     return this.constructor.name + ' ' + obj;
   };
 }
-},{"safe-buffer":265,"util":3}],255:[function(require,module,exports){
+},{"safe-buffer":267,"util":3}],257:[function(require,module,exports){
 'use strict';
 
 /*<replacement>*/
@@ -64793,10 +65316,10 @@ module.exports = {
   destroy: destroy,
   undestroy: undestroy
 };
-},{"process-nextick-args":243}],256:[function(require,module,exports){
+},{"process-nextick-args":245}],258:[function(require,module,exports){
 module.exports = require('events').EventEmitter;
 
-},{"events":284}],257:[function(require,module,exports){
+},{"events":41}],259:[function(require,module,exports){
 exports = module.exports = require('./lib/_stream_readable.js');
 exports.Stream = exports;
 exports.Readable = exports;
@@ -64805,7 +65328,7 @@ exports.Duplex = require('./lib/_stream_duplex.js');
 exports.Transform = require('./lib/_stream_transform.js');
 exports.PassThrough = require('./lib/_stream_passthrough.js');
 
-},{"./lib/_stream_duplex.js":249,"./lib/_stream_passthrough.js":250,"./lib/_stream_readable.js":251,"./lib/_stream_transform.js":252,"./lib/_stream_writable.js":253}],258:[function(require,module,exports){
+},{"./lib/_stream_duplex.js":251,"./lib/_stream_passthrough.js":252,"./lib/_stream_readable.js":253,"./lib/_stream_transform.js":254,"./lib/_stream_writable.js":255}],260:[function(require,module,exports){
 'use strict'
 
 /**
@@ -64835,7 +65358,7 @@ module.exports = function removeItems(arr, startIdx, removeCount)
   arr.length = len
 }
 
-},{}],259:[function(require,module,exports){
+},{}],261:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -65488,7 +66011,7 @@ var Loader = function () {
 
 exports.default = Loader;
 
-},{"./Resource":260,"./async":261,"mini-signals":47,"parse-uri":49}],260:[function(require,module,exports){
+},{"./Resource":262,"./async":263,"mini-signals":49,"parse-uri":51}],262:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -66644,7 +67167,7 @@ function reqType(xhr) {
     return xhr.toString().replace('object ', '');
 }
 
-},{"mini-signals":47,"parse-uri":49}],261:[function(require,module,exports){
+},{"mini-signals":49,"parse-uri":51}],263:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -66853,7 +67376,7 @@ function queue(worker, concurrency) {
     return q;
 }
 
-},{}],262:[function(require,module,exports){
+},{}],264:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -66921,7 +67444,7 @@ function encodeBinary(input) {
     return output;
 }
 
-},{}],263:[function(require,module,exports){
+},{}],265:[function(require,module,exports){
 'use strict';
 
 // import Loader from './Loader';
@@ -66945,7 +67468,7 @@ module.exports = Loader;
 // export default Loader;
 module.exports.default = Loader;
 
-},{"./Loader":259,"./Resource":260,"./async":261,"./b64":262}],264:[function(require,module,exports){
+},{"./Loader":261,"./Resource":262,"./async":263,"./b64":264}],266:[function(require,module,exports){
 'use strict';
 
 exports.__esModule = true;
@@ -67033,7 +67556,7 @@ function blobMiddlewareFactory() {
     };
 }
 
-},{"../../Resource":260,"../../b64":262}],265:[function(require,module,exports){
+},{"../../Resource":262,"../../b64":264}],267:[function(require,module,exports){
 /* eslint-disable node/no-deprecated-api */
 var buffer = require('buffer')
 var Buffer = buffer.Buffer
@@ -67097,7 +67620,7 @@ SafeBuffer.allocUnsafeSlow = function (size) {
   return buffer.SlowBuffer(size)
 }
 
-},{"buffer":4}],266:[function(require,module,exports){
+},{"buffer":5}],268:[function(require,module,exports){
 // A library of seedable RNGs implemented in Javascript.
 //
 // Usage:
@@ -67159,7 +67682,7 @@ sr.tychei = tychei;
 
 module.exports = sr;
 
-},{"./lib/alea":267,"./lib/tychei":268,"./lib/xor128":269,"./lib/xor4096":270,"./lib/xorshift7":271,"./lib/xorwow":272,"./seedrandom":273}],267:[function(require,module,exports){
+},{"./lib/alea":269,"./lib/tychei":270,"./lib/xor128":271,"./lib/xor4096":272,"./lib/xorshift7":273,"./lib/xorwow":274,"./seedrandom":275}],269:[function(require,module,exports){
 // A port of an algorithm by Johannes Baagøe <baagoe@baagoe.com>, 2010
 // http://baagoe.com/en/RandomMusings/javascript/
 // https://github.com/nquinlan/better-random-numbers-for-javascript-mirror
@@ -67275,7 +67798,7 @@ if (module && module.exports) {
 
 
 
-},{}],268:[function(require,module,exports){
+},{}],270:[function(require,module,exports){
 // A Javascript implementaion of the "Tyche-i" prng algorithm by
 // Samuel Neves and Filipe Araujo.
 // See https://eden.dei.uc.pt/~sneves/pubs/2011-snfa2.pdf
@@ -67380,7 +67903,7 @@ if (module && module.exports) {
 
 
 
-},{}],269:[function(require,module,exports){
+},{}],271:[function(require,module,exports){
 // A Javascript implementaion of the "xor128" prng algorithm by
 // George Marsaglia.  See http://www.jstatsoft.org/v08/i14/paper
 
@@ -67463,7 +67986,7 @@ if (module && module.exports) {
 
 
 
-},{}],270:[function(require,module,exports){
+},{}],272:[function(require,module,exports){
 // A Javascript implementaion of Richard Brent's Xorgens xor4096 algorithm.
 //
 // This fast non-cryptographic random number generator is designed for
@@ -67611,7 +68134,7 @@ if (module && module.exports) {
   (typeof define) == 'function' && define   // present with an AMD loader
 );
 
-},{}],271:[function(require,module,exports){
+},{}],273:[function(require,module,exports){
 // A Javascript implementaion of the "xorshift7" algorithm by
 // François Panneton and Pierre L'ecuyer:
 // "On the Xorgshift Random Number Generators"
@@ -67710,7 +68233,7 @@ if (module && module.exports) {
 );
 
 
-},{}],272:[function(require,module,exports){
+},{}],274:[function(require,module,exports){
 // A Javascript implementaion of the "xorwow" prng algorithm by
 // George Marsaglia.  See http://www.jstatsoft.org/v08/i14/paper
 
@@ -67798,7 +68321,7 @@ if (module && module.exports) {
 
 
 
-},{}],273:[function(require,module,exports){
+},{}],275:[function(require,module,exports){
 /*
 Copyright 2014 David Bau.
 
@@ -68047,7 +68570,7 @@ if ((typeof module) == 'object' && module.exports) {
   Math    // math: package containing random, pow, and seedrandom
 );
 
-},{"crypto":3}],274:[function(require,module,exports){
+},{"crypto":3}],276:[function(require,module,exports){
 (function (global){
 var ClientRequest = require('./lib/request')
 var IncomingMessage = require('./lib/response')
@@ -68133,7 +68656,7 @@ http.METHODS = [
 	'UNSUBSCRIBE'
 ]
 }).call(this,typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{"./lib/request":276,"./lib/response":277,"builtin-status-codes":5,"url":280,"xtend":286}],275:[function(require,module,exports){
+},{"./lib/request":278,"./lib/response":279,"builtin-status-codes":6,"url":282,"xtend":286}],277:[function(require,module,exports){
 (function (global){
 exports.fetch = isFunction(global.fetch) && isFunction(global.ReadableStream)
 
@@ -68210,7 +68733,7 @@ function isFunction (value) {
 xhr = null // Help gc
 
 }).call(this,typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{}],276:[function(require,module,exports){
+},{}],278:[function(require,module,exports){
 (function (process,global,Buffer){
 var capability = require('./capability')
 var inherits = require('inherits')
@@ -68537,7 +69060,7 @@ var unsafeHeaders = [
 ]
 
 }).call(this,require('_process'),typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {},require("buffer").Buffer)
-},{"./capability":275,"./response":277,"_process":244,"buffer":4,"inherits":43,"readable-stream":257,"to-arraybuffer":279}],277:[function(require,module,exports){
+},{"./capability":277,"./response":279,"_process":246,"buffer":5,"inherits":45,"readable-stream":259,"to-arraybuffer":281}],279:[function(require,module,exports){
 (function (process,global,Buffer){
 var capability = require('./capability')
 var inherits = require('inherits')
@@ -68758,7 +69281,7 @@ IncomingMessage.prototype._onXHRProgress = function () {
 }
 
 }).call(this,require('_process'),typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {},require("buffer").Buffer)
-},{"./capability":275,"_process":244,"buffer":4,"inherits":43,"readable-stream":257}],278:[function(require,module,exports){
+},{"./capability":277,"_process":246,"buffer":5,"inherits":45,"readable-stream":259}],280:[function(require,module,exports){
 'use strict';
 
 var Buffer = require('safe-buffer').Buffer;
@@ -69031,7 +69554,7 @@ function simpleWrite(buf) {
 function simpleEnd(buf) {
   return buf && buf.length ? this.write(buf) : '';
 }
-},{"safe-buffer":265}],279:[function(require,module,exports){
+},{"safe-buffer":267}],281:[function(require,module,exports){
 var Buffer = require('buffer').Buffer
 
 module.exports = function (buf) {
@@ -69060,7 +69583,7 @@ module.exports = function (buf) {
 	}
 }
 
-},{"buffer":4}],280:[function(require,module,exports){
+},{"buffer":5}],282:[function(require,module,exports){
 // Copyright Joyent, Inc. and other Node contributors.
 //
 // Permission is hereby granted, free of charge, to any person obtaining a
@@ -69794,7 +70317,7 @@ Url.prototype.parseHost = function() {
   if (host) this.hostname = host;
 };
 
-},{"./util":281,"punycode":245,"querystring":248}],281:[function(require,module,exports){
+},{"./util":283,"punycode":247,"querystring":250}],283:[function(require,module,exports){
 'use strict';
 
 module.exports = {
@@ -69812,7 +70335,7 @@ module.exports = {
   }
 };
 
-},{}],282:[function(require,module,exports){
+},{}],284:[function(require,module,exports){
 (function (global){
 
 /**
@@ -69883,312 +70406,6 @@ function config (name) {
 }
 
 }).call(this,typeof global !== "undefined" ? global : typeof self !== "undefined" ? self : typeof window !== "undefined" ? window : {})
-},{}],283:[function(require,module,exports){
-arguments[4][3][0].apply(exports,arguments)
-},{"dup":3}],284:[function(require,module,exports){
-// Copyright Joyent, Inc. and other Node contributors.
-//
-// Permission is hereby granted, free of charge, to any person obtaining a
-// copy of this software and associated documentation files (the
-// "Software"), to deal in the Software without restriction, including
-// without limitation the rights to use, copy, modify, merge, publish,
-// distribute, sublicense, and/or sell copies of the Software, and to permit
-// persons to whom the Software is furnished to do so, subject to the
-// following conditions:
-//
-// The above copyright notice and this permission notice shall be included
-// in all copies or substantial portions of the Software.
-//
-// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
-// OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
-// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN
-// NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
-// DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
-// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE
-// USE OR OTHER DEALINGS IN THE SOFTWARE.
-
-function EventEmitter() {
-  this._events = this._events || {};
-  this._maxListeners = this._maxListeners || undefined;
-}
-module.exports = EventEmitter;
-
-// Backwards-compat with node 0.10.x
-EventEmitter.EventEmitter = EventEmitter;
-
-EventEmitter.prototype._events = undefined;
-EventEmitter.prototype._maxListeners = undefined;
-
-// By default EventEmitters will print a warning if more than 10 listeners are
-// added to it. This is a useful default which helps finding memory leaks.
-EventEmitter.defaultMaxListeners = 10;
-
-// Obviously not all Emitters should be limited to 10. This function allows
-// that to be increased. Set to zero for unlimited.
-EventEmitter.prototype.setMaxListeners = function(n) {
-  if (!isNumber(n) || n < 0 || isNaN(n))
-    throw TypeError('n must be a positive number');
-  this._maxListeners = n;
-  return this;
-};
-
-EventEmitter.prototype.emit = function(type) {
-  var er, handler, len, args, i, listeners;
-
-  if (!this._events)
-    this._events = {};
-
-  // If there is no 'error' event listener then throw.
-  if (type === 'error') {
-    if (!this._events.error ||
-        (isObject(this._events.error) && !this._events.error.length)) {
-      er = arguments[1];
-      if (er instanceof Error) {
-        throw er; // Unhandled 'error' event
-      } else {
-        // At least give some kind of context to the user
-        var err = new Error('Uncaught, unspecified "error" event. (' + er + ')');
-        err.context = er;
-        throw err;
-      }
-    }
-  }
-
-  handler = this._events[type];
-
-  if (isUndefined(handler))
-    return false;
-
-  if (isFunction(handler)) {
-    switch (arguments.length) {
-      // fast cases
-      case 1:
-        handler.call(this);
-        break;
-      case 2:
-        handler.call(this, arguments[1]);
-        break;
-      case 3:
-        handler.call(this, arguments[1], arguments[2]);
-        break;
-      // slower
-      default:
-        args = Array.prototype.slice.call(arguments, 1);
-        handler.apply(this, args);
-    }
-  } else if (isObject(handler)) {
-    args = Array.prototype.slice.call(arguments, 1);
-    listeners = handler.slice();
-    len = listeners.length;
-    for (i = 0; i < len; i++)
-      listeners[i].apply(this, args);
-  }
-
-  return true;
-};
-
-EventEmitter.prototype.addListener = function(type, listener) {
-  var m;
-
-  if (!isFunction(listener))
-    throw TypeError('listener must be a function');
-
-  if (!this._events)
-    this._events = {};
-
-  // To avoid recursion in the case that type === "newListener"! Before
-  // adding it to the listeners, first emit "newListener".
-  if (this._events.newListener)
-    this.emit('newListener', type,
-              isFunction(listener.listener) ?
-              listener.listener : listener);
-
-  if (!this._events[type])
-    // Optimize the case of one listener. Don't need the extra array object.
-    this._events[type] = listener;
-  else if (isObject(this._events[type]))
-    // If we've already got an array, just append.
-    this._events[type].push(listener);
-  else
-    // Adding the second element, need to change to array.
-    this._events[type] = [this._events[type], listener];
-
-  // Check for listener leak
-  if (isObject(this._events[type]) && !this._events[type].warned) {
-    if (!isUndefined(this._maxListeners)) {
-      m = this._maxListeners;
-    } else {
-      m = EventEmitter.defaultMaxListeners;
-    }
-
-    if (m && m > 0 && this._events[type].length > m) {
-      this._events[type].warned = true;
-      console.error('(node) warning: possible EventEmitter memory ' +
-                    'leak detected. %d listeners added. ' +
-                    'Use emitter.setMaxListeners() to increase limit.',
-                    this._events[type].length);
-      if (typeof console.trace === 'function') {
-        // not supported in IE 10
-        console.trace();
-      }
-    }
-  }
-
-  return this;
-};
-
-EventEmitter.prototype.on = EventEmitter.prototype.addListener;
-
-EventEmitter.prototype.once = function(type, listener) {
-  if (!isFunction(listener))
-    throw TypeError('listener must be a function');
-
-  var fired = false;
-
-  function g() {
-    this.removeListener(type, g);
-
-    if (!fired) {
-      fired = true;
-      listener.apply(this, arguments);
-    }
-  }
-
-  g.listener = listener;
-  this.on(type, g);
-
-  return this;
-};
-
-// emits a 'removeListener' event iff the listener was removed
-EventEmitter.prototype.removeListener = function(type, listener) {
-  var list, position, length, i;
-
-  if (!isFunction(listener))
-    throw TypeError('listener must be a function');
-
-  if (!this._events || !this._events[type])
-    return this;
-
-  list = this._events[type];
-  length = list.length;
-  position = -1;
-
-  if (list === listener ||
-      (isFunction(list.listener) && list.listener === listener)) {
-    delete this._events[type];
-    if (this._events.removeListener)
-      this.emit('removeListener', type, listener);
-
-  } else if (isObject(list)) {
-    for (i = length; i-- > 0;) {
-      if (list[i] === listener ||
-          (list[i].listener && list[i].listener === listener)) {
-        position = i;
-        break;
-      }
-    }
-
-    if (position < 0)
-      return this;
-
-    if (list.length === 1) {
-      list.length = 0;
-      delete this._events[type];
-    } else {
-      list.splice(position, 1);
-    }
-
-    if (this._events.removeListener)
-      this.emit('removeListener', type, listener);
-  }
-
-  return this;
-};
-
-EventEmitter.prototype.removeAllListeners = function(type) {
-  var key, listeners;
-
-  if (!this._events)
-    return this;
-
-  // not listening for removeListener, no need to emit
-  if (!this._events.removeListener) {
-    if (arguments.length === 0)
-      this._events = {};
-    else if (this._events[type])
-      delete this._events[type];
-    return this;
-  }
-
-  // emit removeListener for all listeners on all events
-  if (arguments.length === 0) {
-    for (key in this._events) {
-      if (key === 'removeListener') continue;
-      this.removeAllListeners(key);
-    }
-    this.removeAllListeners('removeListener');
-    this._events = {};
-    return this;
-  }
-
-  listeners = this._events[type];
-
-  if (isFunction(listeners)) {
-    this.removeListener(type, listeners);
-  } else if (listeners) {
-    // LIFO order
-    while (listeners.length)
-      this.removeListener(type, listeners[listeners.length - 1]);
-  }
-  delete this._events[type];
-
-  return this;
-};
-
-EventEmitter.prototype.listeners = function(type) {
-  var ret;
-  if (!this._events || !this._events[type])
-    ret = [];
-  else if (isFunction(this._events[type]))
-    ret = [this._events[type]];
-  else
-    ret = this._events[type].slice();
-  return ret;
-};
-
-EventEmitter.prototype.listenerCount = function(type) {
-  if (this._events) {
-    var evlistener = this._events[type];
-
-    if (isFunction(evlistener))
-      return 1;
-    else if (evlistener)
-      return evlistener.length;
-  }
-  return 0;
-};
-
-EventEmitter.listenerCount = function(emitter, type) {
-  return emitter.listenerCount(type);
-};
-
-function isFunction(arg) {
-  return typeof arg === 'function';
-}
-
-function isNumber(arg) {
-  return typeof arg === 'number';
-}
-
-function isObject(arg) {
-  return typeof arg === 'object' && arg !== null;
-}
-
-function isUndefined(arg) {
-  return arg === void 0;
-}
-
 },{}],285:[function(require,module,exports){
 (function (process,Buffer){
 /**
@@ -70813,7 +71030,7 @@ exports.XMLHttpRequest = function() {
 };
 
 }).call(this,require('_process'),require("buffer").Buffer)
-},{"_process":244,"buffer":4,"child_process":283,"fs":283,"http":274,"https":41,"url":280}],286:[function(require,module,exports){
+},{"_process":246,"buffer":5,"child_process":4,"fs":4,"http":276,"https":43,"url":282}],286:[function(require,module,exports){
 module.exports = extend
 
 var hasOwnProperty = Object.prototype.hasOwnProperty;
@@ -71845,7 +72062,7 @@ class Random
 }
 
 module.exports = new Random()
-},{"seedrandom":266}],290:[function(require,module,exports){
+},{"seedrandom":268}],290:[function(require,module,exports){
 "use strict";
 exports.__esModule = true;
 var PIXI = require("pixi.js");
@@ -71865,8 +72082,17 @@ var Axis = /** @class */ (function () {
             .attr("width", windowWidth)
             .attr("class", "top-axis")
             .append("g");
+        this.clock = this.container.append("text")
+            .attr("x", windowWidth)
+            .attr("y", axisHeight)
+            .attr("text-anchor", "end")
+            .attr("font-size", "10px");
     }
-    Axis.prototype.update = function (startTs, endTs) {
+    Axis.prototype.update = function (startTs, endTs, wallClock) {
+        // Absolute time at the left edge of the view, when the trace says when it started.
+        if (wallClock !== null) {
+            this.clock.text(new Date((wallClock + startTs) * 1000).toISOString());
+        }
         var axisScale = d3.scaleLinear()
             .domain([startTs, endTs])
             .range([0, this.windowWidth]);
@@ -71940,22 +72166,64 @@ var Cyclotron = /** @class */ (function () {
         this.arrowOverlay.height = this.viewportHeight;
         this.app.stage.addChild(this.arrowOverlay);
         this.arrowColor = 0xca271b;
+        this.messageColor = 0x1b6eca;
         this.ticker = PIXI.ticker.shared;
         this.ticker.autoStart = true;
         this.ticker.add(this.draw, this);
         this.lastViewport = { width: 0, height: 0, ts: 0 };
         this.spanManager = new model_1.SpanManager(this.timeline);
         // TODO: Print that we're waiting for data or something here.
-        var socket = new WebSocket("ws://127.0.0.1:3001", "cyclotron-ws");
-        var i = 0;
+        fetch("/config")
+            .then(function (response) { return response.json(); })
+            .then(function (config) { return _this.connect(config); })["catch"](function (error) { alert("Couldn't load config: " + error); });
+    }
+    // Open the websocket wherever the server says it is, on the host and port we were served from.
+    Cyclotron.prototype.connect = function (config) {
+        var _this = this;
+        var scheme = window.location.protocol === "https:" ? "wss:" : "ws:";
+        // Pass on which traces to show and how to filter them, e.g. `?trace=run.log&grep=request`, or
+        // `&max_events=100000` to keep a huge trace from swamping us.
+        var url = scheme + "//" + window.location.host + config.websocket + window.location.search;
+        var socket = new WebSocket(url, "cyclotron-ws");
         socket.onmessage = function (event) { _this.addEvent(JSON.parse(event.data)); };
-        socket.onopen = function (event) { socket.send("cyclotron.log"); };
         socket.onerror = function (event) { alert("Socket error " + event); };
         socket.onclose = function (event) { alert("Socket closed " + event); };
-    }
+        this.socket = socket;
+    };
+    // Show only the spans named in `goals` (or everything, if it's empty) with their ancestors and
+    // descendants, hiding wakeups from spans named in `hideWakeupsFrom`.  The server resets us and
+    // resends the trace filtered that way.
+    Cyclotron.prototype.setFilter = function (goals, hideWakeupsFrom) {
+        if (hideWakeupsFrom === void 0) { hideWakeupsFrom = []; }
+        this.socket.send(JSON.stringify({ SetFilter: { goals: goals, hide_wakeups_from: hideWakeupsFrom } }));
+    };
+    // Show only what happened between `start` and `end` seconds into the trace, and at most
+    // `maxEvents` events of it; zoomed out, polls are merged together to stay under that.  Without
+    // a `start`, go back to showing the whole trace, following along as it grows.
+    Cyclotron.prototype.setWindow = function (start, end, maxEvents) {
+        if (start === void 0) { start = null; }
+        if (end === void 0) { end = null; }
+        if (maxEvents === void 0) { maxEvents = 100000; }
+        var ts = function (secs) { return secs === null ? null : { secs: Math.floor(secs), nanos: Math.floor((secs % 1) * 1e9) }; };
+        var window = start === null ? null : { start: ts(start), end: ts(end), max_events: maxEvents };
+        this.socket.send(JSON.stringify({ SetWindow: window }));
+    };
     Cyclotron.prototype.addEvent = function (event) {
+        if (event.Reset !== undefined) {
+            this.reset();
+            return;
+        }
         this.spanManager.addEvent(event);
     };
+    // Throw away everything drawn so far, when the server is about to resend the trace from the
+    // start (e.g. because the file was truncated).
+    Cyclotron.prototype.reset = function () {
+        this.timeline.removeChildren();
+        this.textOverlay.removeChildren();
+        this.arrowOverlay.removeChildren();
+        this.spanManager = new model_1.SpanManager(this.timeline);
+        this.lastViewport = { width: 0, height: 0, ts: 0 };
+    };
     Cyclotron.prototype.viewportDirty = function () {
         var viewArea = this.timeline.hitArea;
         return this.lastViewport.width !== viewArea.width
@@ -72009,7 +72277,7 @@ var Cyclotron = /** @class */ (function () {
     Cyclotron.prototype.drawViewport = function (assignment) {
         var startTs = this.timeline.hitArea.x;
         var endTs = startTs + this.timeline.hitArea.width;
-        this.axis.update(startTs, endTs);
+        this.axis.update(startTs, endTs, this.spanManager.wallClock);
         var maxHeight = Object.keys(assignment).length;
         var laneHeightPx = this.viewportHeight / maxHeight;
         var tsWidthPx = this.windowWidth / this.timeline.hitArea.width;
@@ -72071,13 +72339,6 @@ var Cyclotron = /** @class */ (function () {
             if (!wakeup.end_ts) {
                 return;
             }
-            var waking = _this.spanManager.getSpan(wakeup.waking_id);
-            if (!waking.overlaps(startTs, endTs)) {
-                return;
-            }
-            if (assignment[waking.laneID] === undefined) {
-                throw new Error("Missing assignment for " + waking.laneID);
-            }
             var parked = _this.spanManager.getSpan(wakeup.parked_id);
             if (!parked.overlaps(startTs, endTs)) {
                 return;
@@ -72086,16 +72347,34 @@ var Cyclotron = /** @class */ (function () {
                 throw new Error("Missing assignment for " + parked.laneID);
             }
             var x1 = (wakeup.start_ts - _this.timeline.hitArea.x) * tsWidthPx;
-            var y1 = (assignment[waking.laneID] + 0.5) * laneHeightPx;
+            var y1;
+            if (wakeup.waking_id === null) {
+                // Woken by an untraced thread: come in from the top of the parked lane.
+                y1 = assignment[parked.laneID] * laneHeightPx;
+                wakeup.label.x = x1;
+                wakeup.label.y = y1;
+                _this.arrowOverlay.addChild(wakeup.label);
+            }
+            else {
+                var waking = _this.spanManager.getSpan(wakeup.waking_id);
+                if (!waking.overlaps(startTs, endTs)) {
+                    return;
+                }
+                if (assignment[waking.laneID] === undefined) {
+                    throw new Error("Missing assignment for " + waking.laneID);
+                }
+                y1 = (assignment[waking.laneID] + 0.5) * laneHeightPx;
+            }
             var x2 = (wakeup.end_ts - _this.timeline.hitArea.x) * tsWidthPx;
             var y2 = (assignment[parked.laneID] + 0.5) * laneHeightPx;
             var cX1 = x1;
             var cY1 = 0.8 * y1 + 0.2 * y2;
             var cX2 = x1;
             var cY2 = y2;
+            var color = wakeup.channel === null ? _this.arrowColor : _this.messageColor;
             var arrow = wakeup.arrow;
             arrow.clear();
-            arrow.lineStyle(1.5, _this.arrowColor, 0.5);
+            arrow.lineStyle(1.5, color, 0.5);
             arrow.moveTo(x1, y1);
             arrow.bezierCurveTo(cX1, cY1, cX2, cY2, x2, y2);
             var arrowSize = 5;
@@ -72103,7 +72382,7 @@ var Cyclotron = /** @class */ (function () {
                 arrowSize = 0.25 * (x2 - x1);
             }
             // Draw the arrow head
-            arrow.beginFill(_this.arrowColor, 0.5);
+            arrow.beginFill(color, 0.5);
             arrow.drawPolygon([x2, y2,
                 x2 - arrowSize, y2 + arrowSize / 2,
                 x2 - arrowSize, y2 - arrowSize / 2]);
@@ -72131,7 +72410,7 @@ var Cyclotron = /** @class */ (function () {
 exports.Cyclotron = Cyclotron;
 window["cyclotron"] = new Cyclotron();
 
-},{"./model":292,"d3":37,"pixi-viewport":92,"pixi.js":209}],291:[function(require,module,exports){
+},{"./model":292,"d3":38,"pixi-viewport":94,"pixi.js":211}],291:[function(require,module,exports){
 "use strict";
 exports.__esModule = true;
 var PIXI = require("pixi.js");
@@ -72182,19 +72461,28 @@ var Lane = /** @class */ (function () {
 }());
 exports.Lane = Lane;
 
-},{"pixi.js":209}],292:[function(require,module,exports){
+},{"pixi.js":211}],292:[function(require,module,exports){
 "use strict";
 exports.__esModule = true;
 var lane_1 = require("./lane");
 var span_1 = require("./span");
 var PIXI = require("pixi.js");
 var Wakeup = /** @class */ (function () {
-    function Wakeup(id, waking_id, parked_id, start_ts) {
+    // `waking_id` is null for wakeups from untraced threads, which are identified by
+    // `waking_thread` instead.
+    function Wakeup(id, waking_id, parked_id, start_ts, waking_thread) {
+        if (waking_thread === void 0) { waking_thread = null; }
         this.id = id;
         this.waking_id = waking_id;
         this.parked_id = parked_id;
         this.start_ts = start_ts;
+        this.waking_thread = waking_thread;
+        this.channel = null;
         this.arrow = new PIXI.Graphics();
+        this.label = null;
+        if (waking_thread !== null) {
+            this.label = new PIXI.Text(waking_thread, new PIXI.TextStyle({ fontSize: 10, fill: 0xca271b }));
+        }
     }
     return Wakeup;
 }());
@@ -72222,7 +72510,10 @@ var SpanManager = /** @class */ (function () {
         // Maps from a waking Span id to the wakeup. These are removed when
         // `AsyncOnCPU` events arrive.
         this.openWakeups = {};
+        this.openMessages = {};
+        this.lockHolders = {};
         this.maxTime = 0;
+        this.wallClock = null;
         this.lanes = {};
         this.laneByIndex = [];
         this.nextLaneID = 0;
@@ -72345,7 +72636,15 @@ var SpanManager = /** @class */ (function () {
         span.close(ts);
     };
     SpanManager.prototype.addEvent = function (event) {
-        if (event.AsyncStart) {
+        if (event.Header) {
+            var header = event.Header;
+            if (this.wallClock === null) {
+                var wallClock = header.wall_clock;
+                this.wallClock = wallClock.secs + wallClock.nanos * 1e-9;
+            }
+            document.title = "Cyclotron: " + header.executable + " (pid " + header.pid + " on " + header.hostname + ")";
+        }
+        else if (event.AsyncStart) {
             this.addSpanWithParent(event.AsyncStart);
         }
         else if (event.AsyncOnCPU) {
@@ -72367,6 +72666,24 @@ var SpanManager = /** @class */ (function () {
             var ts = this.convertTs(event.AsyncOffCPU.ts);
             span.offCPU(ts);
         }
+        else if (event.Busy) {
+            // When zoomed out, the server merges a span's polls into intervals it was busy for,
+            // instead of sending each `AsyncOnCPU` and `AsyncOffCPU`, and counts the edges into
+            // the span instead of sending them.
+            var span = this.getSpan(event.Busy.id);
+            span.setSummarizedEdges(event.Busy.edges);
+            for (var _a = 0, _b = event.Busy.intervals; _a < _b.length; _a++) {
+                var _c = _b[_a], start = _c[0], end = _c[1];
+                span.onCPU(this.convertTs(start));
+                if (end !== null) {
+                    span.offCPU(this.convertTs(end));
+                }
+            }
+        }
+        else if (event.AsyncMigrate || event.Counter) {
+            // Nothing to draw for a span polled from somewhere new, or for a counter's values.
+            return;
+        }
         else if (event.AsyncEnd) {
             var span = this.getSpan(event.AsyncEnd.id);
             var ts = this.convertTs(event.AsyncEnd.ts);
@@ -72387,6 +72704,42 @@ var SpanManager = /** @class */ (function () {
             span.offCPU(ts);
             this.closeSpan(span, ts);
         }
+        else if (event.LockStart) {
+            var start = event.LockStart;
+            var phase = start.phase === "Wait" ? "wait" : "hold";
+            var span = this.addSpanWithParent({
+                name: start.name + " (" + phase + ")",
+                id: start.id,
+                parent_id: start.parent_id,
+                ts: start.ts,
+                metadata: null
+            });
+            if (start.phase === "Wait") {
+                // Waiting spans are blocked, so leave them off-CPU.
+                if (start.holder && this.spans[start.holder]) {
+                    this.lockHolders[start.id] = start.holder;
+                }
+            }
+            else {
+                span.onCPU(this.convertTs(start.ts));
+            }
+        }
+        else if (event.LockEnd) {
+            var span = this.getSpan(event.LockEnd.id);
+            var ts = this.convertTs(event.LockEnd.ts);
+            if (span.scheduled.length > 0) {
+                span.offCPU(ts);
+            }
+            this.closeSpan(span, ts);
+            // Point from the span that had the lock to the one that was waiting on it.
+            var holder = this.lockHolders[span.id];
+            if (holder) {
+                var edge = new Wakeup(this.wakeups.length, holder, span.id, ts);
+                edge.end_ts = ts;
+                this.wakeups.push(edge);
+                delete this.lockHolders[span.id];
+            }
+        }
         else if (event.ThreadStart) {
             var start = event.ThreadStart;
             if (this.threads[start.name]) {
@@ -72397,6 +72750,13 @@ var SpanManager = /** @class */ (function () {
             start.name, this);
             this.addSpan(span);
             this.threads[start.name] = new Thread(start.id);
+            // Point from the spawning span, if it's being shown too.
+            if (start.spawned_by && this.spans[start.spawned_by]) {
+                var ts = this.convertTs(start.ts);
+                var wakeup = new Wakeup(this.wakeups.length, start.spawned_by, start.id, ts);
+                wakeup.end_ts = ts;
+                this.wakeups.push(wakeup);
+            }
         }
         else if (event.ThreadEnd) {
             var span = this.getSpan(event.ThreadEnd.id);
@@ -72408,17 +72768,44 @@ var SpanManager = /** @class */ (function () {
                 return;
             }
             var wakeup = new Wakeup(this.wakeups.length, event.Wakeup.waking_span, event.Wakeup.parked_span, this.convertTs(event.Wakeup.ts));
-            if (!(event.Wakeup.parked_span in this.openWakeups)) {
-                this.openWakeups[event.Wakeup.parked_span] = [];
+            this.addWakeup(wakeup);
+        }
+        else if (event.UntracedWakeup) {
+            var wakeup = new Wakeup(this.wakeups.length, null, event.UntracedWakeup.parked_span, this.convertTs(event.UntracedWakeup.ts), event.UntracedWakeup.waking_thread);
+            this.addWakeup(wakeup);
+        }
+        else if (event.MessageSend || event.MessageRecv) {
+            var send = event.MessageSend;
+            var recv = event.MessageRecv;
+            var msg = (send || recv).msg;
+            var other = this.openMessages[msg];
+            if (!other) {
+                this.openMessages[msg] = event;
+                return;
+            }
+            delete this.openMessages[msg];
+            send = send || other.MessageSend;
+            recv = recv || other.MessageRecv;
+            if (!send || !recv || !this.spans[send.span] || !this.spans[recv.span]) {
+                return;
             }
-            this.openWakeups[event.Wakeup.parked_span].push(wakeup);
-            this.wakeups.push(wakeup);
+            var edge = new Wakeup(this.wakeups.length, send.span, recv.span, this.convertTs(send.ts));
+            edge.end_ts = this.convertTs(recv.ts);
+            edge.channel = send.channel;
+            this.wakeups.push(edge);
         }
         else {
             throw new Error("Unexpected event: " + event);
         }
         this.dirty = true;
     };
+    SpanManager.prototype.addWakeup = function (wakeup) {
+        if (!(wakeup.parked_id in this.openWakeups)) {
+            this.openWakeups[wakeup.parked_id] = [];
+        }
+        this.openWakeups[wakeup.parked_id].push(wakeup);
+        this.wakeups.push(wakeup);
+    };
     SpanManager.prototype.convertTs = function (ts) {
         if (typeof ts !== "number") {
             ts = ts.secs + ts.nanos * 1e-9;
@@ -72432,7 +72819,7 @@ var SpanManager = /** @class */ (function () {
 }());
 exports.SpanManager = SpanManager;
 
-},{"./lane":291,"./span":293,"pixi.js":209}],293:[function(require,module,exports){
+},{"./lane":291,"./span":293,"pixi.js":211}],293:[function(require,module,exports){
 "use strict";
 exports.__esModule = true;
 var PIXI = require("pixi.js");
@@ -72472,6 +72859,7 @@ var Span = /** @class */ (function () {
         this.end = null;
         this.scheduled = [];
         this.outcome = null;
+        this.summarizedEdges = 0;
         this.laneID = null;
         this.freeLanes = {};
         this.maxSubtreeLaneID = null;
@@ -72486,6 +72874,11 @@ var Span = /** @class */ (function () {
         var style = new PIXI.TextStyle({ fill: "white" });
         this.text = new PIXI.Text(this.name, style);
     }
+    // Note how many edges into us the server left out, in our label since they aren't drawn.
+    Span.prototype.setSummarizedEdges = function (edges) {
+        this.summarizedEdges = edges;
+        this.text.text = edges > 0 ? this.name + " (+" + edges + " edges)" : this.name;
+    };
     Span.prototype.toggleCollapsed = function () {
         if (this.inheritVisible) {
             console.log("Collapsing " + this.id);
@@ -72585,4 +72978,4 @@ var Span = /** @class */ (function () {
 }());
 exports.Span = Span;
 
-},{"pixi.js":209}]},{},[290,291,292,293]);
+},{"pixi.js":211}]},{},[290,291,292,293]);
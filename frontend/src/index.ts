@@ -6,6 +6,7 @@ import d3 = require("d3");
 class Axis {
     private container;
     private axis;
+    private clock;
 
     constructor(private windowWidth, private axisHeight) {
         this.container = d3.select("body")
@@ -17,10 +18,19 @@ class Axis {
             .attr("width", windowWidth)
             .attr("class", "top-axis")
             .append("g");
-
+        this.clock = this.container.append("text")
+            .attr("x", windowWidth)
+            .attr("y", axisHeight)
+            .attr("text-anchor", "end")
+            .attr("font-size", "10px");
     }
 
-    public update(startTs, endTs) {
+    public update(startTs, endTs, wallClock) {
+        // Absolute time at the left edge of the view, when the trace says when it started.
+        if (wallClock !== null) {
+            this.clock.text(new Date((wallClock + startTs) * 1000).toISOString());
+        }
+
         let axisScale = d3.scaleLinear()
             .domain([startTs, endTs])
             .range([0, this.windowWidth]);
@@ -62,6 +72,8 @@ export class Cyclotron {
     private arrowOverlay;
 
     private arrowColor;
+    private messageColor;
+    private socket;
 
     constructor() {
         this.windowWidth = window.innerWidth * 0.9;
@@ -113,6 +125,7 @@ export class Cyclotron {
         this.arrowOverlay.height = this.viewportHeight;
         this.app.stage.addChild(this.arrowOverlay);
         this.arrowColor = 0xca271b;
+        this.messageColor = 0x1b6eca;
 
         this.ticker = PIXI.ticker.shared;
         this.ticker.autoStart = true;
@@ -122,18 +135,59 @@ export class Cyclotron {
 
         this.spanManager = new SpanManager(this.timeline);
         // TODO: Print that we're waiting for data or something here.
-        var socket = new WebSocket("ws://127.0.0.1:3001", "cyclotron-ws");
-        var i = 0;
+        fetch("/config")
+            .then(response => response.json())
+            .then(config => this.connect(config))
+            .catch(error => { alert(`Couldn't load config: ${error}`); });
+    }
+
+    // Open the websocket wherever the server says it is, on the host and port we were served from.
+    private connect(config) {
+        let scheme = window.location.protocol === "https:" ? "wss:" : "ws:";
+        // Pass on which traces to show and how to filter them, e.g. `?trace=run.log&grep=request`, or
+        // `&max_events=100000` to keep a huge trace from swamping us.
+        let url = `${scheme}//${window.location.host}${config.websocket}${window.location.search}`;
+        var socket = new WebSocket(url, "cyclotron-ws");
         socket.onmessage = event => { this.addEvent(JSON.parse(event.data)); };
-        socket.onopen = event => { socket.send("cyclotron.log"); };
         socket.onerror = event => { alert(`Socket error ${event}`); };
         socket.onclose = event => { alert(`Socket closed ${event}`); };
+        this.socket = socket;
+    }
+
+    // Show only the spans named in `goals` (or everything, if it's empty) with their ancestors and
+    // descendants, hiding wakeups from spans named in `hideWakeupsFrom`.  The server resets us and
+    // resends the trace filtered that way.
+    public setFilter(goals: string[], hideWakeupsFrom: string[] = []) {
+        this.socket.send(JSON.stringify({SetFilter: {goals: goals, hide_wakeups_from: hideWakeupsFrom}}));
+    }
+
+    // Show only what happened between `start` and `end` seconds into the trace, and at most
+    // `maxEvents` events of it; zoomed out, polls are merged together to stay under that.  Without
+    // a `start`, go back to showing the whole trace, following along as it grows.
+    public setWindow(start: number = null, end: number = null, maxEvents: number = 100000) {
+        let ts = secs => secs === null ? null : {secs: Math.floor(secs), nanos: Math.floor((secs % 1) * 1e9)};
+        let window = start === null ? null : {start: ts(start), end: ts(end), max_events: maxEvents};
+        this.socket.send(JSON.stringify({SetWindow: window}));
     }
 
     private addEvent(event) {
+        if (event.Reset !== undefined) {
+            this.reset();
+            return;
+        }
         this.spanManager.addEvent(event);
     }
 
+    // Throw away everything drawn so far, when the server is about to resend the trace from the
+    // start (e.g. because the file was truncated).
+    private reset() {
+        this.timeline.removeChildren();
+        this.textOverlay.removeChildren();
+        this.arrowOverlay.removeChildren();
+        this.spanManager = new SpanManager(this.timeline);
+        this.lastViewport = {width: 0, height: 0, ts: 0};
+    }
+
     private viewportDirty() {
         let viewArea = this.timeline.hitArea;
         return this.lastViewport.width !== viewArea.width
@@ -194,7 +248,7 @@ export class Cyclotron {
     private drawViewport(assignment) {
         let startTs = this.timeline.hitArea.x;
         let endTs = startTs + this.timeline.hitArea.width;
-        this.axis.update(startTs, endTs);
+        this.axis.update(startTs, endTs, this.spanManager.wallClock);
 
         let maxHeight = Object.keys(assignment).length;
         let laneHeightPx = this.viewportHeight / maxHeight;
@@ -274,14 +328,6 @@ export class Cyclotron {
             if (!wakeup.end_ts) {
                 return;
             }
-            let waking = this.spanManager.getSpan(wakeup.waking_id);
-            if (!waking.overlaps(startTs, endTs)) {
-                return;
-            }
-            if (assignment[waking.laneID] === undefined) {
-                throw new Error(`Missing assignment for ${waking.laneID}`);
-            }
-
             let parked = this.spanManager.getSpan(wakeup.parked_id);
             if (!parked.overlaps(startTs, endTs)) {
                 return;
@@ -291,7 +337,23 @@ export class Cyclotron {
             }
 
             let x1 = (wakeup.start_ts - this.timeline.hitArea.x) * tsWidthPx;
-            let y1 = (assignment[waking.laneID] + 0.5) * laneHeightPx;
+            let y1;
+            if (wakeup.waking_id === null) {
+                // Woken by an untraced thread: come in from the top of the parked lane.
+                y1 = assignment[parked.laneID] * laneHeightPx;
+                wakeup.label.x = x1;
+                wakeup.label.y = y1;
+                this.arrowOverlay.addChild(wakeup.label);
+            } else {
+                let waking = this.spanManager.getSpan(wakeup.waking_id);
+                if (!waking.overlaps(startTs, endTs)) {
+                    return;
+                }
+                if (assignment[waking.laneID] === undefined) {
+                    throw new Error(`Missing assignment for ${waking.laneID}`);
+                }
+                y1 = (assignment[waking.laneID] + 0.5) * laneHeightPx;
+            }
             let x2 = (wakeup.end_ts - this.timeline.hitArea.x) * tsWidthPx;
             let y2 = (assignment[parked.laneID] + 0.5) * laneHeightPx;
 
@@ -300,9 +362,10 @@ export class Cyclotron {
             let cX2 = x1;
             let cY2 = y2;
 
+            let color = wakeup.channel === null ? this.arrowColor : this.messageColor;
             let arrow = wakeup.arrow;
             arrow.clear();
-            arrow.lineStyle(1.5, this.arrowColor, 0.5);
+            arrow.lineStyle(1.5, color, 0.5);
             arrow.moveTo(x1, y1);
             arrow.bezierCurveTo(cX1, cY1, cX2, cY2, x2, y2);
 
@@ -311,7 +374,7 @@ export class Cyclotron {
                 arrowSize = 0.25 * (x2 - x1);
             }
             // Draw the arrow head
-            arrow.beginFill(this.arrowColor, 0.5);
+            arrow.beginFill(color, 0.5);
             arrow.drawPolygon([x2, y2,
                                x2-arrowSize, y2+arrowSize/2,
                                x2-arrowSize, y2-arrowSize/2]);
@@ -5,9 +5,19 @@ import PIXI = require("pixi.js");
 class Wakeup {
     public end_ts;
     public arrow;
-
-    constructor(public id, public waking_id, public parked_id, public start_ts) {
+    public label;
+    // Set for edges drawn from a message's send to its receive, rather than for real wakeups.
+    public channel;
+
+    // `waking_id` is null for wakeups from untraced threads, which are identified by
+    // `waking_thread` instead.
+    constructor(public id, public waking_id, public parked_id, public start_ts, public waking_thread = null) {
+        this.channel = null;
         this.arrow = new PIXI.Graphics();
+        this.label = null;
+        if (waking_thread !== null) {
+            this.label = new PIXI.Text(waking_thread, new PIXI.TextStyle({fontSize: 10, fill: 0xca271b}));
+        }
     }
 }
 
@@ -36,8 +46,15 @@ export class SpanManager {
     public threads;
     public maxTime;
     public wakeups;
+    // Seconds since the Unix epoch at which the trace's timestamps start, if the trace has a
+    // header.
+    public wallClock;
 
     private openWakeups;
+    // Maps from a message id to whichever end of it we've seen first.
+    private openMessages;
+    // Maps from a lock wait's span id to the hold span it was waiting on.
+    private lockHolders;
 
     private lanes;
     private laneByIndex;
@@ -51,7 +68,10 @@ export class SpanManager {
         // Maps from a waking Span id to the wakeup. These are removed when
         // `AsyncOnCPU` events arrive.
         this.openWakeups = {};
+        this.openMessages = {};
+        this.lockHolders = {};
         this.maxTime = 0;
+        this.wallClock = null;
 
         this.lanes = {};
         this.laneByIndex = [];
@@ -205,7 +225,14 @@ export class SpanManager {
     }
 
     public addEvent(event) {
-        if (event.AsyncStart) {
+        if (event.Header) {
+            let header = event.Header;
+            if (this.wallClock === null) {
+                let wallClock = header.wall_clock;
+                this.wallClock = wallClock.secs + wallClock.nanos * 1e-9;
+            }
+            document.title = `Cyclotron: ${header.executable} (pid ${header.pid} on ${header.hostname})`;
+        } else if (event.AsyncStart) {
             this.addSpanWithParent(event.AsyncStart);
         } else if (event.AsyncOnCPU) {
             let span = this.getSpan(event.AsyncOnCPU.id);
@@ -223,6 +250,21 @@ export class SpanManager {
             let span = this.getSpan(event.AsyncOffCPU.id);
             let ts = this.convertTs(event.AsyncOffCPU.ts);
             span.offCPU(ts);
+        } else if (event.Busy) {
+            // When zoomed out, the server merges a span's polls into intervals it was busy for,
+            // instead of sending each `AsyncOnCPU` and `AsyncOffCPU`, and counts the edges into
+            // the span instead of sending them.
+            let span = this.getSpan(event.Busy.id);
+            span.setSummarizedEdges(event.Busy.edges);
+            for (let [start, end] of event.Busy.intervals) {
+                span.onCPU(this.convertTs(start));
+                if (end !== null) {
+                    span.offCPU(this.convertTs(end));
+                }
+            }
+        } else if (event.AsyncMigrate || event.Counter) {
+            // Nothing to draw for a span polled from somewhere new, or for a counter's values.
+            return;
         } else if (event.AsyncEnd) {
             let span = this.getSpan(event.AsyncEnd.id);
             let ts = this.convertTs(event.AsyncEnd.ts);
@@ -240,6 +282,39 @@ export class SpanManager {
             let ts = this.convertTs(event.SyncEnd.ts)
             span.offCPU(ts);
             this.closeSpan(span, ts);
+        } else if (event.LockStart) {
+            let start = event.LockStart;
+            let phase = start.phase === "Wait" ? "wait" : "hold";
+            let span = this.addSpanWithParent({
+                name: `${start.name} (${phase})`,
+                id: start.id,
+                parent_id: start.parent_id,
+                ts: start.ts,
+                metadata: null,
+            });
+            if (start.phase === "Wait") {
+                // Waiting spans are blocked, so leave them off-CPU.
+                if (start.holder && this.spans[start.holder]) {
+                    this.lockHolders[start.id] = start.holder;
+                }
+            } else {
+                span.onCPU(this.convertTs(start.ts));
+            }
+        } else if (event.LockEnd) {
+            let span = this.getSpan(event.LockEnd.id);
+            let ts = this.convertTs(event.LockEnd.ts);
+            if (span.scheduled.length > 0) {
+                span.offCPU(ts);
+            }
+            this.closeSpan(span, ts);
+            // Point from the span that had the lock to the one that was waiting on it.
+            let holder = this.lockHolders[span.id];
+            if (holder) {
+                let edge = new Wakeup(this.wakeups.length, holder, span.id, ts);
+                edge.end_ts = ts;
+                this.wakeups.push(edge);
+                delete this.lockHolders[span.id];
+            }
         } else if (event.ThreadStart) {
             let start = event.ThreadStart;
             if (this.threads[start.name]) {
@@ -256,6 +331,13 @@ export class SpanManager {
             );
             this.addSpan(span);
             this.threads[start.name] = new Thread(start.id);
+            // Point from the spawning span, if it's being shown too.
+            if (start.spawned_by && this.spans[start.spawned_by]) {
+                let ts = this.convertTs(start.ts);
+                let wakeup = new Wakeup(this.wakeups.length, start.spawned_by, start.id, ts);
+                wakeup.end_ts = ts;
+                this.wakeups.push(wakeup);
+            }
         } else if (event.ThreadEnd) {
             let span = this.getSpan(event.ThreadEnd.id);
             this.closeSpan(span, this.convertTs(event.ThreadEnd.ts));
@@ -270,17 +352,49 @@ export class SpanManager {
                 event.Wakeup.parked_span,
                 this.convertTs(event.Wakeup.ts),
             );
-            if (!(event.Wakeup.parked_span in this.openWakeups)) {
-                this.openWakeups[event.Wakeup.parked_span] = [];
+            this.addWakeup(wakeup);
+        } else if (event.UntracedWakeup) {
+            let wakeup = new Wakeup(
+                this.wakeups.length,
+                null,
+                event.UntracedWakeup.parked_span,
+                this.convertTs(event.UntracedWakeup.ts),
+                event.UntracedWakeup.waking_thread,
+            );
+            this.addWakeup(wakeup);
+        } else if (event.MessageSend || event.MessageRecv) {
+            let send = event.MessageSend;
+            let recv = event.MessageRecv;
+            let msg = (send || recv).msg;
+            let other = this.openMessages[msg];
+            if (!other) {
+                this.openMessages[msg] = event;
+                return;
             }
-            this.openWakeups[event.Wakeup.parked_span].push(wakeup);
-            this.wakeups.push(wakeup);
+            delete this.openMessages[msg];
+            send = send || other.MessageSend;
+            recv = recv || other.MessageRecv;
+            if (!send || !recv || !this.spans[send.span] || !this.spans[recv.span]) {
+                return;
+            }
+            let edge = new Wakeup(this.wakeups.length, send.span, recv.span, this.convertTs(send.ts));
+            edge.end_ts = this.convertTs(recv.ts);
+            edge.channel = send.channel;
+            this.wakeups.push(edge);
         } else {
             throw new Error("Unexpected event: " + event);
         }
         this.dirty = true;
     }
 
+    private addWakeup(wakeup) {
+        if (!(wakeup.parked_id in this.openWakeups)) {
+            this.openWakeups[wakeup.parked_id] = [];
+        }
+        this.openWakeups[wakeup.parked_id].push(wakeup);
+        this.wakeups.push(wakeup);
+    }
+
     private convertTs(ts) {
         if (typeof ts !== "number") {
             ts = ts.secs + ts.nanos * 1e-9;
@@ -26,6 +26,9 @@ export class Span {
     public end;
     public scheduled;
     public outcome;
+    // Wakeups and messages into this span that the server counted instead of sending, when
+    // zoomed out.
+    public summarizedEdges: number;
 
     public laneID;
     public freeLanes;
@@ -50,6 +53,7 @@ export class Span {
         this.end = null;
         this.scheduled = [];
         this.outcome = null;
+        this.summarizedEdges = 0;
 
         this.laneID = null;
         this.freeLanes = {};
@@ -70,6 +74,12 @@ export class Span {
         this.text = new PIXI.Text(this.name, style);
     }
 
+    // Note how many edges into us the server left out, in our label since they aren't drawn.
+    public setSummarizedEdges(edges: number) {
+        this.summarizedEdges = edges;
+        this.text.text = edges > 0 ? `${this.name} (+${edges} edges)` : this.name;
+    }
+
     public toggleCollapsed() {
         if (this.inheritVisible) {
             console.log(`Collapsing ${this.id}`);
@@ -0,0 +1,55 @@
+// Compiles the built frontend into the server, so it can be installed and run from anywhere.  Set
+// `CYCLOTRON_FRONTEND` to embed a frontend from somewhere other than `../frontend`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The path each asset is served at, and where it is in the frontend directory.
+const ASSETS: &[(&str, &str)] = &[
+    ("/src/index.html", "src/index.html"),
+    ("/bundle.js", "bundle.js"),
+];
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=CYCLOTRON_FRONTEND");
+    let dir = match env::var_os("CYCLOTRON_FRONTEND") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("../frontend"),
+    };
+
+    let mut out = String::from("pub static ASSETS: &[(&str, &[u8])] = &[\n");
+    for &(path, file) in ASSETS {
+        let file = dir.join(file);
+        println!("cargo:rerun-if-changed={}", file.display());
+        match fs::canonicalize(&file) {
+            Ok(file) => out.push_str(&format!("    ({:?}, include_bytes!({:?})),\n", path, file)),
+            Err(e) => panic!("can't embed {}: {} (set CYCLOTRON_FRONTEND?)", file.display(), e),
+        }
+    }
+    out.push_str("];\n");
+    check_bundle(&dir);
+
+    let dest = Path::new(&env::var_os("OUT_DIR").unwrap()).join("assets.rs");
+    fs::write(dest, out).unwrap();
+}
+
+// Warn if `bundle.js` is older than the sources it's built from, so we don't embed a stale one.
+fn check_bundle(dir: &Path) {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let bundle = match modified(&dir.join("bundle.js")) {
+        Some(bundle) => bundle,
+        None => return,
+    };
+    let src = dir.join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+    let entries = match fs::read_dir(&src) {
+        Ok(entries) => entries,
+        Err(e) => return println!("cargo:warning=can't check {} is up to date: {}", src.display(), e),
+    };
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.extension().is_some_and(|ext| ext == "ts") && modified(&path).is_some_and(|t| t > bundle) {
+            println!("cargo:warning=bundle.js is older than {}; rebuild it with build.sh", path.display());
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Read-only JSON API for querying a trace over HTTP:
+//!
+//! - `GET /api/roots`: threads, and spans whose parents we never saw
+//! - `GET /api/spans?name=<pattern>`: spans whose names contain `pattern`
+//! - `GET /api/spans/<id>`: one span, with its metadata and events
+//! - `GET /api/spans/<id>/children`
+//! - `GET /api/spans/<id>/ancestors`: thread first, parent last
+//! - `GET /api/allocations?limit=<n>`: the span names that allocated the most bytes, summed over
+//!   every span with that name, heaviest first (10 unless `limit` says otherwise)
+//!
+//! Every endpoint takes `?trace=<name>` to pick a trace, like the websocket does.  Spans are
+//! listed in the order they started.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+use hyper::StatusCode;
+use serde::Serialize;
+use serde_json::{self, Value};
+
+use event::{EventTree, SpanId};
+use follow::{Change, Follower};
+use traces::query_values;
+
+// What a trace file looked like when we loaded it, to tell whether it's changed since.
+type FileKey = (PathBuf, u64, Option<SystemTime>);
+
+/// The tree for the last trace queried, so a script making lots of queries doesn't reload it
+/// each time.
+#[derive(Clone)]
+pub struct Cache {
+    key: Vec<FileKey>,
+    tree: Arc<EventTree>,
+}
+
+/// Load `paths` into a tree, or reuse the cached one if they haven't changed.
+pub fn load(cache: &mut Option<Cache>, paths: &[PathBuf]) -> io::Result<Arc<EventTree>> {
+    let key = paths.iter()
+        .map(|path| {
+            let metadata = fs::metadata(path)?;
+            Ok((path.clone(), metadata.len(), metadata.modified().ok()))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    if let Some(ref cache) = *cache {
+        if cache.key == key {
+            return Ok(cache.tree.clone());
+        }
+    }
+
+    let mut tree = EventTree::new_hide_wakeups(vec![], vec![]);
+    for path in paths {
+        if paths.len() > 1 {
+            tree.set_source(Some(::trace_label(path)));
+        }
+        let lines = match Follower::open(path)?.poll()? {
+            Change::Lines(lines) => lines,
+            Change::Truncated => vec![],
+        };
+        for line in lines {
+            if let Err((e, buf)) = tree.add(line) {
+                println!("warning: couldn't process event '{}': {:?}", buf, e);
+            }
+        }
+    }
+    tree.stitch();
+    let tree = Arc::new(tree);
+    *cache = Some(Cache { key, tree: tree.clone() });
+    Ok(tree)
+}
+
+/// Answer a query for `path` (everything after `/api`) with a status and a JSON body.
+pub fn handle(tree: &EventTree, path: &str, query: &str) -> (StatusCode, String) {
+    let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match parts[..] {
+        ["roots"] => ok(&tree.root_spans()),
+        ["spans"] => {
+            let uri = format!("?{}", query);
+            match query_values(&uri, "name").into_iter().next() {
+                Some(pattern) => ok(&tree.find_spans(&pattern)),
+                None => error(StatusCode::BadRequest, "missing ?name=<pattern>".to_string()),
+            }
+        },
+        ["spans", id] => with_span(id, |id| {
+            let info = tree.span_info(id)?;
+            let events: Vec<Value> = tree.span_events(id)?.into_iter()
+                .filter_map(|buf| serde_json::from_str(buf).ok())
+                .collect();
+            // Start events are `{"SyncStart": {...}}` and so on; their metadata is inside.
+            let metadata = events.first()
+                .and_then(|start| start.as_object())
+                .and_then(|start| start.values().next())
+                .and_then(|start| start.get("metadata"))
+                .cloned()
+                .unwrap_or(Value::Null);
+            Some(json!({ "span": info, "metadata": metadata, "events": events }))
+        }),
+        ["spans", id, "children"] => with_span(id, |id| tree.children(id)),
+        ["spans", id, "ancestors"] => with_span(id, |id| tree.ancestors(id)),
+        ["allocations"] => {
+            let uri = format!("?{}", query);
+            let limit = match query_values(&uri, "limit").into_iter().next() {
+                Some(limit) => match limit.parse() {
+                    Ok(limit) => limit,
+                    Err(..) => return error(StatusCode::BadRequest, format!("bad limit {:?}", limit)),
+                },
+                None => 10,
+            };
+            let top: Vec<Value> = tree.top_allocations(limit).into_iter()
+                .map(|(name, alloc)| json!({ "name": name, "count": alloc.count, "bytes": alloc.bytes }))
+                .collect();
+            ok(&top)
+        },
+        _ => error(StatusCode::NotFound, format!("no such endpoint: /api{}", path)),
+    }
+}
+
+fn with_span<T: Serialize, F: FnOnce(SpanId) -> Option<T>>(id: &str, f: F) -> (StatusCode, String) {
+    match id.parse() {
+        Ok(id) => match f(SpanId(id)) {
+            Some(result) => ok(&result),
+            None => error(StatusCode::NotFound, format!("no span with id {}", id)),
+        },
+        Err(..) => error(StatusCode::BadRequest, format!("bad span id {:?}", id)),
+    }
+}
+
+fn ok<T: Serialize>(result: &T) -> (StatusCode, String) {
+    match serde_json::to_string(result) {
+        Ok(body) => (StatusCode::Ok, body),
+        Err(e) => error(StatusCode::InternalServerError, e.to_string()),
+    }
+}
+
+pub fn error(status: StatusCode, message: String) -> (StatusCode, String) {
+    (status, json!({ "error": message }).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::StatusCode;
+    use serde_json::{self, Value};
+    use event::EventTree;
+    use super::handle;
+
+    fn tree() -> EventTree {
+        let mut tree = EventTree::new(vec![]);
+        let events = [
+            r#"{"ThreadStart":{"name":"main","id":1,"ts":{"secs":0,"nanos":0}}}"#,
+            r#"{"SyncStart":{"name":"request","id":2,"parent_id":1,"metadata":{"path":"/"},"ts":{"secs":1,"nanos":0}}}"#,
+            r#"{"SyncStart":{"name":"parse request","id":3,"parent_id":2,"metadata":null,"ts":{"secs":2,"nanos":0}}}"#,
+            r#"{"SyncEnd":{"id":3,"ts":{"secs":3,"nanos":0},"alloc":{"count":2,"bytes":64}}}"#,
+        ];
+        for event in &events {
+            tree.add(event.to_string()).expect("add event");
+        }
+        tree
+    }
+
+    fn get(tree: &EventTree, path: &str, query: &str) -> (StatusCode, Value) {
+        let (status, body) = handle(tree, path, query);
+        (status, serde_json::from_str(&body).expect("response isn't JSON"))
+    }
+
+    #[test]
+    fn test_api_queries() {
+        let tree = tree();
+        let (status, roots) = get(&tree, "/roots", "");
+        assert_eq!(status, StatusCode::Ok);
+        assert_eq!(roots[0]["name"], "main");
+        assert_eq!(roots[0]["children"], 1);
+
+        let (_, found) = get(&tree, "/spans", "name=request");
+        assert_eq!(found.as_array().unwrap().len(), 2);
+
+        let (_, span) = get(&tree, "/spans/2", "");
+        assert_eq!(span["metadata"]["path"], "/");
+        assert_eq!(span["span"]["end"], Value::Null);
+        let (_, span) = get(&tree, "/spans/3", "");
+        assert_eq!(span["span"]["end"]["secs"], 3);
+        assert_eq!(span["events"].as_array().unwrap().len(), 2);
+
+        let (_, children) = get(&tree, "/spans/2/children", "");
+        assert_eq!(children[0]["id"], 3);
+        let (_, ancestors) = get(&tree, "/spans/3/ancestors", "");
+        assert_eq!(ancestors[0]["name"], "main");
+        assert_eq!(ancestors[1]["name"], "request");
+
+        let (_, top) = get(&tree, "/allocations", "");
+        assert_eq!(top, json!([{ "name": "parse request", "count": 2, "bytes": 64 }]));
+        assert_eq!(get(&tree, "/allocations", "limit=0").1, json!([]));
+    }
+
+    #[test]
+    fn test_api_errors() {
+        let tree = tree();
+        assert_eq!(get(&tree, "/spans/99", "").0, StatusCode::NotFound);
+        assert_eq!(get(&tree, "/spans/abc", "").0, StatusCode::BadRequest);
+        assert_eq!(get(&tree, "/spans", "").0, StatusCode::BadRequest);
+        assert_eq!(get(&tree, "/allocations", "limit=lots").0, StatusCode::BadRequest);
+        assert_eq!(get(&tree, "/nope", "").0, StatusCode::NotFound);
+    }
+}
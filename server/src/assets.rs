@@ -0,0 +1,185 @@
+//! The frontend, compiled into the server by `build.rs` or served from a directory with
+//! `--assets`.
+
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use hyper::StatusCode;
+use hyper::header::{CacheControl, CacheDirective, ContentType, ETag, EntityTag, IfNoneMatch};
+use hyper::mime::{self, Mime};
+use hyper::server::Response;
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+// The embedded asset served at `path`, if there is one.
+fn get(path: &str) -> Option<&'static [u8]> {
+    ASSETS.iter()
+        .find(|&&(asset, _)| asset == path)
+        .map(|&(_, bytes)| bytes)
+}
+
+struct Asset {
+    bytes: Cow<'static, [u8]>,
+    etag: EntityTag,
+}
+
+// Find `path` in `dir`, or among the embedded assets if there's no `dir`.  Anything that
+// resolves to outside `dir`, whether through `..` or a symlink, isn't found.
+fn find(dir: Option<&Path>, path: &str) -> io::Result<Option<Asset>> {
+    let dir = match dir {
+        Some(dir) => dir,
+        None => return Ok(get(path).map(|bytes| {
+            let mut hasher = DefaultHasher::new();
+            bytes.hash(&mut hasher);
+            Asset { bytes: Cow::Borrowed(bytes), etag: EntityTag::new(false, format!("{:x}", hasher.finish())) }
+        })),
+    };
+    let root = dir.canonicalize()?;
+    let file = match root.join(path.trim_start_matches('/')).canonicalize() {
+        Ok(file) => file,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if !file.starts_with(&root) || !file.is_file() {
+        return Ok(None);
+    }
+    let metadata = fs::metadata(&file)?;
+    let modified = metadata.modified().ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+    Ok(Some(Asset {
+        bytes: Cow::Owned(fs::read(&file)?),
+        etag: EntityTag::new(false, format!("{:x}-{:x}", metadata.len(), modified)),
+    }))
+}
+
+fn content_type(path: &str) -> Mime {
+    let extension = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension {
+        "html" | "htm" => mime::TEXT_HTML_UTF_8,
+        "js" => mime::APPLICATION_JAVASCRIPT_UTF_8,
+        "css" => mime::TEXT_CSS_UTF_8,
+        "json" | "map" => mime::APPLICATION_JSON,
+        "svg" => mime::IMAGE_SVG,
+        "png" => mime::IMAGE_PNG,
+        "txt" => mime::TEXT_PLAIN_UTF_8,
+        _ => mime::APPLICATION_OCTET_STREAM,
+    }
+}
+
+/// Respond to a GET for `path`, from `dir` or from the embedded assets.  Browsers check back
+/// each time, but get a bodiless 304 if what they have (per `if_none_match`) is still current.
+pub fn serve(dir: Option<&Path>, path: &str, if_none_match: Option<&IfNoneMatch>) -> Response {
+    let mut response = Response::new();
+    let asset = match find(dir, path) {
+        Ok(Some(asset)) => asset,
+        Ok(None) => {
+            response.set_status(StatusCode::NotFound);
+            return response;
+        },
+        Err(e) => {
+            println!("Failed to read {:?}: {:?}", path, e);
+            response.set_status(StatusCode::InternalServerError);
+            return response;
+        },
+    };
+    response.headers_mut().set(ETag(asset.etag.clone()));
+    response.headers_mut().set(CacheControl(vec![CacheDirective::NoCache]));
+    let fresh = match if_none_match {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&asset.etag)),
+        None => false,
+    };
+    if fresh {
+        response.set_status(StatusCode::NotModified);
+        return response;
+    }
+    response.headers_mut().set(ContentType(content_type(path)));
+    response.set_body(asset.bytes.into_owned());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use futures::{Future, Stream};
+    use hyper::StatusCode;
+    use hyper::header::{ContentType, ETag, IfNoneMatch};
+    use hyper::mime;
+    use hyper::server::Response;
+    use super::serve;
+
+    fn body(response: Response) -> Vec<u8> {
+        response.body().concat2().wait().unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_serve_dir() {
+        let base = env::temp_dir().join("cyclotron_test_assets");
+        let _ = fs::remove_dir_all(&base);
+        let dir = base.join("frontend");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/index.html"), "<html></html>").unwrap();
+        fs::write(dir.join("bundle.js"), "let x = 1;").unwrap();
+        fs::write(base.join("secret.txt"), "hunter2").unwrap();
+
+        let response = serve(Some(&dir), "/src/index.html", None);
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(response.headers().get::<ContentType>(), Some(&ContentType(mime::TEXT_HTML_UTF_8)));
+        let js = serve(Some(&dir), "/bundle.js", None);
+        assert_eq!(js.headers().get::<ContentType>(), Some(&ContentType(mime::APPLICATION_JAVASCRIPT_UTF_8)));
+        assert_eq!(body(js), b"let x = 1;");
+
+        // Nothing outside the directory, however it's asked for.
+        assert_eq!(serve(Some(&dir), "/../secret.txt", None).status(), StatusCode::NotFound);
+        assert_eq!(serve(Some(&dir), "/src/../../secret.txt", None).status(), StatusCode::NotFound);
+        assert_eq!(serve(Some(&dir), "/missing.js", None).status(), StatusCode::NotFound);
+        assert_eq!(serve(Some(&dir), "/src", None).status(), StatusCode::NotFound);
+        #[cfg(unix)]
+        {
+            ::std::os::unix::fs::symlink(base.join("secret.txt"), dir.join("link.txt")).unwrap();
+            assert_eq!(serve(Some(&dir), "/link.txt", None).status(), StatusCode::NotFound);
+        }
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_serve_not_modified() {
+        let dir = env::temp_dir().join("cyclotron_test_assets_etag");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("style.css"), "body {}").unwrap();
+
+        let response = serve(Some(&dir), "/style.css", None);
+        let etag = response.headers().get::<ETag>().expect("no ETag").0.clone();
+        let response = serve(Some(&dir), "/style.css", Some(&IfNoneMatch::Items(vec![etag.clone()])));
+        assert_eq!(response.status(), StatusCode::NotModified);
+        assert!(body(response).is_empty());
+
+        // Changed since, so it's sent again.
+        fs::write(dir.join("style.css"), "body { margin: 0 }").unwrap();
+        let response = serve(Some(&dir), "/style.css", Some(&IfNoneMatch::Items(vec![etag])));
+        assert_eq!(response.status(), StatusCode::Ok);
+        assert_eq!(body(response), b"body { margin: 0 }");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_serve_embedded() {
+        assert!(super::ASSETS.iter().any(|&(path, _)| path == "/src/index.html"));
+        for &(path, bytes) in super::ASSETS {
+            let response = serve(None, path, None);
+            assert_eq!(response.status(), StatusCode::Ok);
+            let etag = response.headers().get::<ETag>().expect("no ETag").0.clone();
+            assert_eq!(body(response), bytes);
+            let response = serve(None, path, Some(&IfNoneMatch::Items(vec![etag])));
+            assert_eq!(response.status(), StatusCode::NotModified);
+        }
+        assert_eq!(serve(None, "/../Cargo.toml", None).status(), StatusCode::NotFound);
+    }
+}
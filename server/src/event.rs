@@ -7,9 +7,42 @@ use std::collections::{
 
 // Copied from dropbox/cyclotron/src/event.rs
 
+/// Newest trace format we understand.  Traces without a `Header` predate versioning and are
+/// read as-is.  Version 2 added interned names.
+pub const SCHEMA_VERSION: u32 = 2;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
 pub struct SpanId(pub u64);
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct MessageId(pub u64);
+
+/// A span, channel or counter name, either spelled out or as the id from an earlier `NameDef`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Name {
+    Ref(u32),
+    Str(String),
+}
+
+impl Name {
+    fn into_string(self) -> String {
+        match self {
+            Name::Ref(id) => format!("#{}", id),
+            Name::Str(name) => name,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct ProcessId(pub u64);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct SpanContext {
+    pub process_id: ProcessId,
+    pub span_id: SpanId,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum AsyncOutcome {
     Success,
@@ -17,22 +50,60 @@ pub enum AsyncOutcome {
     Error(String),
 }
 
+/// Heap allocations made by a span, if the traced process counts them.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum LockPhase {
+    Wait,
+    Hold,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum TraceEvent {
+    /// Defines `id` as shorthand for `name` in the rest of the trace.  Names in the events we send
+    /// on are always spelled out, so these aren't passed along.
+    NameDef { id: u32, name: String },
+
+    /// Written once at the start of a trace.  `wall_clock` is the time since the Unix epoch that
+    /// corresponds to a `ts` of zero, so relative timestamps can be shown as absolute times.
+    Header {
+        schema_version: u32,
+        wall_clock: Duration,
+        clock: String,
+        process_id: ProcessId,
+        pid: u32,
+        hostname: Option<String>,
+        executable: Option<String>,
+        #[serde(default)]
+        command_line: Vec<String>,
+    },
+
     /// Logged the first time a future is polled after a logger is installed.  If this is the first
     /// time the future is *ever* polled, `is_restart` will be false.
     AsyncStart {
-        name: String,
+        name: Name,
         id: SpanId,
         parent_id: SpanId,
         ts: Duration,
         metadata: serde_json::Value,
+        #[serde(default)]
         is_restart: bool,
     },
     /// Logged immediately before each time the future is polled
     AsyncOnCPU { id: SpanId, ts: Duration },
-    /// Logged immediately after each time the future is polled
-    AsyncOffCPU { id: SpanId, ts: Duration },
+    /// Logged immediately after each time the future is polled, with the allocations made while
+    /// it was on CPU, not counting spans nested in it, if the process is counting them.
+    AsyncOffCPU {
+        id: SpanId,
+        ts: Duration,
+        #[serde(default)]
+        alloc: Option<AllocStats>,
+    },
     /// Logged when the future is completed. Returning `Ok(Async::Ready(..))` will set
     /// `AsyncOutcome::Success`, `Err(e)` will set `AsyncOutcome::Error`, and dropping the future
     /// will set `AsyncOutcome::Cancelled`.
@@ -41,28 +112,46 @@ pub enum TraceEvent {
         ts: Duration,
         outcome: AsyncOutcome,
     },
+    /// Logged before a poll when the future is polled from a different span or thread than last
+    /// time, e.g. by a work-stealing executor.  Its parent stays the span that first polled it.
+    AsyncMigrate {
+        id: SpanId,
+        poller: Option<SpanId>,
+        thread: String,
+        ts: Duration,
+    },
 
     /// Logged when a sync span is entered.  Note that since we don't repeatedly
     /// poll synchronous spans, we don't make an attempt to restart them when
     /// the logger changes.
     SyncStart {
-        name: String,
+        name: Name,
         id: SpanId,
         parent_id: SpanId,
         ts: Duration,
         metadata: serde_json::Value,
     },
     /// Logged when a sync span is exited and the current generation matches the
-    /// one at the span's start.
-    SyncEnd { id: SpanId, ts: Duration },
+    /// one at the span's start.  `alloc` is the allocations made during the span, not counting
+    /// spans nested in it, if counted.
+    SyncEnd {
+        id: SpanId,
+        ts: Duration,
+        #[serde(default)]
+        alloc: Option<AllocStats>,
+    },
 
     /// Logged when a logger is installed on a thread.  If this corresponds with thread creation,
-    /// `is_restart` will be set to false.
+    /// `is_restart` will be set to false.  `spawned_by` is the span that spawned the thread, if it
+    /// was spawned through cyclotron.
     ThreadStart {
-        name: String,
+        name: Name,
         id: SpanId,
         ts: Duration,
+        #[serde(default)]
         is_restart: bool,
+        #[serde(default)]
+        spawned_by: Option<SpanId>,
     },
     /// Logged when a thread is dropped.
     ThreadEnd { id: SpanId, ts: Duration },
@@ -74,51 +163,223 @@ pub enum TraceEvent {
         parked_span: SpanId,
         ts: Duration,
     },
+
+    /// Logged when a wakeup originates from a thread without a current span, such as an I/O
+    /// reactor or timer thread.  `waking_thread` is that thread's name, or its id if unnamed.
+    UntracedWakeup {
+        waking_thread: String,
+        parked_span: SpanId,
+        ts: Duration,
+    },
+
+    /// Logged right after a span's start when it was created on behalf of a span in another
+    /// process, e.g. one that sent us a request carrying its serialized context.
+    RemoteParent {
+        id: SpanId,
+        parent: SpanContext,
+        ts: Duration,
+    },
+
+    /// Logged when a message is put on a traced channel.  `span` is the sending span, if any.
+    MessageSend {
+        msg: MessageId,
+        channel: Name,
+        span: Option<SpanId>,
+        ts: Duration,
+    },
+    /// Logged when a message is taken off a traced channel, with the same `msg` as its send.
+    MessageRecv {
+        msg: MessageId,
+        channel: Name,
+        span: Option<SpanId>,
+        ts: Duration,
+    },
+
+    /// Logged when a span starts waiting for a traced lock (`LockPhase::Wait`) or acquires it
+    /// (`LockPhase::Hold`).  Lock spans are children of the span using the lock, but never become
+    /// the current span themselves.  For waits, `holder` is the hold span that had the lock when
+    /// the wait started.
+    LockStart {
+        name: Name,
+        id: SpanId,
+        parent_id: SpanId,
+        phase: LockPhase,
+        holder: Option<SpanId>,
+        ts: Duration,
+    },
+    /// Logged when a wait ends with the lock acquired, or when the lock is released.
+    LockEnd { id: SpanId, ts: Duration },
+
+    /// Logged whenever a named quantity changes, e.g. the number of messages queued on a channel.
+    Counter {
+        name: Name,
+        value: u64,
+        ts: Duration,
+    },
 }
 
-#[derive(Clone, Eq, Hash)]
+impl TraceEvent {
+    fn name_mut(&mut self) -> Option<&mut Name> {
+        match *self {
+            TraceEvent::AsyncStart { ref mut name, .. }
+            | TraceEvent::SyncStart { ref mut name, .. }
+            | TraceEvent::ThreadStart { ref mut name, .. }
+            | TraceEvent::LockStart { ref mut name, .. }
+            | TraceEvent::Counter { ref mut name, .. }
+            | TraceEvent::MessageSend { channel: ref mut name, .. }
+            | TraceEvent::MessageRecv { channel: ref mut name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash)]
 struct EventResult {
     buf: String, // buffer before json conversion; list includes e.g. both AsyncStart and AsyncEnd
     ts: Duration, // the ts from self.event, extracted for convenient sorting
+    cpu: Cpu,
 }
 
-// Allow sorting by timestamp.
-impl Ord for EventResult {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.ts.cmp(&other.ts) }
-}
-impl PartialOrd for EventResult {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.ts.cmp(&other.ts)) }
-}
-impl PartialEq for EventResult {
-    fn eq(&self, other: &Self) -> bool { self.ts == other.ts }
+// Whether an event put its span on or off CPU, so polls can be paired up without parsing again.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+enum Cpu {
+    On,
+    Off,
+    Neither,
 }
 
 #[derive(Clone)]
 struct EventNode {
     events: Vec<EventResult>,
     name: String,
+    // allocations summed over all of the span's polls
+    alloc: AllocStats,
     parent: Option<SpanId>,
     children: Vec<SpanId>,
+    // when the span ended, if it has
+    end: Option<Duration>,
+}
+
+/// A span as the query API describes it.
+#[derive(Clone, Debug, Serialize)]
+pub struct SpanInfo {
+    pub id: SpanId,
+    pub name: String,
+    pub parent: Option<SpanId>,
+    pub start: Duration,
+    pub end: Option<Duration>,
+    pub children: usize,
+    pub alloc: AllocStats,
+}
+
+// The two ends of a message, as far as we've seen them.
+#[derive(Default)]
+struct Message {
+    send: Option<(EventResult, Option<SpanId>)>,
+    recv: Option<(EventResult, Option<SpanId>)>,
 }
 
-#[derive(Eq, PartialEq, Ord, PartialOrd, Hash)]
+// The spans `filter` shows, kept up to date as events are added once we've worked them out:
+// what a viewer following along live has been sent, and what a window picks from.
+#[derive(Default)]
+struct LiveSpans {
+    visible: HashSet<SpanId>,
+    // goal spans and their descendants, whose new children are shown too
+    expanded: HashSet<SpanId>,
+}
+
+#[derive(Eq, PartialEq, Hash)]
 struct Wakeup {
     event: EventResult,
     waking_span: SpanId,
     parked_span: SpanId,
 }
 
+/// What a viewer wants to see: the spans named in `goals` (or every span, if there are none)
+/// with their ancestors and descendants, leaving out wakeups from spans named in
+/// `hide_wakeups_from`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Filter {
+    pub goals: Vec<String>,
+    pub hide_wakeups_from: Vec<String>,
+}
+
+/// Most events sent for a window, unless the viewer asks for some other limit.
+pub const DEFAULT_MAX_EVENTS: usize = 100_000;
+// When a window has too many events to send, on-CPU intervals closer together than this fraction
+// of it are merged, and shorter spans are left out.
+const SUMMARY_BUCKETS: u32 = 2000;
+
+/// The stretch of a trace a viewer is looking at, from `start` up to (but not including) `end`,
+/// or to the end of the trace if there's no `end`.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Window {
+    #[serde(default)]
+    pub start: Duration,
+    #[serde(default)]
+    pub end: Option<Duration>,
+    #[serde(default = "default_max_events")]
+    pub max_events: usize,
+}
+
+fn default_max_events() -> usize {
+    DEFAULT_MAX_EVENTS
+}
+
+impl Window {
+    fn contains(&self, ts: Duration) -> bool {
+        ts >= self.start && self.end.is_none_or(|end| ts < end)
+    }
+
+    fn overlaps(&self, start: Duration, end: Option<Duration>) -> bool {
+        self.end.is_none_or(|window_end| start < window_end) && end.is_none_or(|end| end >= self.start)
+    }
+}
+
+/// Messages a viewer can send over its websocket.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum ClientMessage {
+    /// `{"SetFilter":{"goals":[...],"hide_wakeups_from":[...]}}`: show this viewer a different
+    /// slice of the trace from now on.
+    SetFilter(Filter),
+    /// `{"SetWindow":{"start":{"secs":1,"nanos":0},"end":...,"max_events":10000}}`: send only
+    /// what's in a window, for traces too big to send whole.  `{"SetWindow":null}` goes back to
+    /// the whole trace.
+    SetWindow(Option<Window>),
+}
+
 pub struct EventTree {
     slab: HashMap<SpanId, EventNode>,
     roots: HashSet<SpanId>,
     // emit these in postprocessing, if both nodes are in the tree
     wakeups: HashSet<Wakeup>,
+    // wakeups from untraced threads, emitted if the parked node is in the tree
+    untraced_wakeups: Vec<(EventResult, SpanId)>,
+    // messages over traced channels, emitted if both the sender and receiver are in the tree
+    messages: HashMap<MessageId, Message>,
+    // counters aren't tied to a span, so they're always emitted
+    counters: Vec<EventResult>,
     // what we're gonna filter for
     goal_names: HashSet<String>,
     goal_spans: HashSet<SpanId>,
     // filter out any wakeups originating from this node (popular choice: Control)
     hide_wakeups_from_names: HashSet<String>,
     hide_wakeups_from_spans: HashSet<SpanId>,
+    // links to parents in other traces, applied by `stitch` once both ends are loaded
+    remote_parents: Vec<(SpanId, SpanId)>,
+    // prefix for thread names, so threads from different traces can be told apart
+    source: Option<String>,
+    // which of the source's connections events are coming from, for ingested events
+    conn: usize,
+    // headers of the traces read so far, sent ahead of everything else
+    headers: Vec<String>,
+    // set once we've seen a trace we can't read
+    incompatible: Option<String>,
+    // interned names, for each source and connection
+    names: HashMap<(Option<String>, usize), HashMap<u32, String>>,
+    // what `filter` has shown so far, once we're adding events live
+    live: Option<LiveSpans>,
 }
 
 impl EventTree {
@@ -132,13 +393,59 @@ impl EventTree {
             slab: HashMap::new(),
             roots: HashSet::new(),
             wakeups: HashSet::new(),
+            untraced_wakeups: vec![],
+            messages: HashMap::new(),
+            counters: vec![],
             goal_names: goals.into_iter().collect(),
             goal_spans: HashSet::new(),
             hide_wakeups_from_names: hide_wakeups_from.into_iter().collect(),
             hide_wakeups_from_spans: HashSet::new(),
+            remote_parents: vec![],
+            source: None,
+            conn: 0,
+            headers: vec![],
+            incompatible: None,
+            names: HashMap::new(),
+            live: None,
         }
     }
 
+    /// Why the trace can't be shown, if one of its headers has a schema version we don't support.
+    pub fn incompatible(&self) -> Option<&str> {
+        self.incompatible.as_deref()
+    }
+
+    /// Label the threads of subsequently added events with `source`, e.g. the name of the trace
+    /// file they came from.
+    pub fn set_source(&mut self, source: Option<String>) {
+        self.source = source;
+        self.conn = 0;
+    }
+
+    /// Like `set_source`, for events ingested on connection `conn` from `source`.  A process can
+    /// open several connections, each with names of its own.
+    pub fn set_connection(&mut self, source: String, conn: usize) {
+        self.source = Some(source);
+        self.conn = conn;
+    }
+
+    /// Filter for `filter` from now on, rather than what the tree was created with.  A viewer
+    /// following along live should be sent `filter` again afterwards.
+    pub fn set_filter(&mut self, filter: &Filter) {
+        self.goal_names = filter.goals.iter().cloned().collect();
+        self.hide_wakeups_from_names = filter.hide_wakeups_from.iter().cloned().collect();
+        let (goal_names, hide_names) = (&self.goal_names, &self.hide_wakeups_from_names);
+        self.goal_spans = self.slab.iter()
+            .filter(|(_, node)| goal_names.is_empty() || goal_names.contains(&node.name))
+            .map(|(&id, _)| id)
+            .collect();
+        self.hide_wakeups_from_spans = self.slab.iter()
+            .filter(|(_, node)| hide_names.contains(&node.name))
+            .map(|(&id, _)| id)
+            .collect();
+        self.live = None;
+    }
+
     fn add_node(&mut self, id: SpanId, buf: String, name: String, ts: Duration, parent: Option<SpanId>) -> Result<(), (failure::Error, String)> {
         if self.slab.contains_key(&id) {
             return Err((failure::format_err!("duplicate node"), buf));
@@ -150,63 +457,591 @@ impl EventTree {
             self.hide_wakeups_from_spans.insert(id);
         }
         self.slab.insert(id, EventNode {
-            events: vec![EventResult { buf, ts }],
+            events: vec![EventResult { buf, ts, cpu: Cpu::Neither }],
             name,
+            alloc: AllocStats::default(),
             parent,
             children: vec![],
+            end: None,
         });
         Ok(())
     }
 
     pub fn add(&mut self, buf: String) -> Result<(), (failure::Error, String)> {
-        let event: TraceEvent = match serde_json::from_str(&buf) {
+        if self.live.is_some() {
+            return self.add_live(buf).map(|_| ());
+        }
+        let (event, buf) = self.prepare(buf)?;
+        self.insert(event, buf)
+    }
+
+    /// Add an event that arrived after the initial `filter`, returning whatever should now be
+    /// sent on to viewers: the event itself if it passes the filter, preceded by any ancestors
+    /// that weren't shown before if it's a goal span.  Wakeups and messages are sent once both
+    /// ends are shown.  Links to remote parents are applied by the next full `filter`.
+    pub fn add_live(&mut self, buf: String) -> Result<Vec<String>, (failure::Error, String)> {
+        let mut live = match self.live.take() {
+            Some(live) => live,
+            None => self.live_spans(),
+        };
+        let (event, buf) = self.prepare(buf)?;
+        let mut result = vec![];
+        let shown = match event {
+            TraceEvent::Header { .. } | TraceEvent::Counter { .. } => true,
+            TraceEvent::Wakeup { waking_span, parked_span, .. } => {
+                live.visible.contains(&waking_span) && live.visible.contains(&parked_span)
+                    && !self.hide_wakeups_from_spans.contains(&waking_span)
+            },
+            TraceEvent::UntracedWakeup { parked_span, .. } => live.visible.contains(&parked_span),
+            TraceEvent::AsyncOnCPU { id, .. }
+            | TraceEvent::AsyncOffCPU { id, .. }
+            | TraceEvent::AsyncEnd { id, .. }
+            | TraceEvent::AsyncMigrate { id, .. }
+            | TraceEvent::SyncEnd { id, .. }
+            | TraceEvent::LockEnd { id, .. }
+            | TraceEvent::ThreadEnd { id, .. } => live.visible.contains(&id),
+            _ => false,
+        };
+        if shown {
+            result.push(buf.clone());
+        }
+        let start = match event {
+            TraceEvent::ThreadStart { id, .. }
+            | TraceEvent::AsyncStart { id, .. }
+            | TraceEvent::SyncStart { id, .. }
+            | TraceEvent::LockStart { id, .. } => Some(id),
+            _ => None,
+        };
+        let msg = match event {
+            TraceEvent::MessageSend { msg, .. } | TraceEvent::MessageRecv { msg, .. } => Some(msg),
+            _ => None,
+        };
+        self.insert(event, buf)?;
+
+        if let Some(id) = start {
+            let parent = self.slab[&id].parent;
+            if self.goal_spans.contains(&id) {
+                self.show_ancestors(&mut live, &mut result, parent);
+            }
+            if self.goal_spans.contains(&id) || parent.is_some_and(|p| live.expanded.contains(&p)) {
+                live.visible.insert(id);
+                live.expanded.insert(id);
+                result.push(self.slab[&id].events[0].buf.clone());
+            }
+        }
+        if let Some(message) = msg.and_then(|msg| self.messages.get(&msg)) {
+            if let (Some((ref send, Some(sender))), Some((ref recv, Some(receiver)))) = (&message.send, &message.recv) {
+                if live.visible.contains(sender) && live.visible.contains(receiver) {
+                    result.push(send.buf.clone());
+                    result.push(recv.buf.clone());
+                }
+            }
+        }
+        self.live = Some(live);
+        Ok(result)
+    }
+
+    // The spans `filter` shows, to carry on from.
+    fn live_spans(&self) -> LiveSpans {
+        let mut live = LiveSpans::default();
+        let mut stack: Vec<SpanId> = self.goal_spans.iter().cloned().collect();
+        while let Some(id) = stack.pop() {
+            if live.expanded.insert(id) {
+                stack.extend(&self.slab[&id].children);
+            }
+        }
+        live.visible = live.expanded.clone();
+        for id in &self.goal_spans {
+            let mut parent = self.slab[id].parent;
+            while let Some(id) = parent {
+                if !live.visible.insert(id) {
+                    break;
+                }
+                parent = self.slab[&id].parent;
+            }
+        }
+        live
+    }
+
+    // Show a new goal span's ancestors that weren't already, root first, with everything they've
+    // logged so far.
+    fn show_ancestors(&self, live: &mut LiveSpans, result: &mut Vec<String>, mut parent: Option<SpanId>) {
+        let mut hidden = vec![];
+        while let Some(id) = parent {
+            if live.visible.contains(&id) {
+                break;
+            }
+            hidden.push(id);
+            parent = self.slab[&id].parent;
+        }
+        for id in hidden.into_iter().rev() {
+            live.visible.insert(id);
+            result.extend(self.slab[&id].events.iter().map(|e| e.buf.clone()));
+        }
+    }
+
+    // Parse an event, resolving interned names and labelling threads with the current source.
+    // Returns the event along with its JSON, rewritten if anything changed.
+    fn prepare(&mut self, buf: String) -> Result<(TraceEvent, String), (failure::Error, String)> {
+        let mut event: TraceEvent = match serde_json::from_str(&buf) {
             Ok(event) => event,
             Err(e) => return Err((e.into(), buf)),
         };
+        let mut dirty = false;
+        if let Some(name) = event.name_mut() {
+            if let Name::Ref(id) = *name {
+                let key = (self.source.clone(), self.conn);
+                let resolved = self.names.get(&key).and_then(|names| names.get(&id));
+                match resolved {
+                    Some(resolved) => *name = Name::Str(resolved.clone()),
+                    None => return Err((failure::format_err!("undefined name #{}", id), buf)),
+                }
+                dirty = true;
+            }
+        }
+        if let TraceEvent::ThreadStart { ref mut name, .. } = event {
+            if let (Some(source), &mut Name::Str(ref mut name)) = (self.source.as_ref(), name) {
+                *name = format!("{}/{}", source, name);
+                dirty = true;
+            }
+        }
+        let buf = if dirty {
+            match serde_json::to_string(&event) {
+                Ok(buf) => buf,
+                Err(e) => return Err((e.into(), buf)),
+            }
+        } else {
+            buf
+        };
+        Ok((event, buf))
+    }
+
+    fn insert(&mut self, event: TraceEvent, buf: String) -> Result<(), (failure::Error, String)> {
+        let end = match event {
+            TraceEvent::SyncEnd { id, ts, .. }
+            | TraceEvent::AsyncEnd { id, ts, .. }
+            | TraceEvent::LockEnd { id, ts, .. }
+            | TraceEvent::ThreadEnd { id, ts, .. } => Some((id, ts)),
+            _ => None,
+        };
+        let cpu = match event {
+            TraceEvent::AsyncOnCPU { .. } => Cpu::On,
+            TraceEvent::AsyncOffCPU { .. } => Cpu::Off,
+            _ => Cpu::Neither,
+        };
         match event {
+            TraceEvent::Header { schema_version, .. } => {
+                if schema_version > SCHEMA_VERSION {
+                    let reason = format!("trace has schema version {}, but only versions up to {} are supported",
+                                         schema_version, SCHEMA_VERSION);
+                    self.incompatible = Some(reason.clone());
+                    return Err((failure::err_msg(reason), buf));
+                }
+                // A new trace starts with a fresh set of names.
+                self.names.remove(&(self.source.clone(), self.conn));
+                self.headers.push(buf);
+            }
+
+            TraceEvent::NameDef { id, name } => {
+                self.names.entry((self.source.clone(), self.conn)).or_default().insert(id, name);
+            }
+
             // Add new root.
             TraceEvent::ThreadStart { id, name, ts, .. } => {
-                self.add_node(id, buf, name, ts, None)?;
+                self.add_node(id, buf, name.into_string(), ts, None)?;
                 self.roots.insert(id);
             }
 
             // Add new node with a parent.
             TraceEvent::AsyncStart { id, parent_id, name, ts, .. }
-            | TraceEvent::SyncStart { id, parent_id, name, ts, .. } => {
-                assert!(!self.slab.contains_key(&id), "duplicate node");
+            | TraceEvent::SyncStart { id, parent_id, name, ts, .. }
+            | TraceEvent::LockStart { id, parent_id, name, ts, .. } => {
+                if self.slab.contains_key(&id) {
+                    return Err((failure::format_err!("duplicate node"), buf));
+                }
                 if let Some(parent_node) = self.slab.get_mut(&parent_id) {
                     parent_node.children.push(id);
-                    self.add_node(id, buf, name, ts, Some(parent_id))?;
+                    self.add_node(id, buf, name.into_string(), ts, Some(parent_id))?;
                 } else {
                     println!("warning: parentless node {:?} (alleged parent: {:?}); treating as root", id, parent_id);
-                    self.add_node(id, buf, name, ts, None)?;
+                    self.add_node(id, buf, name.into_string(), ts, None)?;
                     self.roots.insert(id);
                 }
             },
 
-            // Add event to existing node in the tree.
+            // Add event to existing node in the tree, along with anything it allocated.
+            TraceEvent::AsyncOffCPU { id, ts, alloc }
+            | TraceEvent::SyncEnd { id, ts, alloc } => {
+                let node = match self.slab.get_mut(&id) {
+                    Some(node) => node,
+                    None => return Err((failure::format_err!("nodeless event"), buf)),
+                };
+                node.events.push(EventResult { buf, ts, cpu });
+                if let Some(alloc) = alloc {
+                    node.alloc.count += alloc.count;
+                    node.alloc.bytes += alloc.bytes;
+                }
+            }
             TraceEvent::AsyncOnCPU { id, ts, .. }
-            | TraceEvent::AsyncOffCPU { id, ts, .. }
             | TraceEvent::AsyncEnd { id, ts, .. }
-            | TraceEvent::SyncEnd { id, ts, .. }
+            | TraceEvent::AsyncMigrate { id, ts, .. }
+            | TraceEvent::LockEnd { id, ts, .. }
             | TraceEvent::ThreadEnd { id, ts, .. } => {
-                let node = self.slab.get_mut(&id).expect("nodeless event");
-                node.events.push(EventResult { buf, ts });
+                let node = match self.slab.get_mut(&id) {
+                    Some(node) => node,
+                    None => return Err((failure::format_err!("nodeless event"), buf)),
+                };
+                node.events.push(EventResult { buf, ts, cpu });
             }
 
             // Add new wakeup.
             TraceEvent::Wakeup { waking_span, parked_span, ts, .. } => {
-                self.wakeups.insert(Wakeup { event: EventResult { buf, ts }, waking_span, parked_span });
+                self.wakeups.insert(Wakeup { event: EventResult { buf, ts, cpu }, waking_span, parked_span });
+            }
+
+            TraceEvent::UntracedWakeup { parked_span, ts, .. } => {
+                self.untraced_wakeups.push((EventResult { buf, ts, cpu }, parked_span));
+            }
+
+            // Remember the link; the parent may live in a trace we haven't read yet.
+            TraceEvent::RemoteParent { id, parent, .. } => {
+                self.remote_parents.push((id, parent.span_id));
+            }
+
+            // Pair up the two ends of a message; either may show up first.
+            TraceEvent::MessageSend { msg, span, ts, .. } => {
+                self.messages.entry(msg).or_default().send = Some((EventResult { buf, ts, cpu }, span));
+            }
+            TraceEvent::MessageRecv { msg, span, ts, .. } => {
+                self.messages.entry(msg).or_default().recv = Some((EventResult { buf, ts, cpu }, span));
+            }
+
+            TraceEvent::Counter { ts, .. } => {
+                self.counters.push(EventResult { buf, ts, cpu });
+            }
+        }
+        if let Some((id, ts)) = end {
+            // The node is there, or we'd have returned above.
+            if let Some(node) = self.slab.get_mut(&id) {
+                node.end = Some(ts);
             }
         }
         Ok(())
     }
 
-    // TODO: be able to do this filter in-line
+    /// Move spans under their remote parents, for every link where both ends have been added.
+    /// Links whose parent is still missing are kept around for a later call.
+    pub fn stitch(&mut self) {
+        let links = std::mem::take(&mut self.remote_parents);
+        for (id, remote) in links {
+            if !self.slab.contains_key(&id) || !self.slab.contains_key(&remote) {
+                self.remote_parents.push((id, remote));
+                continue;
+            }
+            if self.is_ancestor(id, remote) {
+                println!("warning: not stitching {:?} under its own descendant {:?}", id, remote);
+                continue;
+            }
+            let start = &self.slab[&id].events[0];
+            let buf = match reparent(&start.buf, remote) {
+                Ok(buf) => buf,
+                Err(e) => {
+                    println!("warning: couldn't stitch '{}': {:?}", start.buf, e);
+                    continue;
+                },
+            };
+
+            match self.slab[&id].parent {
+                Some(parent) => self.slab.get_mut(&parent).unwrap().children.retain(|c| *c != id),
+                None => { self.roots.remove(&id); },
+            }
+            self.slab.get_mut(&remote).unwrap().children.push(id);
+            let node = self.slab.get_mut(&id).unwrap();
+            node.parent = Some(remote);
+            node.events[0].buf = buf;
+            self.live = None;
+        }
+    }
+
+    /// Span names that allocated the most bytes, summed over every span with that name, and
+    /// heaviest first.  Empty unless the traces count allocations.
+    pub fn top_allocations(&self, limit: usize) -> Vec<(String, AllocStats)> {
+        let mut by_name: HashMap<&str, AllocStats> = HashMap::new();
+        for node in self.slab.values() {
+            if node.alloc.count == 0 {
+                continue;
+            }
+            let total = by_name.entry(&node.name).or_default();
+            total.count += node.alloc.count;
+            total.bytes += node.alloc.bytes;
+        }
+        let mut result: Vec<_> = by_name.into_iter()
+            .map(|(name, alloc)| (name.to_string(), alloc))
+            .collect();
+        result.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then_with(|| a.0.cmp(&b.0)));
+        result.truncate(limit);
+        result
+    }
+
+    pub fn span_info(&self, id: SpanId) -> Option<SpanInfo> {
+        self.slab.get(&id).map(|node| SpanInfo {
+            id,
+            name: node.name.clone(),
+            parent: node.parent,
+            start: node.events[0].ts,
+            end: node.end,
+            children: node.children.len(),
+            alloc: node.alloc,
+        })
+    }
+
+    /// Threads, and any spans whose parents we never saw, in the order they started.
+    pub fn root_spans(&self) -> Vec<SpanInfo> {
+        self.span_infos(self.roots.iter().cloned())
+    }
+
+    /// Spans whose names contain `pattern`, in the order they started.
+    pub fn find_spans(&self, pattern: &str) -> Vec<SpanInfo> {
+        let ids = self.slab.iter()
+            .filter(|&(_, node)| node.name.contains(pattern))
+            .map(|(id, _)| *id);
+        self.span_infos(ids)
+    }
+
+    pub fn children(&self, id: SpanId) -> Option<Vec<SpanInfo>> {
+        let node = self.slab.get(&id)?;
+        Some(self.span_infos(node.children.iter().cloned()))
+    }
+
+    /// A span's ancestors, its thread first and its parent last.
+    pub fn ancestors(&self, id: SpanId) -> Option<Vec<SpanInfo>> {
+        let mut parent = self.slab.get(&id)?.parent;
+        let mut result = vec![];
+        while let Some(id) = parent {
+            let info = self.span_info(id)?;
+            parent = info.parent;
+            result.push(info);
+        }
+        result.reverse();
+        Some(result)
+    }
+
+    /// The JSON of every event logged for a span, in the order they were logged.
+    pub fn span_events(&self, id: SpanId) -> Option<Vec<&str>> {
+        self.slab.get(&id).map(|node| node.events.iter().map(|e| &e.buf[..]).collect())
+    }
+
+    fn span_infos<I: Iterator<Item=SpanId>>(&self, ids: I) -> Vec<SpanInfo> {
+        let mut result: Vec<SpanInfo> = ids.filter_map(|id| self.span_info(id)).collect();
+        result.sort_by_key(|info| (info.start, info.id));
+        result
+    }
+
+    fn is_ancestor(&self, ancestor: SpanId, mut id: SpanId) -> bool {
+        loop {
+            if id == ancestor {
+                return true;
+            }
+            match self.slab.get(&id).and_then(|node| node.parent) {
+                Some(parent) => id = parent,
+                None => return false,
+            }
+        }
+    }
+
+    // Latest start time along the path from the root to this node.  Traces stitched together from
+    // different processes don't share a clock, so a child can claim to start before its parent.
+    fn start_floor(&self, mut id: SpanId) -> Duration {
+        let mut floor = Duration::from_secs(0);
+        while let Some(node) = self.slab.get(&id) {
+            floor = std::cmp::max(floor, node.events[0].ts);
+            match node.parent {
+                Some(parent) => id = parent,
+                None => break,
+            }
+        }
+        floor
+    }
+
+    /// What `filter` would show in `window`: the spans overlapping it, with their ancestors, and
+    /// what happened in it.  Spans' starts and ends are sent even if they're outside the window,
+    /// as is any on-CPU interval that overlaps it.  If that's more than `window.max_events`
+    /// events, we zoom out: each span's on-CPU intervals are merged into a `Busy` summary, which
+    /// counts the wakeups and messages into the span instead of sending them, and spans too
+    /// short to see are left out.  Either way, no more than `max_events` are returned after the
+    /// headers.
+    pub fn window(&mut self, window: &Window) -> Vec<String> {
+        if self.live.is_none() {
+            self.live = Some(self.live_spans());
+        }
+        let visible = &self.live.as_ref().unwrap().visible;
+        let mut result = self.window_events(visible, window, None);
+        if result.len() > window.max_events {
+            let end = window.end.unwrap_or_else(|| self.last_ts());
+            let min_width = end.saturating_sub(window.start) / SUMMARY_BUCKETS;
+            result = self.window_events(visible, window, Some(min_width));
+        }
+        // Stable, so parents still come before children that claim the same start time.
+        result.sort_by_key(|&(ts, _)| ts);
+        result.truncate(window.max_events);
+        self.headers.iter().cloned()
+            .chain(result.into_iter().map(|(_, buf)| buf))
+            .collect()
+    }
+
+    // The events to send for `window`, parents before children, summarized if `min_width` is set.
+    fn window_events(&self, visible: &HashSet<SpanId>, window: &Window, min_width: Option<Duration>) -> Vec<(Duration, String)> {
+        let mut kept = HashSet::new();
+        for &id in visible {
+            let start = self.start_floor(id);
+            let end = self.slab[&id].end;
+            if !window.overlaps(start, end) {
+                continue;
+            }
+            let too_short = min_width.is_some_and(|min| end.is_some_and(|end| end.saturating_sub(start) < min));
+            if too_short {
+                continue;
+            }
+            let mut next = Some(id);
+            while let Some(id) = next {
+                if !kept.insert(id) {
+                    break;
+                }
+                next = self.slab[&id].parent;
+            }
+        }
+
+        // Wakeups and messages between the spans we're keeping, by the span each leads to.
+        let mut edges: Vec<(SpanId, Vec<&EventResult>)> = vec![];
+        for wakeup in &self.wakeups {
+            if kept.contains(&wakeup.waking_span) && kept.contains(&wakeup.parked_span)
+                && !self.hide_wakeups_from_spans.contains(&wakeup.waking_span)
+                && window.contains(wakeup.event.ts)
+            {
+                edges.push((wakeup.parked_span, vec![&wakeup.event]));
+            }
+        }
+        for &(ref event, parked_span) in &self.untraced_wakeups {
+            if kept.contains(&parked_span) && window.contains(event.ts) {
+                edges.push((parked_span, vec![event]));
+            }
+        }
+        for message in self.messages.values() {
+            if let (Some((ref send, Some(sender))), Some((ref recv, Some(receiver)))) = (&message.send, &message.recv) {
+                if kept.contains(sender) && kept.contains(receiver) && window.overlaps(send.ts, Some(recv.ts)) {
+                    edges.push((*receiver, vec![send, recv]));
+                }
+            }
+        }
+
+        let mut edge_counts: HashMap<SpanId, usize> = HashMap::new();
+        if min_width.is_some() {
+            for &(to, _) in &edges {
+                *edge_counts.entry(to).or_default() += 1;
+            }
+        }
+
+        let mut result = vec![];
+        let mut roots: Vec<SpanId> = kept.iter().cloned().filter(|id| self.slab[id].parent.is_none()).collect();
+        roots.sort();
+        let mut stack: Vec<SpanId> = roots.into_iter().rev().collect();
+        while let Some(id) = stack.pop() {
+            let edges = edge_counts.get(&id).cloned().unwrap_or(0);
+            self.push_window_events(&mut result, id, window, min_width, edges);
+            let children = &self.slab[&id].children;
+            stack.extend(children.iter().rev().filter(|child| kept.contains(child)));
+        }
+        if min_width.is_none() {
+            result.extend(edges.into_iter()
+                .flat_map(|(_, events)| events)
+                .map(|event| (event.ts, event.buf.clone())));
+        }
+        result.extend(self.counters.iter()
+            .filter(|event| window.contains(event.ts))
+            .map(|event| (event.ts, event.buf.clone())));
+        result
+    }
+
+    // Push one span's events for `window`.  When summarizing, `edges` is how many wakeups and
+    // messages into it were left out.
+    fn push_window_events(&self, result: &mut Vec<(Duration, String)>, id: SpanId, window: &Window, min_width: Option<Duration>, edges: usize) {
+        let node = &self.slab[&id];
+        let (start, rest) = node.events.split_first().expect("span without a start");
+        let (end, middle) = match node.end {
+            Some(_) => match rest.split_last() {
+                Some((end, middle)) => (Some(end), middle),
+                None => (None, rest),
+            },
+            None => (None, rest),
+        };
+        result.push((self.start_floor(id), start.buf.clone()));
+
+        // Pair up on- and off-CPU events, keeping the intervals that overlap the window.
+        let mut busy = vec![];
+        let mut on_cpu = None;
+        for event in middle {
+            match event.cpu {
+                Cpu::On => on_cpu = Some(event),
+                Cpu::Off => busy.extend(on_cpu.take().map(|on| (on, Some(event)))),
+                Cpu::Neither if min_width.is_none() && window.contains(event.ts) => {
+                    result.push((event.ts, event.buf.clone()));
+                },
+                Cpu::Neither => (),
+            }
+        }
+        busy.extend(on_cpu.map(|on| (on, None)));
+        busy.retain(|&(on, off)| window.overlaps(on.ts, off.map(|off| off.ts)));
+
+        match min_width {
+            None => {
+                for (on, off) in busy {
+                    result.push((on.ts, on.buf.clone()));
+                    result.extend(off.map(|off| (off.ts, off.buf.clone())));
+                }
+            },
+            Some(min_width) => {
+                let mut merged: Vec<(Duration, Option<Duration>)> = vec![];
+                for (on, off) in busy {
+                    let off = off.map(|off| off.ts);
+                    let merge = match merged.last() {
+                        Some(&(_, Some(last_off))) => on.ts < last_off + min_width,
+                        Some(&(_, None)) => true,
+                        None => false,
+                    };
+                    if merge {
+                        let last_off = &mut merged.last_mut().unwrap().1;
+                        *last_off = match (*last_off, off) {
+                            (Some(last_off), Some(off)) => Some(std::cmp::max(last_off, off)),
+                            _ => None,
+                        };
+                    } else {
+                        merged.push((on.ts, off));
+                    }
+                }
+                if let Some(&(first, _)) = merged.first() {
+                    result.push((first, json!({ "Busy": { "id": id, "intervals": merged, "edges": edges } }).to_string()));
+                }
+            },
+        }
+        if let Some(end) = end {
+            result.push((end.ts, end.buf.clone()));
+        }
+    }
+
+    // When the last thing in the trace happened.
+    fn last_ts(&self) -> Duration {
+        self.slab.values()
+            .filter_map(|node| node.events.last())
+            .chain(&self.counters)
+            .map(|event| event.ts)
+            .max()
+            .unwrap_or_default()
+    }
+
     // Guaranteed to return in root-first order (parents before children), and wakeups last, i guess.
     pub fn filter(&self) -> Vec<String> {
         let mut seen_ids = HashSet::new();
-        let mut result = vec![];
+        let mut result: Vec<(Duration, EventResult)> = vec![];
         for id in &self.goal_spans {
             let node = self.slab.get(id).expect("this node missing during filter");
             // Process this node's ancestors.
@@ -221,15 +1056,39 @@ impl EventTree {
                 // (and if we weren't told explicitly to avoid printing it)
                 if !self.hide_wakeups_from_spans.contains(&wakeup.waking_span) {
                     // println!("adding wakeup: {}", wakeup.event.buf);
-                    result.push(wakeup.event.clone());
+                    result.push((wakeup.event.ts, wakeup.event.clone()));
+                }
+            }
+        }
+        for &(ref event, parked_span) in &self.untraced_wakeups {
+            if seen_ids.contains(&parked_span) {
+                result.push((event.ts, event.clone()));
+            }
+        }
+        for message in self.messages.values() {
+            if let (Some((ref send, Some(sender))), Some((ref recv, Some(receiver)))) = (&message.send, &message.recv) {
+                if seen_ids.contains(sender) && seen_ids.contains(receiver) {
+                    result.push((send.ts, send.clone()));
+                    result.push((recv.ts, recv.clone()));
                 }
             }
         }
-        result.sort();
-        result.into_iter().map(|x| x.buf).collect()
+        result.extend(self.counters.iter().map(|event| (event.ts, event.clone())));
+        // Stable, so parents still come before children that claim the same start time.
+        result.sort_by_key(|&(ts, _)| ts);
+        self.headers.iter().cloned()
+            .chain(result.into_iter().map(|(_, x)| x.buf))
+            .collect()
+    }
+
+    fn push_events(&self, result: &mut Vec<(Duration, EventResult)>, id: SpanId, node: &EventNode) {
+        let floor = self.start_floor(id);
+        for event in &node.events {
+            result.push((std::cmp::max(floor, event.ts), event.clone()));
+        }
     }
 
-    fn add_ancestors(&self, seen_ids: &mut HashSet<SpanId>, result: &mut Vec<EventResult>, ancestor_id: Option<SpanId>) {
+    fn add_ancestors(&self, seen_ids: &mut HashSet<SpanId>, result: &mut Vec<(Duration, EventResult)>, ancestor_id: Option<SpanId>) {
         if let Some(id) = ancestor_id {
             if !seen_ids.contains(&id) {
                 let node = self.slab.get(&id).expect("ancestor node missing");
@@ -237,22 +1096,18 @@ impl EventTree {
                 seen_ids.insert(id);
                 // Add after iterating, to ensure parent-first order.
                 self.add_ancestors(seen_ids, result, node.parent);
-                for event in &node.events {
-                    result.push(event.clone());
-                }
+                self.push_events(result, id, node);
             }
         }
     }
 
-    fn add_children(&self, seen_ids: &mut HashSet<SpanId>, result: &mut Vec<EventResult>, id: SpanId) {
+    fn add_children(&self, seen_ids: &mut HashSet<SpanId>, result: &mut Vec<(Duration, EventResult)>, id: SpanId) {
         if !seen_ids.contains(&id) {
             // Add before iterating, to ensure parent-first order.
             let node = self.slab.get(&id).expect("child node missing");
             //println!("adding {} events from node named '{}'", node.events.len(), node.name);
             seen_ids.insert(id);
-            for event in &node.events {
-                result.push(event.clone());
-            }
+            self.push_events(result, id, node);
             for child in &node.children {
                 self.add_children(seen_ids, result, *child);
             }
@@ -260,9 +1115,22 @@ impl EventTree {
     }
 }
 
+// Point a start event at a new parent, e.g. when stitching it under its remote parent.
+fn reparent(buf: &str, new_parent: SpanId) -> Result<String, failure::Error> {
+    let mut event: TraceEvent = serde_json::from_str(buf)?;
+    match event {
+        TraceEvent::AsyncStart { ref mut parent_id, .. }
+        | TraceEvent::SyncStart { ref mut parent_id, .. } => *parent_id = new_parent,
+        _ => return Err(failure::format_err!("not a span start")),
+    }
+    Ok(serde_json::to_string(&event)?)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::EventTree;
+    use serde_json;
+    use std::time::Duration;
+    use super::{ClientMessage, EventTree, Filter, Window, SCHEMA_VERSION};
 
     fn buf_thread_start(name: &str, id: usize) -> String {
         format!("{{\"ThreadStart\":{{\"name\":\"{}\",\"id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}},\"is_restart\":false}}}}", name, id)
@@ -272,15 +1140,60 @@ mod tests {
         format!("{{\"SyncStart\":{{\"name\":\"{}\",\"id\":{},\"parent_id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}},\"metadata\":null}}}}", name, id, parent_id)
     }
 
+    fn buf_thread_start_ref(name_id: usize, id: usize) -> String {
+        format!("{{\"ThreadStart\":{{\"name\":{},\"id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", name_id, id)
+    }
+
+    fn buf_sync_start_ref(name_id: usize, id: usize, parent_id: usize) -> String {
+        format!("{{\"SyncStart\":{{\"name\":{},\"id\":{},\"parent_id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}},\"metadata\":null}}}}", name_id, id, parent_id)
+    }
+
     fn buf_sync_end(id: usize) -> String {
         format!("{{\"SyncEnd\":{{\"id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", id)
     }
 
+    fn buf_sync_end_alloc(id: usize, count: usize, bytes: usize) -> String {
+        format!("{{\"SyncEnd\":{{\"id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}},\"alloc\":{{\"count\":{},\"bytes\":{}}}}}}}", id, count, bytes)
+    }
+
+    fn buf_name_def(id: usize, name: &str) -> String {
+        format!("{{\"NameDef\":{{\"id\":{},\"name\":\"{}\"}}}}", id, name)
+    }
+
+    fn buf_header(schema_version: u32) -> String {
+        format!("{{\"Header\":{{\"schema_version\":{},\"wall_clock\":{{\"secs\":1500000000,\"nanos\":0}},\"clock\":\"monotonic\",\"process_id\":7,\"pid\":42,\"hostname\":null,\"executable\":null,\"command_line\":[]}}}}", schema_version)
+    }
+
+    fn buf_untraced_wakeup(parked_id: usize) -> String {
+        format!("{{\"UntracedWakeup\":{{\"waking_thread\":\"reactor\",\"parked_span\":{},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", parked_id)
+    }
+
+    fn buf_remote_parent(id: usize, parent_id: usize) -> String {
+        format!("{{\"RemoteParent\":{{\"id\":{},\"parent\":{{\"process_id\":7,\"span_id\":{}}},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", id, parent_id)
+    }
+
+    fn buf_message(kind: &str, msg: usize, span_id: usize) -> String {
+        format!("{{\"{}\":{{\"msg\":{},\"channel\":\"requests\",\"span\":{},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", kind, msg, span_id)
+    }
+
+    fn buf_lock_start(phase: &str, id: usize, parent_id: usize) -> String {
+        format!("{{\"LockStart\":{{\"name\":\"counter\",\"id\":{},\"parent_id\":{},\"phase\":\"{}\",\"holder\":null,\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", id, parent_id, phase)
+    }
+
+    fn buf_lock_end(id: usize) -> String {
+        format!("{{\"LockEnd\":{{\"id\":{},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", id)
+    }
+
+    fn buf_counter(value: usize) -> String {
+        format!("{{\"Counter\":{{\"name\":\"requests\",\"value\":{},\"ts\":{{\"secs\":0,\"nanos\":0}}}}}}", value)
+    }
+
     fn buf_wakeup(waking_id: usize, parked_id: usize, ts: usize) -> String {
         format!("{{\"Wakeup\":{{\"waking_span\":{},\"parked_span\":{},\"ts\":{{\"secs\":0,\"nanos\":{}}}}}}}", waking_id, parked_id, ts)
     }
 
     #[test]
+    #[allow(clippy::explicit_counter_loop)]
     fn test_event_tree_multiple_roots() {
         let mut tree = EventTree::new(vec![]);
         let mut root_id = 0;
@@ -292,6 +1205,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::explicit_counter_loop)]
     fn test_event_tree_no_goals_no_problem() {
         let mut tree = EventTree::new(vec![]);
         let mut root_id = 0;
@@ -320,6 +1234,30 @@ mod tests {
         assert_eq!(tree.filter().len(), 2); // not include patrick
     }
 
+    #[test]
+    fn test_event_tree_bad_events() {
+        let mut tree = EventTree::new(vec![]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Niko", 1, 0)).expect("add child");
+
+        // A span started twice is rejected, and handed back, without touching the tree.
+        let dup = buf_sync_start("Niko", 1, 0);
+        let (_, buf) = tree.add(dup.clone()).expect_err("duplicate start");
+        assert_eq!(buf, dup);
+        assert!(tree.add(buf_thread_start("Graydon", 0)).is_err());
+        assert_eq!(tree.slab[&super::SpanId(0)].children, vec![super::SpanId(1)]);
+
+        // So is an event for a span we never saw start.
+        let orphan = buf_sync_end(7);
+        let (_, buf) = tree.add(orphan.clone()).expect_err("orphan end");
+        assert_eq!(buf, orphan);
+        assert!(tree.add(buf_async("AsyncEnd", 8, 0)).is_err());
+        assert!(!tree.slab.contains_key(&super::SpanId(7)));
+
+        tree.add(buf_sync_end(1)).expect("end child");
+        assert_eq!(tree.filter().len(), 3);
+    }
+
     #[test]
     fn test_event_not_include_duplicates() {
         let mut tree = EventTree::new(vec!["Niko".to_string(), "Patrick".to_string()]);
@@ -360,4 +1298,252 @@ mod tests {
         }
         assert_eq!(tree.filter().len(), 22);
     }
+
+    #[test]
+    fn test_event_untraced_wakeups() {
+        let mut tree = EventTree::new(vec!["Niko".to_string()]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Niko", 1, 0)).expect("add child");
+        tree.add(buf_sync_start("Patrick", 2, 0)).expect("add child");
+        tree.add(buf_untraced_wakeup(1)).expect("add wakeup");
+        tree.add(buf_untraced_wakeup(2)).expect("add wakeup"); // patrick not in goals
+        assert_eq!(tree.filter().len(), 3);
+    }
+
+    #[test]
+    fn test_event_messages() {
+        let mut tree = EventTree::new(vec!["Niko".to_string()]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Niko", 1, 0)).expect("add child");
+        tree.add(buf_sync_start("Patrick", 2, 0)).expect("add child");
+        // include this one - both ends are in the tree, and the receive can come first
+        tree.add(buf_message("MessageRecv", 10, 0)).expect("add recv");
+        tree.add(buf_message("MessageSend", 10, 1)).expect("add send");
+        // don't include this one - patrick not in goals
+        tree.add(buf_message("MessageSend", 11, 1)).expect("add send");
+        tree.add(buf_message("MessageRecv", 11, 2)).expect("add recv");
+        // don't include this one - never received
+        tree.add(buf_message("MessageSend", 12, 1)).expect("add send");
+        tree.add(buf_counter(1)).expect("add counter");
+        assert_eq!(tree.filter().len(), 5);
+    }
+
+    #[test]
+    fn test_event_lock_spans() {
+        let mut tree = EventTree::new(vec!["Niko".to_string()]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Niko", 1, 0)).expect("add child");
+        tree.add(buf_lock_start("Wait", 2, 1)).expect("add wait");
+        tree.add(buf_lock_end(2)).expect("end wait");
+        tree.add(buf_lock_start("Hold", 3, 1)).expect("add hold");
+        tree.add(buf_lock_end(3)).expect("end hold");
+        tree.add(buf_sync_start("Patrick", 4, 0)).expect("add child");
+        tree.add(buf_lock_start("Hold", 5, 4)).expect("add hold"); // patrick not in goals
+        assert_eq!(tree.filter().len(), 6);
+    }
+
+    #[test]
+    fn test_event_top_allocations() {
+        let mut tree = EventTree::new(vec![]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Niko", 1, 0)).expect("add child");
+        tree.add(buf_sync_end_alloc(1, 1, 100)).expect("end child");
+        tree.add(buf_sync_start("Niko", 2, 0)).expect("add child");
+        tree.add(buf_sync_end_alloc(2, 2, 300)).expect("end child");
+        tree.add(buf_sync_start("Patrick", 3, 0)).expect("add child");
+        tree.add(buf_sync_end_alloc(3, 5, 200)).expect("end child");
+        tree.add(buf_sync_start("Aaron", 4, 0)).expect("add child");
+        tree.add(buf_sync_end(4)).expect("end child"); // doesn't count allocations
+
+        let top = tree.top_allocations(10);
+        let names: Vec<&str> = top.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["Niko", "Patrick"]);
+        assert_eq!((top[0].1.count, top[0].1.bytes), (3, 400));
+        assert_eq!(tree.top_allocations(1).len(), 1);
+    }
+
+    #[test]
+    fn test_event_interned_names() {
+        let mut tree = EventTree::new(vec!["Niko".to_string()]);
+        tree.set_source(Some("a".to_string()));
+        tree.add(buf_name_def(0, "Graydon")).expect("add name");
+        tree.add(buf_name_def(1, "Niko")).expect("add name");
+        tree.add(buf_thread_start_ref(0, 0)).expect("add root");
+        tree.add(buf_sync_start_ref(1, 1, 0)).expect("add child");
+        // the same ids mean something else in another trace
+        tree.set_source(Some("b".to_string()));
+        tree.add(buf_name_def(0, "Patrick")).expect("add name");
+        tree.add(buf_thread_start_ref(0, 2)).expect("add root");
+        assert!(tree.add(buf_sync_start_ref(1, 3, 2)).is_err()); // undefined here
+
+        let events = tree.filter();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].contains("\"name\":\"a/Graydon\""));
+        assert!(events[1].contains("\"name\":\"Niko\""));
+        assert_eq!(tree.slab[&super::SpanId(2)].name, "b/Patrick");
+    }
+
+    #[test]
+    fn test_event_stitch_remote_parent() {
+        let mut tree = EventTree::new(vec!["Patrick".to_string()]);
+        tree.set_source(Some("client".to_string()));
+        tree.add(buf_thread_start("main", 0)).expect("add root");
+        tree.add(buf_sync_start("request", 1, 0)).expect("add child");
+        tree.set_source(Some("server".to_string()));
+        tree.add(buf_thread_start("main", 2)).expect("add root");
+        tree.add(buf_sync_start("Patrick", 3, 2)).expect("add child");
+        tree.add(buf_remote_parent(3, 1)).expect("add link");
+        tree.stitch();
+
+        let events = tree.filter();
+        assert_eq!(events.len(), 3); // the server's thread isn't an ancestor anymore
+        assert!(events[0].contains("client/main"));
+        assert!(events[2].contains("\"parent_id\":1"));
+        assert!(!tree.roots.contains(&super::SpanId(3)));
+    }
+
+    #[test]
+    fn test_event_header() {
+        let mut tree = EventTree::new(vec![]);
+        tree.add(buf_header(SCHEMA_VERSION)).expect("add header");
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        assert!(tree.incompatible().is_none());
+        let events = tree.filter();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].starts_with("{\"Header\""));
+
+        assert!(tree.add(buf_header(SCHEMA_VERSION + 1)).is_err());
+        assert!(tree.incompatible().is_some());
+    }
+
+    #[test]
+    fn test_event_add_live() {
+        let mut tree = EventTree::new(vec![]);
+        tree.set_source(Some("remote".to_string()));
+        let root = tree.add_live(buf_thread_start("Graydon", 0)).expect("add root");
+        assert!(root[0].contains("remote/Graydon"));
+        assert_eq!(tree.add_live(buf_sync_start("Control", 1, 0)).expect("add child").len(), 1);
+        assert_eq!(tree.add_live(buf_sync_start("Niko", 2, 0)).expect("add child").len(), 1);
+        // wakeups from Control are hidden
+        assert!(tree.add_live(buf_wakeup(1, 2, 3)).expect("add wakeup").is_empty());
+        assert_eq!(tree.add_live(buf_wakeup(2, 1, 4)).expect("add wakeup").len(), 1);
+    }
+
+    #[test]
+    fn test_event_add_live_filtered() {
+        let mut tree = EventTree::new(vec!["Niko".to_string()]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Patrick", 1, 0)).expect("add child");
+        assert_eq!(tree.filter().len(), 0);
+
+        // not a goal, nor under one
+        assert!(tree.add_live(buf_sync_start("Patrick", 2, 1)).expect("add child").is_empty());
+        assert!(tree.add_live(buf_sync_end(2)).expect("end child").is_empty());
+        // a goal brings along its ancestors, root first
+        let events = tree.add_live(buf_sync_start("Niko", 3, 1)).expect("add goal");
+        assert_eq!(events.len(), 3);
+        assert!(events[0].contains("Graydon"));
+        assert!(events[1].contains("Patrick"));
+        assert!(events[2].contains("Niko"));
+        // and then its descendants follow, along with anything between them
+        assert_eq!(tree.add_live(buf_sync_start("Patrick", 4, 3)).expect("add grandchild").len(), 1);
+        assert_eq!(tree.add_live(buf_sync_end(4)).expect("end grandchild").len(), 1);
+        assert_eq!(tree.add_live(buf_message("MessageSend", 10, 4)).expect("add send").len(), 0);
+        assert_eq!(tree.add_live(buf_message("MessageRecv", 10, 3)).expect("add recv").len(), 2);
+        assert!(tree.add_live(buf_message("MessageRecv", 11, 2)).expect("add recv").is_empty());
+        assert_eq!(tree.add_live(buf_counter(1)).expect("add counter").len(), 1);
+
+        // the same spans show up after a full filter, too
+        assert_eq!(tree.filter().len(), 8);
+    }
+
+    #[test]
+    fn test_event_set_filter() {
+        let mut tree = EventTree::new(vec![]);
+        tree.add(buf_thread_start("Graydon", 0)).expect("add root");
+        tree.add(buf_sync_start("Control", 1, 0)).expect("add child");
+        tree.add(buf_sync_start("Niko", 2, 0)).expect("add child");
+        tree.add(buf_sync_start("Patrick", 3, 0)).expect("add child");
+        tree.add(buf_wakeup(1, 2, 4)).expect("add wakeup");
+        assert_eq!(tree.filter().len(), 4);
+
+        let message: ClientMessage = serde_json::from_str(r#"{"SetFilter":{"goals":["Niko"]}}"#)
+            .expect("parse message");
+        let filter = match message {
+            ClientMessage::SetFilter(filter) => filter,
+            _ => panic!("wrong message"),
+        };
+        assert!(filter.hide_wakeups_from.is_empty());
+        tree.set_filter(&filter);
+        assert_eq!(tree.filter().len(), 2);
+        // nothing hides the wakeup any more
+        tree.set_filter(&Filter { goals: vec!["Niko".to_string(), "Control".to_string()], ..Filter::default() });
+        assert_eq!(tree.filter().len(), 4);
+        // and following along live picks up from the new filter
+        assert_eq!(tree.add_live(buf_sync_start("Patrick", 5, 2)).expect("add grandchild").len(), 1);
+        assert!(tree.add_live(buf_sync_start("Patrick", 6, 3)).expect("add grandchild").is_empty());
+    }
+
+    fn buf_async(kind: &str, id: usize, millis: u64) -> String {
+        let ts = serde_json::to_string(&Duration::from_millis(millis)).unwrap();
+        match kind {
+            "AsyncStart" => format!("{{\"AsyncStart\":{{\"name\":\"poll\",\"id\":{},\"parent_id\":1,\"ts\":{},\"metadata\":null}}}}", id, ts),
+            "AsyncEnd" => format!("{{\"AsyncEnd\":{{\"id\":{},\"ts\":{},\"outcome\":\"Success\"}}}}", id, ts),
+            _ => format!("{{\"{}\":{{\"id\":{},\"ts\":{}}}}}", kind, id, ts),
+        }
+    }
+
+    #[test]
+    fn test_event_window() {
+        let mut tree = EventTree::new(vec![]);
+        tree.add(buf_thread_start("Graydon", 1)).expect("add root");
+        tree.add(buf_async("AsyncStart", 2, 1)).expect("add span");
+        for &(on, off) in &[(1, 2), (3, 4), (100, 101)] {
+            tree.add(buf_async("AsyncOnCPU", 2, on)).expect("add poll");
+            tree.add(buf_async("AsyncOffCPU", 2, off)).expect("add poll");
+        }
+        tree.add(buf_async("AsyncEnd", 2, 102)).expect("end span");
+        tree.add(buf_async("AsyncStart", 3, 50)).expect("add span");
+        tree.add(buf_async("AsyncEnd", 3, 51)).expect("end span");
+        tree.add(r#"{"Counter":{"name":"depth","value":1,"ts":{"secs":10,"nanos":0}}}"#.to_string()).expect("add counter");
+
+        // Everything that overlaps the window, whole spans and polls included.
+        let window = Window { start: Duration::from_micros(2500), end: Some(Duration::from_micros(50500)), max_events: 100 };
+        let events = tree.window(&window);
+        assert_eq!(events.len(), 7);
+        assert!(events[2].starts_with("{\"AsyncOnCPU\"") && events[2].contains("\"nanos\":3000000"));
+        assert!(events[3].starts_with("{\"AsyncOffCPU\""));
+        assert!(events[4].contains("\"id\":3"));
+
+        // Added after the window was worked out, and sent with it next time.
+        tree.add(buf_wakeup(1, 2, 5_000_000)).expect("add wakeup");
+        tree.add(buf_async("AsyncStart", 4, 20)).expect("add span");
+        tree.add(buf_async("AsyncEnd", 4, 21)).expect("end span");
+        let events = tree.window(&window);
+        assert_eq!(events.len(), 10);
+        assert!(events[4].starts_with("{\"Wakeup\""));
+        assert!(events[5].contains("\"id\":4"));
+
+        // Too many events for the whole trace, so polls are merged and the short span dropped.
+        let message: ClientMessage = serde_json::from_str(r#"{"SetWindow":{"max_events":10}}"#).expect("parse message");
+        let window = match message {
+            ClientMessage::SetWindow(Some(window)) => window,
+            _ => panic!("wrong message"),
+        };
+        assert_eq!(window.start, Duration::from_secs(0));
+        let events = tree.window(&window);
+        assert_eq!(events.len(), 5);
+        let busy: serde_json::Value = serde_json::from_str(&events[2]).unwrap();
+        let intervals = busy["Busy"]["intervals"].as_array().unwrap();
+        assert_eq!(intervals.len(), 2);
+        assert_eq!(intervals[0][0]["nanos"], 1000000);
+        assert_eq!(intervals[0][1]["nanos"], 4000000);
+        // The wakeup is counted rather than sent.
+        assert_eq!(busy["Busy"]["edges"], 1);
+        assert!(events.iter().all(|event| !event.starts_with("{\"Wakeup\"")));
+        assert!(events.iter().all(|event| !event.contains("\"id\":3")));
+
+        // And never more than asked for.
+        assert_eq!(tree.window(&Window { max_events: 3, ..window }).len(), 3);
+    }
 }
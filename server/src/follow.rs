@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::fs::{self, File, Metadata};
+use std::io::{self, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use notify::{self, RecommendedWatcher, RecursiveMode, Watcher};
+
+use reader::TraceReader;
+
+// How much of the start of a file to remember, to tell if it's been rewritten.  The header line,
+// with the process and start time, fits in this, so a rerun never starts the same way.
+const HEAD_BYTES: u64 = 256;
+
+/// What's happened to a followed trace file since we last looked.
+pub enum Change {
+    /// Complete lines appended to the file, without their newlines.
+    Lines(Vec<String>),
+    /// The file was truncated or written over, so everything read from it so far is gone.
+    Truncated,
+}
+
+/// Reads a trace file as it grows, carrying on with the new file if it's rotated or replaced.
+pub struct Follower {
+    path: PathBuf,
+    reader: TraceReader,
+    // Another handle on the file `reader` reads, to see how far into it we are.
+    file: File,
+    id: Option<FileId>,
+    // The first `HEAD_BYTES` of the file, as far as it had been written when we last looked.
+    head: Vec<u8>,
+    // The start of a line that hasn't been finished yet.
+    fragment: String,
+}
+
+impl Follower {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let id = file_id(&file.metadata()?);
+        Ok(Follower {
+            path: path.to_path_buf(),
+            reader: TraceReader::new(file.try_clone()?, path)?,
+            file,
+            id,
+            head: read_head(path)?,
+            fragment: String::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn poll(&mut self) -> io::Result<Change> {
+        let mut lines = self.read_lines()?;
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // Rotated away, with nothing in its place yet.
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Change::Lines(lines)),
+            Err(e) => return Err(e),
+        };
+        if file_id(&metadata) != self.id {
+            // Rotated or replaced.  We've read what was left of the old file, so carry on from
+            // the start of the new one.
+            *self = Follower::open(&self.path)?;
+            lines.extend(self.read_lines()?);
+        } else {
+            // Truncated, or truncated and written again past where we'd got to, e.g. by running
+            // the same command again, in which case it starts differently.
+            let head = read_head(&self.path)?;
+            let common = head.len().min(self.head.len());
+            if metadata.len() < (&self.file).stream_position()? || head[..common] != self.head[..common] {
+                return Ok(Change::Truncated);
+            }
+            self.head = head;
+        }
+        Ok(Change::Lines(lines))
+    }
+
+    fn read_lines(&mut self) -> io::Result<Vec<String>> {
+        let mut lines = vec![];
+        while self.reader.read_line(&mut self.fragment)? > 0 && self.fragment.ends_with('\n') {
+            self.fragment.pop();
+            lines.push(std::mem::take(&mut self.fragment));
+        }
+        Ok(lines)
+    }
+}
+
+fn read_head(path: &Path) -> io::Result<Vec<u8>> {
+    let mut head = vec![];
+    File::open(path)?.take(HEAD_BYTES).read_to_end(&mut head)?;
+    Ok(head)
+}
+
+#[cfg(unix)]
+type FileId = (u64, u64);
+
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+// Without inode numbers we can't tell a replaced file from the original.
+#[cfg(not(unix))]
+type FileId = ();
+
+#[cfg(not(unix))]
+fn file_id(_: &Metadata) -> Option<FileId> {
+    None
+}
+
+/// Send on `wake` whenever one of `paths` may have changed: been written to, truncated, or
+/// replaced.  We watch their directories rather than the files themselves, so that we hear
+/// about new files put in their place.  Stops when the returned watcher is dropped.
+pub fn watch(paths: &[PathBuf], wake: Sender<()>) -> notify::Result<RecommendedWatcher> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::raw_watcher(tx)?;
+    let mut dirs = HashSet::new();
+    let mut names = HashSet::new();
+    for path in paths {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        if dirs.insert(dir) {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+        names.extend(path.file_name().map(|name| name.to_os_string()));
+    }
+    thread::spawn(move || {
+        for event in rx {
+            let ours = event.path.as_ref()
+                .and_then(|path| path.file_name())
+                .is_none_or(|name| names.contains(name));
+            if ours && wake.send(()).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use super::{watch, Change, Follower};
+
+    fn lines(change: Change) -> Vec<String> {
+        match change {
+            Change::Lines(lines) => lines,
+            Change::Truncated => panic!("unexpected truncation"),
+        }
+    }
+
+    #[test]
+    fn test_follow_rotate_and_truncate() {
+        let dir = env::temp_dir().join("cyclotron_test_follow");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("trace.log");
+        fs::write(&path, "one\ntw").unwrap();
+
+        let (wake_tx, wake_rx) = mpsc::channel();
+        let _watcher = watch(std::slice::from_ref(&path), wake_tx).unwrap();
+        let mut follower = Follower::open(&path).unwrap();
+        assert_eq!(lines(follower.poll().unwrap()), vec!["one"]);
+
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"o\n").unwrap();
+        wake_rx.recv_timeout(Duration::from_secs(5)).expect("no notification for write");
+        assert_eq!(lines(follower.poll().unwrap()), vec!["two"]);
+
+        // Rotated: the rest of the old file, then the new one from the start.
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"three\n").unwrap();
+        fs::rename(&path, dir.join("trace.log.1")).unwrap();
+        fs::write(&path, "four\n").unwrap();
+        wake_rx.recv_timeout(Duration::from_secs(5)).expect("no notification for rotation");
+        assert_eq!(lines(follower.poll().unwrap()), vec!["three", "four"]);
+
+        // Truncated in place.
+        fs::write(&path, "").unwrap();
+        assert!(matches!(follower.poll().unwrap(), Change::Truncated));
+
+        // Truncated and written again past where we'd got to before we looked.
+        let mut follower = Follower::open(&path).unwrap();
+        fs::write(&path, "{\"run\":1}\nfive\n").unwrap();
+        assert_eq!(lines(follower.poll().unwrap()), vec!["{\"run\":1}", "five"]);
+        fs::write(&path, "{\"run\":2}\nsix\nseven\n").unwrap();
+        assert!(matches!(follower.poll().unwrap(), Change::Truncated));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
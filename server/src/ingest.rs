@@ -0,0 +1,257 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+use std::net::{SocketAddr, TcpListener};
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread;
+use serde_json;
+
+use event::TraceEvent;
+
+// How many lines to keep by default, about a few hundred megabytes' worth.
+const DEFAULT_CAPACITY: usize = 1 << 20;
+
+/// An event received from a process logging over the network.
+#[derive(Clone, Debug)]
+pub struct IngestedLine {
+    // Which process it came from, as "hostname:pid" once we've seen its header.
+    pub source: String,
+    // Which connection it came in on.  Names are interned per connection, and one process can
+    // have several.
+    pub conn: usize,
+    pub line: String,
+}
+
+#[derive(Default)]
+struct Lines {
+    // The newest lines, at most `capacity` of them.
+    recent: VecDeque<IngestedLine>,
+    // How many lines have been dropped from the front of `recent`.
+    dropped: usize,
+    // Headers and name definitions dropped from `recent`, which later lines still need.
+    preamble: Vec<IngestedLine>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct Shared {
+    lines: Mutex<Lines>,
+    // told whenever a line arrives
+    subscribers: Mutex<Vec<Sender<()>>>,
+    connections: AtomicUsize,
+}
+
+/// Events streamed in by `NetLogger`s, kept in the order they arrived so each viewer can catch up
+/// and then follow along.  Only the newest lines are kept, so a long-running server doesn't grow
+/// without limit.
+#[derive(Clone, Default)]
+pub struct Ingest {
+    shared: Arc<Shared>,
+}
+
+impl Ingest {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Keep at most `lines` lines, besides headers and name definitions.
+    pub fn with_capacity(lines: usize) -> Self {
+        let ingest = Self::default();
+        ingest.shared.lines.lock().unwrap().capacity = lines;
+        ingest
+    }
+
+    /// Accept connections on `addr`: `unix:<path>` for a Unix domain socket, or a TCP address
+    /// like `127.0.0.1:3002`.
+    pub fn listen(&self, addr: &str) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            if let Some(path) = addr.strip_prefix("unix:") {
+                let _ = ::std::fs::remove_file(path);
+                let listener = UnixListener::bind(path)?;
+                let ingest = self.clone();
+                thread::spawn(move || {
+                    for (i, conn) in listener.incoming().filter_map(Result::ok).enumerate() {
+                        let ingest = ingest.clone();
+                        thread::spawn(move || ingest.receive(conn, format!("unix#{}", i)));
+                    }
+                });
+                return Ok(());
+            }
+        }
+        self.listen_tcp(addr).map(|_| ())
+    }
+
+    /// Accept connections on a TCP address, returning the address actually bound.
+    pub fn listen_tcp(&self, addr: &str) -> io::Result<SocketAddr> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let ingest = self.clone();
+        thread::spawn(move || {
+            for conn in listener.incoming().filter_map(Result::ok) {
+                let peer = conn.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+                let ingest = ingest.clone();
+                thread::spawn(move || ingest.receive(conn, peer));
+            }
+        });
+        Ok(local_addr)
+    }
+
+    // Read lines until the connection closes.  A partial line at the end is dropped; the logger
+    // sends it again when it reconnects.
+    fn receive<R: Read>(&self, conn: R, peer: String) {
+        println!("Ingesting events from {}", peer);
+        let id = self.shared.connections.fetch_add(1, Ordering::SeqCst);
+        let mut source = peer.clone();
+        let mut reader = BufReader::new(conn);
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) if !line.ends_with('\n') => break,
+                Ok(_) => (),
+                Err(e) => {
+                    println!("Ingest from {} failed: {:?}", peer, e);
+                    break;
+                },
+            }
+            line.pop();
+            if let Some(label) = header_source(&line) {
+                source = label;
+            }
+            self.shared.lines.lock().unwrap().push(IngestedLine { source: source.clone(), conn: id, line });
+            self.shared.subscribers.lock().unwrap().retain(|tx| tx.send(()).is_ok());
+        }
+        println!("Done ingesting events from {}", peer);
+    }
+
+    /// The lines that have arrived since the first `start`, and the `start` to ask with next
+    /// time.  If some of those have been dropped, the headers and name definitions among them
+    /// come first, so the rest can still be read.
+    pub fn since(&self, start: usize) -> (Vec<IngestedLine>, usize) {
+        let lines = self.shared.lines.lock().unwrap();
+        let mut result = vec![];
+        if start < lines.dropped {
+            result.extend(lines.preamble.iter().cloned());
+        }
+        let skip = start.saturating_sub(lines.dropped);
+        result.extend(lines.recent.iter().skip(skip).cloned());
+        (result, lines.dropped + lines.recent.len())
+    }
+
+    /// Send on `tx` whenever a line arrives, until its receiver is dropped.
+    pub fn subscribe(&self, tx: Sender<()>) {
+        self.shared.subscribers.lock().unwrap().push(tx);
+    }
+}
+
+impl Lines {
+    fn push(&mut self, line: IngestedLine) {
+        self.recent.push_back(line);
+        while self.recent.len() > self.capacity {
+            let line = self.recent.pop_front().unwrap();
+            self.dropped += 1;
+            if line.line.starts_with("{\"Header\"") || line.line.starts_with("{\"NameDef\"") {
+                self.preamble.push(line);
+            }
+        }
+    }
+}
+
+// Label events from a process by its host and pid, so that a process that reconnects keeps the
+// same label.
+fn header_source(line: &str) -> Option<String> {
+    if !line.starts_with("{\"Header\"") {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(TraceEvent::Header { hostname, pid, .. }) => {
+            Some(format!("{}:{}", hostname.unwrap_or_else(|| "?".to_string()), pid))
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use cyclotron_backend::{Logger, Name, TraceEvent};
+    use cyclotron_backend::net::NetLogger;
+    use serde_json;
+    use event::EventTree;
+    use super::Ingest;
+
+    #[test]
+    fn test_ingest_net_logger() {
+        let ingest = Ingest::new();
+        let (tx, rx) = mpsc::channel();
+        ingest.subscribe(tx);
+        let addr = ingest.listen_tcp("127.0.0.1:0").unwrap();
+        let mut logger = NetLogger::tcp(addr.to_string());
+        logger.write(TraceEvent::Counter { name: Name::new("depth"), value: 7, ts: Duration::from_secs(1) });
+        drop(logger);
+
+        let mut lines = vec![];
+        while lines.len() < 3 {
+            rx.recv_timeout(Duration::from_secs(5)).expect("nothing ingested");
+            lines = ingest.since(0).0;
+        }
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].line.starts_with("{\"Header\""));
+        assert!(lines[0].source.ends_with(&format!(":{}", ::std::process::id())));
+        assert!(lines[1].line.starts_with("{\"NameDef\""));
+        assert!(lines[1..].iter().all(|line| line.source == lines[0].source));
+
+        let mut tree = EventTree::new(vec![]);
+        for line in lines {
+            tree.set_connection(line.source, line.conn);
+            tree.add(line.line).unwrap();
+        }
+        assert_eq!(tree.filter().len(), 2);
+    }
+
+    #[test]
+    fn test_ingest_capacity() {
+        let ingest = Ingest::with_capacity(2);
+        let (tx, rx) = mpsc::channel();
+        ingest.subscribe(tx);
+        let addr = ingest.listen_tcp("127.0.0.1:0").unwrap();
+
+        // Two loggers in one process, taking turns, each with names of its own.
+        let mut loggers = [NetLogger::tcp(addr.to_string()), NetLogger::tcp(addr.to_string())];
+        let sends = [(0, "first", 3), (1, "second", 6), (0, "first", 7), (1, "second", 8)];
+        for (ts, &(i, name, total)) in sends.iter().enumerate() {
+            let counter = TraceEvent::Counter { name: Name::new(name), value: ts as u64, ts: Duration::from_secs(ts as u64) };
+            loggers[i].write(counter);
+            loggers[i].flush();
+            while ingest.since(0).1 < total {
+                rx.recv_timeout(Duration::from_secs(5)).expect("nothing ingested");
+            }
+        }
+
+        // Only the last two lines are kept, but the headers and names they need come along.
+        let (lines, next) = ingest.since(0);
+        assert_eq!(next, 8);
+        assert_eq!(lines.len(), 6);
+        assert_ne!(lines[0].conn, lines[5].conn);
+        assert!(ingest.since(next).0.is_empty());
+        assert_eq!(ingest.since(7).0.len(), 1);
+
+        let mut tree = EventTree::new(vec![]);
+        for line in lines {
+            tree.set_connection(line.source, line.conn);
+            tree.add(line.line).unwrap();
+        }
+        let counters = tree.filter().into_iter()
+            .filter_map(|event| match serde_json::from_str(&event) {
+                Ok(TraceEvent::Counter { name, value, .. }) => Some((name.as_str().unwrap().to_string(), value)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counters, vec![("first".to_string(), 2), ("second".to_string(), 3)]);
+    }
+}
@@ -6,57 +6,87 @@ extern crate serde_derive;
 extern crate websocket;
 extern crate futures;
 extern crate failure;
+extern crate flate2;
+extern crate notify;
+extern crate serde;
+#[macro_use]
 extern crate serde_json;
+extern crate tokio_core;
+extern crate zstd;
 
+mod api;
+mod assets;
 mod event;
-use event::EventTree;
+mod follow;
+mod ingest;
+mod reader;
+mod traces;
+use event::{ClientMessage, EventTree, Filter, Window};
+use follow::{Change, Follower};
+use ingest::{Ingest, IngestedLine};
+use traces::{query_values, Catalog};
 
-use std::fs::{
-    File,
-};
 use std::net::{
-    Ipv4Addr,
-    SocketAddr,
-    SocketAddrV4,
+    TcpListener,
     TcpStream,
+    ToSocketAddrs,
 };
-use std::io::{
-    self,
-    BufRead,
-    BufReader,
-    Read,
-};
-use std::time::Duration;
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::sync::mpsc::{RecvTimeoutError, TryRecvError};
+use std::process;
 use std::thread;
+use std::time::{Duration, Instant};
 use failure::Error;
 use futures::{
     future,
 };
-use futures::future::Future;
+use futures::future::{Future, IntoFuture};
+use futures::sync::oneshot;
 use docopt::Docopt;
 use hyper::{
     Method,
     StatusCode,
 };
+use hyper::header::{ContentType, IfNoneMatch};
 use hyper::server::{
     Http,
-    NewService,
     Request,
     Response,
     Service,
 };
-use websocket::Message;
+use tokio_core::reactor::{Core, Remote};
+use websocket::{Message, OwnedMessage};
+use websocket::receiver::Reader;
+use websocket::sender::Writer;
 use websocket::server::upgrade::WsUpgrade;
-use websocket::server::upgrade::sync::Buffer;
-use websocket::sync::Server;
+use websocket::server::upgrade::sync::{Buffer, IntoWs};
+
+/// Where viewers connect to the websocket, on the same port as everything else.
+const WS_PATH: &str = "/ws";
+// How long a new connection has to start its request, and a websocket to finish its handshake.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+// How long to wait for more of a request line that has arrived in pieces.
+const PEEK_INTERVAL: Duration = Duration::from_millis(5);
+// The reply to requests for `WS_PATH` that aren't websocket upgrades.
+const BAD_REQUEST: &[u8] = b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+// Tells the frontend to throw away what it has, before the trace is sent again from the start.
+const RESET: &str = "{\"Reset\":null}";
+// How often a viewer looking at a window of a growing trace is sent it again.
+const WINDOW_REFRESH: Duration = Duration::from_secs(1);
 
 struct Inner {
-    trace_path: PathBuf,
-    frontend_dir: PathBuf,
-    grep_goals: Vec<String>,
-    hide_wakeups_from: Vec<String>,
+    traces: Catalog,
+    // where to serve the frontend from, instead of the copy compiled in
+    frontend_dir: Option<PathBuf>,
+    // what viewers see until they ask for something else
+    filter: Filter,
+    // events streamed in over the network, if we're listening for any
+    ingest: Option<Ingest>,
+    // the last trace loaded for the query API
+    api_cache: Option<api::Cache>,
 }
 
 #[derive(Clone)]
@@ -65,112 +95,316 @@ struct CyclotronServer {
 }
 
 impl CyclotronServer {
-    fn new(args: &Args) -> Self {
+    fn new(args: &Args) -> io::Result<Self> {
+        let ingest = if args.flag_ingest.is_empty() {
+            None
+        } else {
+            let ingest = Ingest::new();
+            for addr in &args.flag_ingest {
+                ingest.listen(addr)?;
+                println!("Listening for events on {}", addr);
+            }
+            Some(ingest)
+        };
         let inner = Inner {
-            trace_path: PathBuf::from(&args.flag_trace),
-            frontend_dir: PathBuf::from("../frontend"),
-            grep_goals: args.flag_grep.clone(),
-            hide_wakeups_from: args.flag_hide_wakeups.clone(),
+            traces: Catalog::new(args.flag_trace.iter().map(PathBuf::from).collect(),
+                                 args.flag_dir.as_ref().map(PathBuf::from)),
+            frontend_dir: args.flag_assets.as_ref().map(PathBuf::from),
+            filter: Filter {
+                goals: args.flag_grep.clone(),
+                hide_wakeups_from: args.flag_hide_wakeups.clone(),
+            },
+            ingest,
+            api_cache: None,
         };
-        Self { inner: Arc::new(Mutex::new(inner)) }
+        Ok(Self { inner: Arc::new(Mutex::new(inner)) })
     }
 
-    fn serve_frontend(&self, p: &str) -> Response {
-        let inner = self.inner.lock().unwrap();
-        let path = inner.frontend_dir.clone().join(p.trim_start_matches("/"));
+    fn serve_frontend(&self, p: &str, if_none_match: Option<&IfNoneMatch>) -> Response {
+        let frontend_dir = self.inner.lock().unwrap().frontend_dir.clone();
+        assets::serve(frontend_dir.as_deref(), p, if_none_match)
+    }
 
+    // The traces viewers can ask for, as JSON.
+    fn list_traces(&self) -> Response {
+        let traces = self.inner.lock().unwrap().traces.clone();
         let mut response = Response::new();
-        let file = match File::open(&path) {
-            Ok(f) => f,
-            Err(e) => {
-                println!("Failed to open {:?}: {:?}", path, e);
-                response.set_status(StatusCode::NotFound);
-                return response;
+        match traces.list().map_err(Error::from).and_then(|list| Ok(serde_json::to_string(&list)?)) {
+            Ok(body) => {
+                response.headers_mut().set(ContentType::json());
+                response.set_body(body);
             },
-        };
-        match Read::bytes(file).collect::<Result<Vec<u8>, _>>() {
-            Ok(bytes) => response.set_body(bytes),
             Err(e) => {
-                println!("Failed to read file {:?}", e);
+                println!("Failed to list traces: {:?}", e);
                 response.set_status(StatusCode::InternalServerError);
-                return response;
             },
         }
         response
     }
 
+    fn query_api(&self, path: &str, query: &str) -> Box<dyn Future<Item=Response, Error=hyper::Error>> {
+        let names = query_values(&format!("?{}", query), "trace");
+        let resolved = {
+            let inner = self.inner.lock().unwrap();
+            inner.traces.resolve(&names).map(|paths| (paths, inner.api_cache.clone()))
+        };
+        let (paths, mut cache) = match resolved {
+            Ok(resolved) => resolved,
+            Err(reason) => return Box::new(future::ok(api_response(api::error(StatusCode::NotFound, reason)))),
+        };
+        // Loading a big trace takes a while, so do it on its own thread rather than holding up
+        // every other request on hyper's.  If it panics, the sender is dropped and we say so.
+        let (tx, rx) = oneshot::channel();
+        let (server, path, query) = (self.clone(), path.to_string(), query.to_string());
+        thread::spawn(move || {
+            let answer = api::load(&mut cache, &paths).map(|tree| api::handle(&tree, &path, &query));
+            if answer.is_ok() {
+                server.inner.lock().unwrap().api_cache = cache;
+            }
+            let _ = tx.send(answer);
+        });
+        let response = rx.then(|answer| {
+            let answer = match answer {
+                Ok(Ok(answer)) => answer,
+                Ok(Err(e)) => api::error(StatusCode::InternalServerError, format!("couldn't load trace: {}", e)),
+                Err(..) => api::error(StatusCode::InternalServerError, "couldn't answer query".to_string()),
+            };
+            Ok(api_response(answer))
+        });
+        Box::new(response)
+    }
+
     fn stream(&self, conn: WsUpgrade<TcpStream, Option<Buffer>>) -> Result<(), Error> {
         if !conn.protocols().contains(&"cyclotron-ws".into()) {
             conn.reject().map_err(|(_, e)| e)?;
             return Ok(());
         }
-        let mut client = conn.use_protocol("cyclotron-ws")
+        // Viewers pick traces by name, e.g. `ws://host:port/?trace=run.log`, and can start off
+        // with their own filter with `&grep=<name>` and `&hide_wakeups=<name>`, or with a cap on
+        // how many events to send with `&max_events=<n>`.
+        let uri = conn.uri();
+        let names = query_values(&uri, "trace");
+        let client = conn.use_protocol("cyclotron-ws")
             .accept()
             .map_err(|(_, e)| e)?;
         println!("New connection from {:?}", client.peer_addr()?);
+        let (receiver, mut client) = client.split()?;
 
-        let (mut file, grep_goals, hide_wakeups_from) = {
+        let (traces, mut filter, ingest) = {
             let inner = self.inner.lock().unwrap();
-            let file = BufReader::new(File::open(&inner.trace_path)?);
-            (file, inner.grep_goals.clone(), inner.hide_wakeups_from.clone())
+            (inner.traces.clone(), inner.filter.clone(), inner.ingest.clone())
         };
-
-        // First, push the whole file over the socket
-        let mut events = EventTree::new_hide_wakeups(grep_goals, hide_wakeups_from);
-        let mut fragment = loop {
-            let mut buf = String::new();
-            let num_read = file.read_line(&mut buf)?;
-
-            if num_read == 0 || !buf.ends_with("\n") {
-                break buf;
-            } else {
-                buf.pop();
-                if let Err((e, buf)) = events.add(buf) {
-                    println!("warning: couldn't process event '{}': {:?}", buf, e);
-                }
-            }
+        let (goals, hide_wakeups_from) = (query_values(&uri, "grep"), query_values(&uri, "hide_wakeups"));
+        if !goals.is_empty() || !hide_wakeups_from.is_empty() {
+            filter = Filter { goals, hide_wakeups_from };
+        }
+        // Only what's in this part of the trace, if the viewer's asked for a part.
+        let mut window = query_values(&uri, "max_events").first()
+            .and_then(|n| n.parse().ok())
+            .map(|max_events| Window { start: Duration::from_secs(0), end: None, max_events });
+        let paths = match traces.resolve(&names) {
+            Ok(paths) => paths,
+            Err(reason) => {
+                println!("Rejecting connection: {}", reason);
+                client.send_message(&Message::close_because(1008, reason))?;
+                return Ok(());
+            },
         };
 
-        for event in events.filter() {
-            //let x: TraceEvent = serde_json::from_str(&event)?;
-            //println!("Read {:?}", x);
-            client.send_message(&Message::text(event))?;
+        // Wake up whenever a trace file changes, an event is ingested, or the viewer sends us
+        // something.
+        let (wake_tx, wake_rx) = mpsc::channel();
+        let _watcher = follow::watch(&paths, wake_tx.clone())?;
+        if let Some(ref ingest) = ingest {
+            ingest.subscribe(wake_tx.clone());
         }
+        let (message_tx, message_rx) = mpsc::channel();
+        thread::spawn(move || receive(receiver, message_tx, wake_tx));
 
-        loop {
-            let num_read = file.read_line(&mut fragment)?;
+        let labelled = paths.len() > 1 || ingest.is_some();
+        'reset: loop {
+            let mut followers = paths.iter()
+                .map(|p| Follower::open(p))
+                .collect::<Result<Vec<_>, io::Error>>()?;
 
-            if num_read == 0 || !fragment.ends_with("\n") {
-                // Just poll, sigh.
-                thread::sleep(Duration::from_millis(250));
-                continue;
+            // First, push the whole file over the socket, along with anything ingested so far.
+            // With several traces, label each one's threads with its file name (or the process it
+            // came from over the network) and stitch spans under their remote parents.
+            let mut events = EventTree::new_hide_wakeups(filter.goals.clone(), filter.hide_wakeups_from.clone());
+            for follower in &mut followers {
+                if labelled {
+                    events.set_source(Some(trace_label(follower.path())));
+                }
+                let lines = match follower.poll()? {
+                    Change::Lines(lines) => lines,
+                    Change::Truncated => continue 'reset,
+                };
+                for line in lines {
+                    if let Err((e, buf)) = events.add(line) {
+                        println!("warning: couldn't process event '{}': {:?}", buf, e);
+                    }
+                }
             }
-            // TODO allow on-the-fly filtering and reenable this feature
-            panic!("streaming not supported yet");
+            let mut num_ingested = 0;
+            if let Some(ref ingest) = ingest {
+                let (lines, next) = ingest.since(0);
+                num_ingested = next;
+                for IngestedLine { source, conn, line } in lines {
+                    events.set_connection(source, conn);
+                    if let Err((e, buf)) = events.add(line) {
+                        println!("warning: couldn't process event '{}': {:?}", buf, e);
+                    }
+                }
+            }
+            events.stitch();
+            if let Some(reason) = events.incompatible() {
+                println!("Rejecting connection: {}", reason);
+                client.send_message(&Message::close_because(1003, reason))?;
+                return Ok(());
+            }
+            send_all(&mut events, &mut client, &window)?;
+
+            // Then follow along, sending on whatever passes the filter as it's appended.  A viewer
+            // looking at a window gets the whole window again instead, at most every
+            // `WINDOW_REFRESH`.  If a file is truncated, the viewer starts over from scratch.
+            let (mut stale, mut refreshed) = (false, Instant::now());
+            loop {
+                for follower in &mut followers {
+                    let lines = match follower.poll()? {
+                        Change::Lines(lines) => lines,
+                        Change::Truncated => {
+                            println!("{} was truncated; starting over", follower.path().display());
+                            client.send_message(&Message::text(RESET))?;
+                            continue 'reset;
+                        },
+                    };
+                    if labelled {
+                        events.set_source(Some(trace_label(follower.path())));
+                    }
+                    stale |= window.is_some() && !lines.is_empty();
+                    for line in lines {
+                        send_live(&mut events, &mut client, line, &window)?;
+                    }
+                }
+                if let Some(ref ingest) = ingest {
+                    let (lines, next) = ingest.since(num_ingested);
+                    num_ingested = next;
+                    stale |= window.is_some() && !lines.is_empty();
+                    for IngestedLine { source, conn, line } in lines {
+                        events.set_connection(source, conn);
+                        send_live(&mut events, &mut client, line, &window)?;
+                    }
+                }
+                if stale && refreshed.elapsed() >= WINDOW_REFRESH {
+                    client.send_message(&Message::text(RESET))?;
+                    send_all(&mut events, &mut client, &window)?;
+                    stale = false;
+                    refreshed = Instant::now();
+                }
 
-            #[allow(unreachable_code)]
-            {
-                fragment.pop();
-                // let event: TraceEvent = serde_json::from_str(&fragment)?;
-                // println!("Read {:?}", event);
-                client.send_message(&Message::text(fragment.as_str()))?;
+                if stale {
+                    let wait = WINDOW_REFRESH.saturating_sub(refreshed.elapsed());
+                    if let Err(RecvTimeoutError::Disconnected) = wake_rx.recv_timeout(wait) {
+                        return Ok(());
+                    }
+                } else if wake_rx.recv().is_err() {
+                    return Ok(());
+                }
+                // Handle a burst of notifications in one go.
+                while wake_rx.try_recv().is_ok() {}
 
-                fragment.clear();
+                // If the viewer's asked for a new filter or window, start it over with just what
+                // that shows.
+                let mut changed = false;
+                loop {
+                    match message_rx.try_recv() {
+                        Ok(ClientMessage::SetFilter(f)) => filter = f,
+                        Ok(ClientMessage::SetWindow(w)) => window = w,
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => return Ok(()),
+                    }
+                    changed = true;
+                }
+                if changed {
+                    println!("Showing {:?} in {:?}", filter, window);
+                    events.set_filter(&filter);
+                    client.send_message(&Message::text(RESET))?;
+                    send_all(&mut events, &mut client, &window)?;
+                    stale = false;
+                    refreshed = Instant::now();
+                }
             }
         }
+    }
+}
 
+// Send a viewer everything it's asked to see: the whole filtered trace, or just a window of it.
+fn send_all(events: &mut EventTree, client: &mut Writer<TcpStream>, window: &Option<Window>) -> Result<(), Error> {
+    let events = match *window {
+        Some(ref window) => events.window(window),
+        None => events.filter(),
+    };
+    for event in events {
+        //let x: TraceEvent = serde_json::from_str(&event)?;
+        //println!("Read {:?}", x);
+        client.send_message(&Message::text(event))?;
     }
+    Ok(())
 }
 
-impl NewService for CyclotronServer {
-    type Request = Request;
-    type Response = Response;
-    type Error = hyper::Error;
-    type Instance = Self;
+// Add an event to a viewer's tree, sending on whatever that makes visible, unless it's only
+// looking at a window, which is sent again as a whole.
+fn send_live(events: &mut EventTree, client: &mut Writer<TcpStream>, line: String, window: &Option<Window>) -> Result<(), Error> {
+    let added = match *window {
+        Some(_) => events.add(line).map(|()| vec![]),
+        None => events.add_live(line),
+    };
+    match added {
+        Ok(visible) => {
+            for event in visible {
+                client.send_message(&Message::text(event))?;
+            }
+        },
+        Err((e, buf)) => println!("warning: couldn't process event '{}': {:?}", buf, e),
+    }
+    Ok(())
+}
 
-    fn new_service(&self) -> Result<Self, io::Error> {
-        Ok(self.clone())
+// Read what a viewer sends until it goes away, passing on what it asks for.  Dropping
+// `messages` tells the stream the viewer's gone.
+fn receive(mut receiver: Reader<TcpStream>, messages: mpsc::Sender<ClientMessage>, wake: mpsc::Sender<()>) {
+    for message in receiver.incoming_messages() {
+        match message {
+            Ok(OwnedMessage::Text(text)) => match serde_json::from_str(&text) {
+                Ok(message) => {
+                    if messages.send(message).is_err() {
+                        return;
+                    }
+                    let _ = wake.send(());
+                },
+                Err(e) => println!("warning: ignoring message '{}': {}", text, e),
+            },
+            Ok(OwnedMessage::Close(..)) | Err(..) => break,
+            Ok(..) => (),
+        }
     }
+    drop(messages);
+    let _ = wake.send(());
+}
+
+// Label for a trace's threads when several traces are shown together.
+fn trace_label(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn api_response((status, body): (StatusCode, String)) -> Response {
+    let mut response = Response::new();
+    response.set_status(status);
+    response.headers_mut().set(ContentType::json());
+    response.set_body(body);
+    response
 }
 
 impl Service for CyclotronServer {
@@ -181,12 +415,25 @@ impl Service for CyclotronServer {
 
     fn call(&self, req: Request) -> Self::Future {
         match (req.method(), req.path()) {
+            (&Method::Get, p) if p.starts_with("/api/") => {
+                self.query_api(&p["/api".len()..], req.query().unwrap_or(""))
+            },
+            (&Method::Get, "/config") => {
+                let mut response = Response::new();
+                response.headers_mut().set(ContentType::json());
+                response.set_body(json!({ "websocket": WS_PATH }).to_string());
+                Box::new(future::ok(response))
+            },
+            (&Method::Get, "/traces") => {
+                let response = self.list_traces();
+                Box::new(future::ok(response))
+            },
             (&Method::Get, "/") => {
-                let response = self.serve_frontend("/src/index.html");
+                let response = self.serve_frontend("/src/index.html", req.headers().get());
                 Box::new(future::ok(response))
             },
             (&Method::Get, p) => {
-                let response = self.serve_frontend(p);
+                let response = self.serve_frontend(p, req.headers().get());
                 Box::new(future::ok(response))
             },
             _ => {
@@ -198,53 +445,294 @@ impl Service for CyclotronServer {
     }
 }
 
-const USAGE: &'static str = "
+const USAGE: &str = "
 Cyclotron trace server.
 
 Usage:
-   cyclotron-server --http=<port> --ws=<port> --trace=<path> [--grep=<name>...] [--hide-wakeups=<name>...]
+   cyclotron-server [--port=<port>] [--bind=<addr>] [--trace=<path>...] [--dir=<path>] [--ingest=<addr>...] [--grep=<name>...] [--hide-wakeups=<name>...] [--assets=<dir>]
    cyclotron-server (-h | --help)
 
 Options:
   -h --help              Show this screen.
-  --http=<port>          Port for HTTP server
-  --ws=<port>            Port for websocket server
-  --trace=<path>         Path to trace file to stream in
-  --grep=<name>          Show only these futures (& their descendants+ancestors)
-  --hide-wakeups=<name>  Hide wakeup arrows originating from these futures
+  --port=<port>          Port to serve the viewer on, websocket and all [default: 3000]
+  --bind=<addr>          Address to listen on; use 0.0.0.0 to let other machines connect
+                         [default: 127.0.0.1]
+  --trace=<path>         Path to trace file to stream in; repeat to stitch together traces
+                         from several processes
+  --dir=<path>           Also offer every trace in this directory; viewers pick one with
+                         ?trace=<name>, and /traces lists them all
+  --ingest=<addr>        Also show events streamed live from processes using `NetLogger`,
+                         listening on a TCP address (e.g. 127.0.0.1:3002) or unix:<path>;
+                         only the newest million or so events are kept
+  --grep=<name>          Show only these futures (& their descendants+ancestors); viewers can
+                         pick their own with ?grep=<name> or a SetFilter message
+  --hide-wakeups=<name>  Hide wakeup arrows originating from these futures; viewers can pick
+                         their own with ?hide_wakeups=<name>
+  --assets=<dir>         Serve the frontend from this directory (e.g. ../frontend while working
+                         on it) instead of the copy built into the server
 ";
 
 #[derive(Debug, Deserialize)]
 struct Args {
-    flag_http: u16,
-    flag_ws: u16,
-    flag_trace: String,
+    flag_port: u16,
+    flag_bind: String,
+    flag_trace: Vec<String>,
+    flag_dir: Option<String>,
+    flag_ingest: Vec<String>,
     flag_grep: Vec<String>,
     flag_hide_wakeups: Vec<String>,
+    flag_assets: Option<String>,
 }
 
 fn main() {
     let args: Args = Docopt::new(USAGE)
         .and_then(|d| d.deserialize())
         .unwrap_or_else(|e| e.exit());
-    let server = CyclotronServer::new(&args);
-    let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), args.flag_http));
+    let server = CyclotronServer::new(&args).unwrap_or_else(|e| {
+        eprintln!("Failed to listen for events: {}", e);
+        process::exit(1);
+    });
+    let addr = (args.flag_bind.as_str(), args.flag_port).to_socket_addrs().ok()
+        .and_then(|mut addrs| addrs.next())
+        .unwrap_or_else(|| {
+            eprintln!("Can't listen on {}:{}", args.flag_bind, args.flag_port);
+            process::exit(1);
+        });
+    let result = TcpListener::bind(addr).and_then(|listener| {
+        println!("Serving on http://{}", addr);
+        serve(server, listener)
+    });
+    if let Err(e) = result {
+        eprintln!("Failed to serve on {}: {}", addr, e);
+        process::exit(1);
+    }
+}
 
-    let cyclotron = server.clone();
+// Serve HTTP and the websocket on `listener`, forever.  Each connection gets a thread to see
+// which it wants; HTTP connections are then handed over to hyper, running on this thread.  Hyper
+// closes them after one request, so every request goes through `dispatch`.
+fn serve(server: CyclotronServer, listener: TcpListener) -> io::Result<()> {
+    let mut core = Core::new()?;
+    let remote = core.remote();
     thread::spawn(move || {
-        let cyclotron = cyclotron;
-        let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(0, 0, 0, 0), args.flag_ws));
-        let ws_server = Server::bind(addr).unwrap();
-
-        for connection in ws_server.filter_map(Result::ok) {
-            // Spawn a thread per connection
-            let cyclotron_ = cyclotron.clone();
-            thread::spawn(move || match cyclotron_.stream(connection) {
-                Ok(_) => (),
-                Err(e) => println!("Failed on stream: {:?}", e),
-            });
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let server = server.clone();
+            let remote = remote.clone();
+            thread::spawn(move || server.dispatch(stream, &remote));
         }
     });
+    core.run(future::empty::<(), io::Error>())
+}
+
+impl CyclotronServer {
+    fn dispatch(self, stream: TcpStream, remote: &Remote) {
+        let line = match peek_request_line(&stream) {
+            Ok(line) => line,
+            Err(e) => {
+                println!("Failed to read request from {:?}: {:?}", stream.peer_addr(), e);
+                return;
+            },
+        };
+        if is_websocket(&line) {
+            // The handshake checks the rest of the head, still under `HEAD_TIMEOUT`.
+            match stream.into_ws() {
+                Ok(upgrade) => {
+                    let result = upgrade.stream.set_read_timeout(None)
+                        .map_err(Error::from)
+                        .and_then(|()| self.stream(upgrade));
+                    if let Err(e) = result {
+                        println!("Failed on stream: {:?}", e);
+                    }
+                },
+                Err((mut stream, _, _, e)) => {
+                    println!("Bad websocket handshake: {:?}", e);
+                    let _ = stream.write_all(BAD_REQUEST);
+                },
+            }
+            return;
+        }
+        if let Err(e) = stream.set_read_timeout(None) {
+            println!("Failed to set up connection: {:?}", e);
+            return;
+        }
+        remote.spawn(move |handle| {
+            let mut http = Http::<hyper::Chunk>::new();
+            http.keep_alive(false);
+            tokio_core::net::TcpStream::from_stream(stream, handle)
+                .map_err(hyper::Error::from)
+                .into_future()
+                .and_then(move |io| http.serve_connection(io, self))
+                .map_err(|e| println!("HTTP connection failed: {:?}", e))
+        });
+    }
+}
+
+// Wait for a new connection's request line and return it, without taking it off the socket, so
+// whichever of hyper and the websocket handles it still sees the whole request.  The line can
+// arrive in pieces, so keep peeking until it's complete, the buffer's full, the client hangs up or
+// `HEAD_TIMEOUT` passes; `is_websocket` makes do with whatever we have by then.
+fn peek_request_line(stream: &TcpStream) -> io::Result<Vec<u8>> {
+    let deadline = Instant::now() + HEAD_TIMEOUT;
+    let mut buf = vec![0; 1024];
+    let mut seen = 0;
+    stream.set_read_timeout(Some(HEAD_TIMEOUT))?;
+    let n = loop {
+        // Blocks until there's something to see, after which it returns straight away, so wait a
+        // little between peeks rather than spinning until the rest of the line turns up.
+        let n = stream.peek(&mut buf)?;
+        let complete = n == 0 || n == buf.len() || buf[..n].windows(2).any(|w| w == b"\r\n");
+        if complete || Instant::now() >= deadline {
+            break n;
+        }
+        if n == seen {
+            thread::sleep(PEEK_INTERVAL);
+        }
+        seen = n;
+    };
+    buf.truncate(n);
+    Ok(buf)
+}
+
+// Whether a request line is for the websocket at `WS_PATH`.  The handshake itself is left to
+// `into_ws`, which turns away anything that isn't an upgrade.
+fn is_websocket(line: &[u8]) -> bool {
+    let line = String::from_utf8_lossy(line);
+    let path = line.lines().next()
+        .and_then(|line| line.strip_prefix("GET "))
+        .and_then(|rest| rest.split(' ').next())
+        .and_then(|target| target.split('?').next());
+    path == Some(WS_PATH)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::path::Path;
+    use std::thread;
+    use std::time::Duration;
+    use hyper::StatusCode;
+    use websocket::{ClientBuilder, OwnedMessage};
+    use futures::Future;
+    use super::{is_websocket, serve, Args, CyclotronServer};
+
+    #[test]
+    fn test_is_websocket() {
+        assert!(is_websocket(b"GET /ws?trace=a.log HTTP/1.1\r\nHost: x\r\nupgrade: WebSocket\r\n\r\n"));
+        assert!(is_websocket(b"GET /ws HTTP/1.1\r\n"));
+        assert!(!is_websocket(b"GET /wsx HTTP/1.1\r\nUpgrade: websocket\r\n\r\n"));
+        assert!(!is_websocket(b"POST /ws HTTP/1.1\r\n"));
+        assert!(!is_websocket(b"GET /bundle.js HTTP/1.1\r\n\r\n"));
+        assert!(!is_websocket(b""));
+    }
+
+    // A server for one trace, with a single thread in it.
+    fn server(trace: &Path) -> CyclotronServer {
+        fs::write(trace, "{\"ThreadStart\":{\"name\":\"main\",\"id\":1,\"ts\":{\"secs\":0,\"nanos\":0}}}\n").unwrap();
+        let args = Args {
+            flag_port: 0,
+            flag_bind: "127.0.0.1".to_string(),
+            flag_trace: vec![trace.display().to_string()],
+            flag_dir: None,
+            flag_ingest: vec![],
+            flag_grep: vec![],
+            flag_hide_wakeups: vec![],
+            flag_assets: None,
+        };
+        CyclotronServer::new(&args).unwrap()
+    }
+
+    #[test]
+    fn test_query_api() {
+        let trace = env::temp_dir().join("cyclotron_test_query_api.log");
+        let _ = fs::remove_dir(&trace);
+        let server = server(&trace);
+        assert_eq!(server.query_api("/roots", "").wait().unwrap().status(), StatusCode::Ok);
+
+        // A trace that can't be loaded is the server's problem, and doesn't stop it answering.
+        fs::remove_file(&trace).unwrap();
+        fs::create_dir(&trace).unwrap();
+        assert_eq!(server.query_api("/roots", "").wait().unwrap().status(), StatusCode::InternalServerError);
+        assert_eq!(server.query_api("/roots", "trace=missing.log").wait().unwrap().status(), StatusCode::NotFound);
+        fs::remove_dir(&trace).unwrap();
+    }
 
-    Http::new().bind(&addr, server).unwrap().run().unwrap();
+    #[test]
+    fn test_one_port() {
+        let trace = env::temp_dir().join("cyclotron_test_one_port.log");
+        let server = server(&trace);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(server, listener));
+
+        let mut http = TcpStream::connect(addr).unwrap();
+        http.write_all(b"GET /config HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n").unwrap();
+        let mut response = String::new();
+        http.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("{\"websocket\":\"/ws\"}"));
+
+        // Without asking, the connection is still closed after one request, so the next one
+        // comes through `dispatch` too.
+        let mut http = TcpStream::connect(addr).unwrap();
+        http.write_all(b"GET /config HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        http.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+
+        let mut http = TcpStream::connect(addr).unwrap();
+        http.write_all(b"GET /ws HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        http.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"));
+
+        // A request line that arrives in pieces still goes to the websocket.
+        let mut http = TcpStream::connect(addr).unwrap();
+        http.write_all(b"GET /w").unwrap();
+        thread::sleep(Duration::from_millis(50));
+        http.write_all(b"s HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        http.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 400"));
+
+        let mut client = ClientBuilder::new(&format!("ws://{}/ws", addr)).unwrap()
+            .add_protocol("cyclotron-ws")
+            .connect_insecure()
+            .unwrap();
+        match client.recv_message().unwrap() {
+            OwnedMessage::Text(text) => assert!(text.contains("ThreadStart")),
+            message => panic!("unexpected message {:?}", message),
+        }
+        fs::remove_file(&trace).unwrap();
+    }
+
+    #[test]
+    fn test_window_refresh() {
+        let trace = env::temp_dir().join("cyclotron_test_window_refresh.log");
+        let server = server(&trace);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(server, listener));
+
+        let mut client = ClientBuilder::new(&format!("ws://{}/ws?max_events=100", addr)).unwrap()
+            .add_protocol("cyclotron-ws")
+            .connect_insecure()
+            .unwrap();
+        client.stream_ref().set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        let mut recv = || match client.recv_message().unwrap() {
+            OwnedMessage::Text(text) => text,
+            message => panic!("unexpected message {:?}", message),
+        };
+        assert!(recv().contains("\"main\""));
+
+        // A viewer looking at a window is sent it again as the trace grows.
+        let mut file = fs::OpenOptions::new().append(true).open(&trace).unwrap();
+        file.write_all(b"{\"ThreadStart\":{\"name\":\"second\",\"id\":2,\"ts\":{\"secs\":1,\"nanos\":0}}}\n").unwrap();
+        assert_eq!(recv(), "{\"Reset\":null}");
+        assert!(recv().contains("\"main\""));
+        assert!(recv().contains("\"second\""));
+        fs::remove_file(&trace).unwrap();
+    }
 }
@@ -0,0 +1,136 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use flate2::write::MultiGzDecoder;
+use zstd::stream::write::Decoder as ZstdDecoder;
+
+/// Reads lines from a trace file, decompressing `.gz` and `.zst` files on the fly.  Like
+/// `BufRead::read_line`, reading past the end returns zero until the file grows, so a trace can
+/// be followed while it's still being written.
+pub enum TraceReader {
+    Plain(BufReader<File>),
+    Compressed {
+        file: File,
+        decoder: Box<Decoder>,
+        // Decompressed bytes that haven't made up a whole line yet.
+        pending: Vec<u8>,
+    },
+}
+
+pub enum Decoder {
+    Gzip(MultiGzDecoder<Vec<u8>>),
+    Zstd(ZstdDecoder<'static, Vec<u8>>),
+}
+
+impl TraceReader {
+    /// Read `file`, decompressing it if `path` says it's compressed.
+    pub fn new(file: File, path: &Path) -> io::Result<Self> {
+        let decoder = match path.extension().and_then(|e| e.to_str()) {
+            Some("gz") => Decoder::Gzip(MultiGzDecoder::new(vec![])),
+            Some("zst") => Decoder::Zstd(ZstdDecoder::new(vec![])?),
+            _ => return Ok(TraceReader::Plain(BufReader::new(file))),
+        };
+        Ok(TraceReader::Compressed { file, decoder: Box::new(decoder), pending: vec![] })
+    }
+
+    /// Append the next line, including its newline, to `buf`, returning how many bytes were
+    /// added.  Plain files may return a partial line at the end of the file, like `BufRead`;
+    /// compressed ones only return whole lines, since a frame can end mid-character.
+    pub fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        let (file, decoder, pending) = match *self {
+            TraceReader::Plain(ref mut reader) => return reader.read_line(buf),
+            TraceReader::Compressed { ref mut file, ref mut decoder, ref mut pending } => {
+                (file, decoder, pending)
+            },
+        };
+        loop {
+            if let Some(end) = pending.iter().position(|&b| b == b'\n') {
+                let line = pending.drain(..end + 1).collect::<Vec<_>>();
+                let line = String::from_utf8(line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                buf.push_str(&line);
+                return Ok(line.len());
+            }
+            let mut chunk = [0; 64 * 1024];
+            let num_read = file.read(&mut chunk)?;
+            if num_read == 0 {
+                return Ok(0);
+            }
+            decoder.decode(&chunk[..num_read])?;
+            pending.append(decoder.output());
+        }
+    }
+}
+
+impl Decoder {
+    // Decompress as much of `data` as we can.  The decoders hold on to their output until
+    // they're flushed.
+    fn decode(&mut self, data: &[u8]) -> io::Result<()> {
+        match *self {
+            Decoder::Gzip(ref mut d) => d.write_all(data).and_then(|_| d.flush()),
+            Decoder::Zstd(ref mut d) => d.write_all(data).and_then(|_| d.flush()),
+        }
+    }
+
+    // Everything decompressed so far.
+    fn output(&mut self) -> &mut Vec<u8> {
+        match *self {
+            Decoder::Gzip(ref mut d) => d.get_mut(),
+            Decoder::Zstd(ref mut d) => d.get_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use super::TraceReader;
+
+    fn read_all(reader: &mut TraceReader) -> Vec<String> {
+        let mut lines = vec![];
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                return lines;
+            }
+            lines.push(line);
+        }
+    }
+
+    fn check_growing(name: &str, compress: fn(&[u8]) -> Vec<u8>) {
+        let path = env::temp_dir().join(name);
+        let first = compress(b"{\"a\":1}\n{\"b\":");
+        let second = compress(b"2}\n");
+        let mut file = fs::File::create(&path).unwrap();
+
+        // Half a frame gives nothing, and a frame ending mid-line holds that line back.
+        file.write_all(&first[..first.len() / 2]).unwrap();
+        let mut reader = TraceReader::new(fs::File::open(&path).unwrap(), &path).unwrap();
+        assert!(read_all(&mut reader).is_empty());
+        file.write_all(&first[first.len() / 2..]).unwrap();
+        assert_eq!(read_all(&mut reader), vec!["{\"a\":1}\n"]);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&second).unwrap();
+        assert_eq!(read_all(&mut reader), vec!["{\"b\":2}\n"]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_reader_gzip() {
+        check_growing("cyclotron_test_reader.log.gz", |data| {
+            let mut encoder = GzEncoder::new(vec![], Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        });
+    }
+
+    #[test]
+    fn test_reader_zstd() {
+        check_growing("cyclotron_test_reader.log.zst", |data| ::zstd::stream::encode_all(data, 0).unwrap());
+    }
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// A trace a viewer can ask for, as listed by `/traces`.
+#[derive(Clone, Debug, Serialize)]
+pub struct TraceInfo {
+    pub name: String,
+    pub size: u64,
+    // seconds since the Unix epoch
+    pub modified: Option<u64>,
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// The traces the server can show: the files given with `--trace`, and whatever is in the
+/// `--dir` directory, looked up afresh each time so new runs show up.
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    files: Vec<PathBuf>,
+    dir: Option<PathBuf>,
+}
+
+impl Catalog {
+    pub fn new(files: Vec<PathBuf>, dir: Option<PathBuf>) -> Self {
+        Catalog { files, dir }
+    }
+
+    /// Every trace, the `--trace` files first and then the directory's newest first.  Files that
+    /// can't be looked at, e.g. because they're missing or were rotated away while we were
+    /// listing, are left out rather than failing the whole list.
+    pub fn list(&self) -> io::Result<Vec<TraceInfo>> {
+        let mut result = self.files.iter()
+            .filter_map(|path| trace_info(path).ok())
+            .collect::<Vec<_>>();
+        if let Some(ref dir) = self.dir {
+            let mut in_dir = vec![];
+            for entry in fs::read_dir(dir)?.filter_map(Result::ok) {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if hidden || !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                    continue;
+                }
+                if let Ok(info) = trace_info(&entry.path()) {
+                    in_dir.push(info);
+                }
+            }
+            in_dir.sort_by(|a, b| b.modified.cmp(&a.modified).then_with(|| a.name.cmp(&b.name)));
+            result.extend(in_dir);
+        }
+        Ok(result)
+    }
+
+    /// The files to show for a viewer that asked for `names`.  Asking for nothing gets all the
+    /// `--trace` files stitched together, or failing that the newest trace in the directory.
+    /// Only listed traces can be asked for, so names can't wander off elsewhere on disk.
+    pub fn resolve(&self, names: &[String]) -> Result<Vec<PathBuf>, String> {
+        let traces = self.list().map_err(|e| format!("couldn't list traces: {}", e))?;
+        if names.is_empty() {
+            if !self.files.is_empty() {
+                return Ok(self.files.clone());
+            }
+            return Ok(traces.into_iter().take(1).map(|t| t.path).collect());
+        }
+        names.iter()
+            .map(|name| {
+                traces.iter()
+                    .find(|t| t.name == *name)
+                    .map(|t| t.path.clone())
+                    .ok_or_else(|| format!("no trace named {:?}", name))
+            })
+            .collect()
+    }
+}
+
+fn trace_info(path: &Path) -> io::Result<TraceInfo> {
+    let metadata = fs::metadata(path)?;
+    Ok(TraceInfo {
+        name: path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string()),
+        size: metadata.len(),
+        modified: metadata.modified().ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs()),
+        path: path.to_path_buf(),
+    })
+}
+
+/// Every value of `key` in the query string of `uri`, e.g. the traces in `/?trace=a&trace=b`.
+pub fn query_values(uri: &str, key: &str) -> Vec<String> {
+    let query = match uri.find('?') {
+        Some(i) => &uri[i + 1..],
+        None => return vec![],
+    };
+    query.split('&')
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(k), Some(v)) if percent_decode(k) == key => Some(percent_decode(v)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = s.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(b) = hex {
+                    out.push(b);
+                    i += 3;
+                    continue;
+                }
+                out.push(b'%');
+            },
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use super::{query_values, Catalog};
+
+    #[test]
+    fn test_query_values() {
+        assert_eq!(query_values("/?trace=a.log&x=1&trace=b%20c.log", "trace"), vec!["a.log", "b c.log"]);
+        assert!(query_values("/", "trace").is_empty());
+        assert_eq!(query_values("/?trace=100%", "trace"), vec!["100%"]);
+    }
+
+    #[test]
+    fn test_catalog() {
+        let dir = env::temp_dir().join("cyclotron_test_catalog");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("runs")).unwrap();
+        fs::write(dir.join("runs/a.log"), "").unwrap();
+        fs::write(dir.join("runs/.hidden"), "").unwrap();
+        fs::write(dir.join("pinned.log"), "{}\n").unwrap();
+
+        // A missing file is left out, without hiding the rest.
+        let catalog = Catalog::new(vec![dir.join("pinned.log"), dir.join("missing.log")], Some(dir.join("runs")));
+        let names: Vec<String> = catalog.list().unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["pinned.log", "a.log"]);
+        assert_eq!(catalog.resolve(&[]).unwrap(), vec![dir.join("pinned.log"), dir.join("missing.log")]);
+        assert_eq!(catalog.resolve(&["a.log".to_string()]).unwrap(), vec![dir.join("runs/a.log")]);
+        assert!(catalog.resolve(&["../pinned.log".to_string()]).is_err());
+
+        let catalog = Catalog::new(vec![], Some(dir.join("runs")));
+        assert_eq!(catalog.resolve(&[]).unwrap(), vec![dir.join("runs/a.log")]);
+        assert_eq!(Catalog::new(vec![], None).resolve(&[]).unwrap(), Vec::<PathBuf>::new());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}